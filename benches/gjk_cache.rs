@@ -0,0 +1,59 @@
+//! Compares cold GJK queries with ones warm-started from a [GjkCache].
+//!
+//! Run with `cargo bench --bench gjk_cache`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use auburn::col2d::*;
+
+const FRAMES: usize = 200_000;
+
+fn frame_transform(frame: usize) -> Transform2d {
+    // A character standing near the edge of a wide platform, jittering slightly every frame.
+    let t = frame as f32 * 0.001;
+    Transform2d::from_translation(Vec2::new(8.0 + 0.3 * t.sin(), 1.3 + 0.02 * (7.0 * t).sin()))
+        .with_angle(0.05 * (3.0 * t).cos())
+}
+
+fn run(a: &Box2d, b: &Poly2d, cache: Option<&mut GjkCache>) -> (Duration, usize) {
+    let mut iterations = 0;
+    let mut cache = cache;
+    let start = Instant::now();
+    for frame in 0..FRAMES {
+        let rel = frame_transform(frame);
+        let diff = LocalMinkowskiDiff2d::raw(a, b, &rel);
+        let mut stats = SolverStats::default();
+        let result = match cache.as_deref_mut() {
+            Some(cache) => cache.query::<false, false, _, _, _>(diff, &mut stats),
+            None => SdfvMinkowski2d::<false, false>::sdfv_minkowski_stats(&diff, &mut stats),
+        };
+        black_box(result);
+        iterations += stats.gjk_iterations;
+    }
+    (start.elapsed(), iterations)
+}
+
+fn main() {
+    let a = Box2d::with_halfdims(10.0, 0.25);
+    let b = Poly2d::regular(32, 1.0);
+
+    let (cold_time, cold_iterations) = run(&a, &b, None);
+    let mut cache = GjkCache::new();
+    let (warm_time, warm_iterations) = run(&a, &b, Some(&mut cache));
+
+    println!(
+        "cold: {:?} ({:.2} iterations/query)",
+        cold_time,
+        cold_iterations as f32 / FRAMES as f32
+    );
+    println!(
+        "warm: {:?} ({:.2} iterations/query)",
+        warm_time,
+        warm_iterations as f32 / FRAMES as f32
+    );
+    println!(
+        "speedup: {:.2}x",
+        cold_time.as_secs_f64() / warm_time.as_secs_f64()
+    );
+}
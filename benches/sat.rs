@@ -0,0 +1,68 @@
+//! Compares SAT with GJK/EPA for penetrating and separated rotated boxes.
+//!
+//! Run with `cargo bench --bench sat`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use auburn::col2d::*;
+
+const QUERIES: usize = 200_000;
+
+fn query_transform(query: usize) -> Transform2d {
+    // A crate tumbling over a platform, sometimes resting on it and sometimes in the air.
+    let t = query as f32 * 0.001;
+    Transform2d::from_translation(Vec2::new(3.0 * t.sin(), 1.2 + 0.4 * (3.0 * t).sin()))
+        .with_angle(2.0 * t)
+}
+
+fn run<A, B>(a: &A, b: &B, transforms: &[Transform2d], sat: bool) -> (Duration, usize)
+where
+    A: SdfvCommonRel2d<true, false, B, Transform2d> + ExtremePoint2d,
+    B: ExtremePoint2d,
+{
+    let mut penetrating = 0;
+    let start = Instant::now();
+    for rel in transforms {
+        let result = if sat {
+            SdfvCommonRel2d::<true, false, _, _>::sdfv_common_rel(a, b, rel)
+        } else {
+            let diff = LocalMinkowskiDiff2d::raw(a, b, rel);
+            SdfvMinkowski2d::<true, false>::sdfv_minkowski(&diff)
+        };
+        penetrating += black_box(result).penetration().is_some() as usize;
+    }
+    (start.elapsed(), penetrating)
+}
+
+fn compare<A, B>(name: &str, a: &A, b: &B)
+where
+    A: SdfvCommonRel2d<true, false, B, Transform2d> + ExtremePoint2d,
+    B: ExtremePoint2d,
+{
+    let transforms: Vec<Transform2d> = (0..QUERIES).map(query_transform).collect();
+    let (gjk_time, gjk_penetrating) = run(a, b, &transforms, false);
+    let (sat_time, sat_penetrating) = run(a, b, &transforms, true);
+    println!("{name}:");
+    println!("  gjk/epa: {gjk_time:?} ({gjk_penetrating} penetrating)");
+    println!("  sat:     {sat_time:?} ({sat_penetrating} penetrating)");
+    println!(
+        "  speedup: {:.2}x",
+        gjk_time.as_secs_f64() / sat_time.as_secs_f64()
+    );
+}
+
+fn main() {
+    let platform = Box2d::with_halfdims(4.0, 0.5);
+    let crate_box = Box2d::with_halfdims(0.5, 0.5);
+    compare("box v box", &platform, &crate_box);
+
+    let platform_poly = Poly2d::new(&[
+        Vec2::new(-4.0, -0.5),
+        Vec2::new(4.0, -0.5),
+        Vec2::new(4.0, 0.5),
+        Vec2::new(-4.0, 0.5),
+    ]);
+    let hexagon = Poly2d::regular(6, 0.6);
+    compare("poly v poly", &platform_poly, &hexagon);
+}
@@ -0,0 +1,178 @@
+use super::*;
+
+/// World-space axis-aligned bounding box.
+///
+/// Unlike [SymmetricBoundingBox2d], the box does not need to be centered at the origin.
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub struct Aabb2d {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Aabb2d {
+    pub const fn new(min: Vec2, max: Vec2) -> Self {
+        Self { min, max }
+    }
+
+    pub fn from_center_halfsize(center: Vec2, halfsize: Vec2) -> Self {
+        Self::new(center - halfsize, center + halfsize)
+    }
+
+    /// Smallest box containing all `points`, `None` if there are none.
+    pub fn from_points(points: impl IntoIterator<Item = Vec2>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self::new(first, first), |mut aabb, point| {
+            aabb.cover(point);
+            aabb
+        }))
+    }
+
+    pub fn center(&self) -> Vec2 {
+        0.5 * (self.min + self.max)
+    }
+
+    pub fn halfsize(&self) -> Vec2 {
+        0.5 * (self.max - self.min)
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.max - self.min
+    }
+
+    /// Grows the box to contain `point`.
+    pub fn cover(&mut self, point: Vec2) {
+        self.min = self.min.min(point);
+        self.max = self.max.max(point);
+    }
+
+    /// Smallest box containing both `self` and `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    /// Box grown by `margin` in every direction.
+    pub fn expanded(&self, margin: f32) -> Self {
+        Self::new(
+            self.min - Vec2::splat(margin),
+            self.max + Vec2::splat(margin),
+        )
+    }
+
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        self.min.cmple(point).all() && point.cmple(self.max).all()
+    }
+
+    /// Whether the boxes overlap, touching boxes intersect.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.cmple(other.max).all() && other.min.cmple(self.max).all()
+    }
+}
+
+/// Trait for computing the world-space bounding box of a shape.
+///
+/// Implemented for every shape with [ExtremePointT2d], the box is tight for any transformation.
+///
+/// # See also
+/// * [SymmetricBoundingBox2d]
+pub trait BoundingBox2d<T: Transformation2d> {
+    /// Computes the bounding box of the shape transformed by `t`.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// let b = Box2d::with_halfdims(1.0, 1.0);
+    /// let t = Transform2d::from_translation(Vec2::new(3.0, 0.0))
+    ///     .with_angle(core::f32::consts::FRAC_PI_4);
+    /// let aabb = b.bounding_box_t(&t);
+    /// assert!((aabb.max.x - (3.0 + core::f32::consts::SQRT_2)).abs() < 1e-5);
+    /// ```
+    fn bounding_box_t(&self, t: &T) -> Aabb2d;
+
+    /// Computes the bounding box covering the shape at both poses.
+    ///
+    /// # Important
+    /// Exact for motion without rotation. A shape rotating between `from` and `to` may leave the
+    /// box in between, use [Aabb2d::expanded] for a margin.
+    fn swept_bounding_box_t(&self, from: &T, to: &T) -> Aabb2d {
+        self.bounding_box_t(from).union(&self.bounding_box_t(to))
+    }
+}
+
+impl<S, T> BoundingBox2d<T> for S
+where
+    S: ExtremePointT2d<T>,
+    T: Transformation2d,
+{
+    fn bounding_box_t(&self, t: &T) -> Aabb2d {
+        Aabb2d::new(
+            Vec2::new(
+                self.extreme_point_t(t, Vec2::NEG_X).x,
+                self.extreme_point_t(t, Vec2::NEG_Y).y,
+            ),
+            Vec2::new(
+                self.extreme_point_t(t, Vec2::X).x,
+                self.extreme_point_t(t, Vec2::Y).y,
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn ball_is_tight() {
+        let ball = Ball::with_radius(1.0);
+        let t = Transform2d::from_translation(Vec2::new(2.0, -1.0)).with_angle(0.7);
+        assert_eq!(
+            ball.bounding_box_t(&t),
+            Aabb2d::new(Vec2::new(1.0, -2.0), Vec2::new(3.0, 0.0))
+        );
+    }
+
+    #[test_log::test]
+    fn rotated_box() {
+        let b = Box2d::with_halfdims(2.0, 1.0);
+        let t = Transform2d::from_translation(Vec2::new(1.0, 1.0))
+            .with_angle(core::f32::consts::FRAC_PI_2);
+        let aabb = b.bounding_box_t(&t);
+        assert!(aabb.min.abs_diff_eq(Vec2::new(0.0, -1.0), 1e-5));
+        assert!(aabb.max.abs_diff_eq(Vec2::new(2.0, 3.0), 1e-5));
+    }
+
+    #[test_log::test]
+    fn offset_poly() {
+        let poly = Poly2d::new(&[
+            Vec2::new(-0.5, -0.5),
+            Vec2::new(3.0, -0.5),
+            Vec2::new(3.0, 0.5),
+        ]);
+        let aabb = poly.bounding_box_t(&Vec2::new(1.0, 0.0));
+        assert_eq!(aabb, Aabb2d::new(Vec2::new(0.5, -0.5), Vec2::new(4.0, 0.5)));
+        assert_eq!(
+            Aabb2d::from_points(poly.points.iter().map(|p| *p + Vec2::X)),
+            Some(aabb)
+        );
+    }
+
+    #[test_log::test]
+    fn swept() {
+        let ball = Ball::with_radius(0.5);
+        let aabb = ball.swept_bounding_box_t(&Vec2::new(0.0, 0.0), &Vec2::new(4.0, 1.0));
+        assert_eq!(
+            aabb,
+            Aabb2d::new(Vec2::new(-0.5, -0.5), Vec2::new(4.5, 1.5))
+        );
+        assert!(aabb.contains_point(Vec2::new(2.0, 0.5)));
+        assert!(aabb.intersects(&Aabb2d::from_center_halfsize(
+            Vec2::new(5.0, 0.0),
+            Vec2::ONE
+        )));
+        assert!(!aabb.intersects(&Aabb2d::from_center_halfsize(
+            Vec2::new(6.0, 0.0),
+            Vec2::ONE
+        )));
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::BTreeMap;
+
+use super::*;
+
+/// Boolean mask, e.g. the alpha channel of a sprite, used to generate colliders.
+///
+/// Pixel `(x, y)` covers the unit square centred at `(x, y)`, rows go up.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bitmap2d {
+    width: usize,
+    height: usize,
+    data: Vec<bool>,
+}
+
+impl Bitmap2d {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![false; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, f: impl Fn(usize, usize) -> bool) -> Self {
+        let data = (0..width * height)
+            .map(|i| f(i % width, i / width))
+            .collect();
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// Pixels with alpha of at least `threshold` are solid, `alpha` is row-major starting with the
+    /// bottom row.
+    pub fn from_alpha(width: usize, height: usize, alpha: &[u8], threshold: u8) -> Self {
+        assert_eq!(alpha.len(), width * height);
+        Self {
+            width,
+            height,
+            data: alpha.iter().map(|&a| a >= threshold).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the pixel is solid, pixels out of bounds are empty.
+    pub fn get(&self, x: i32, y: i32) -> bool {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return false;
+        }
+        self.data[x as usize + y as usize * self.width]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, solid: bool) {
+        assert!(x < self.width && y < self.height);
+        self.data[x + y * self.width] = solid;
+    }
+}
+
+impl Bitmap2d {
+    /// Traces the boundaries of solid regions with marching squares.
+    ///
+    /// Outer boundaries are counter-clockwise and holes clockwise. Pixels touching only at a
+    /// corner are not connected.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// let pixel = Bitmap2d::from_fn(1, 1, |_, _| true);
+    /// let contours = pixel.contours();
+    /// assert_eq!(contours.len(), 1);
+    /// assert_eq!(signed_area(&contours[0]), 0.5);
+    /// ```
+    pub fn contours(&self) -> Vec<Vec<Vec2>> {
+        // Samples are at pixel centres, crossings are halfway between two samples and are keyed by
+        // twice their position to stay on the integer grid.
+        let mut next = BTreeMap::new();
+        for y in -1..self.height as i32 {
+            for x in -1..self.width as i32 {
+                let corners = [(x, y), (x + 1, y), (x + 1, y + 1), (x, y + 1)];
+                let solid = corners.map(|(x, y)| self.get(x, y));
+                let crossings: Vec<((i32, i32), bool)> = (0..4)
+                    .filter(|&i| solid[i] != solid[(i + 1) % 4])
+                    .map(|i| {
+                        let (a, b) = (corners[i], corners[(i + 1) % 4]);
+                        ((a.0 + b.0, a.1 + b.1), solid[i])
+                    })
+                    .collect();
+                // Walking the cell counter-clockwise, the boundary leaving the solid corners at
+                // an exit continues from the previous crossing.
+                for (k, &(crossing, exit)) in crossings.iter().enumerate() {
+                    if exit {
+                        let previous = crossings[(k + crossings.len() - 1) % crossings.len()].0;
+                        next.insert(crossing, previous);
+                    }
+                }
+            }
+        }
+
+        let mut contours = Vec::new();
+        while let Some((&start, _)) = next.first_key_value() {
+            let mut contour = Vec::new();
+            let mut key = start;
+            while let Some(following) = next.remove(&key) {
+                contour.push(Vec2::new(key.0 as f32, key.1 as f32) / 2.0);
+                key = following;
+            }
+            contours.push(contour);
+        }
+        contours
+    }
+
+    /// Generates convex colliders covering the solid pixels.
+    ///
+    /// Contours are simplified so that no boundary point moves by more than `epsilon`, then split
+    /// into convex pieces. Each piece is returned with its offset from the bitmap origin, the
+    /// pieces together form one compound collider.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// let l_shape = Bitmap2d::from_fn(3, 3, |x, y| x == 0 || y == 0);
+    /// let colliders = l_shape.colliders(0.1);
+    ///
+    /// let covers = |point: Vec2| {
+    ///     colliders
+    ///         .iter()
+    ///         .any(|(poly, offset)| (poly, offset).collides((&Point, &point)))
+    /// };
+    /// assert!(covers(Vec2::new(0.0, 2.0)));
+    /// assert!(!covers(Vec2::new(2.0, 2.0)));
+    /// ```
+    pub fn colliders(&self, epsilon: f32) -> Vec<(Poly2d, Vec2)> {
+        let contours: Vec<Vec<Vec2>> = self
+            .contours()
+            .iter()
+            .map(|contour| simplify_polygon(contour, epsilon))
+            .collect();
+        convex_colliders(&contours)
+    }
+}
+
+/// Non-zero tiles are solid, e.g. for [Chunk::array2d](crate::col2d::Chunk::array2d).
+#[cfg(feature = "tilemap")]
+impl From<&lk_math::arraynd::Array2d<u8>> for Bitmap2d {
+    fn from(array: &lk_math::arraynd::Array2d<u8>) -> Self {
+        Self {
+            width: array.width(),
+            height: array.height(),
+            data: array.data.iter().map(|&tile| tile != 0).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn total_area(colliders: &[(Poly2d, Vec2)]) -> f32 {
+        colliders
+            .iter()
+            .map(|(poly, _)| poly.mass_properties(1.0).mass)
+            .sum()
+    }
+
+    #[test_log::test]
+    fn block() {
+        let block = Bitmap2d::from_fn(3, 3, |_, _| true);
+        let contours = block.contours();
+        assert_eq!(contours.len(), 1);
+        assert_eq!(signed_area(&contours[0]), 8.5);
+
+        let colliders = block.colliders(0.0);
+        assert_eq!(colliders.len(), 1);
+        assert_eq!(colliders[0].0.points.len(), 8);
+        assert_eq!(colliders[0].1, Vec2::ONE);
+        assert!(colliders[0].0.strict_check());
+    }
+
+    #[test_log::test]
+    fn ring() {
+        let ring = Bitmap2d::from_fn(5, 5, |x, y| x == 0 || y == 0 || x == 4 || y == 4);
+        let contours = ring.contours();
+        assert_eq!(contours.len(), 2);
+        let areas: Vec<f32> = contours.iter().map(|c| signed_area(c)).collect();
+        assert!(areas.contains(&(24.5)), "{areas:?}");
+        assert!(areas.contains(&(-8.5)), "{areas:?}");
+
+        let colliders = ring.colliders(0.0);
+        assert!((total_area(&colliders) - 16.0).abs() < 1e-4);
+        for (poly, offset) in &colliders {
+            assert!(!(poly, offset).collides((&Point, &Vec2::new(2.0, 2.0))));
+        }
+    }
+
+    #[test_log::test]
+    fn diagonal_pixels_are_separate() {
+        let diagonal = Bitmap2d::from_fn(2, 2, |x, y| x == y);
+        assert_eq!(diagonal.contours().len(), 2);
+        assert_eq!(diagonal.colliders(0.0).len(), 2);
+    }
+
+    #[test_log::test]
+    fn from_alpha() {
+        let alpha = [0, 255, 128, 10];
+        let bitmap = Bitmap2d::from_alpha(2, 2, &alpha, 128);
+        assert!(!bitmap.get(0, 0));
+        assert!(bitmap.get(1, 0));
+        assert!(bitmap.get(0, 1));
+        assert!(!bitmap.get(1, 1));
+        assert!(!bitmap.get(-1, 0));
+    }
+
+    #[cfg(feature = "tilemap")]
+    #[test_log::test]
+    fn from_chunk() {
+        let mut chunk = Chunk::default();
+        chunk.array2d.set(lk_math::vector::V2::from_xy(3, 2), 7);
+        let bitmap = Bitmap2d::from(&chunk.array2d);
+        assert_eq!(bitmap.width(), 16);
+        assert!(bitmap.get(3, 2));
+        assert_eq!(bitmap.colliders(0.0).len(), 1);
+    }
+}
@@ -0,0 +1,434 @@
+use std::collections::{HashMap, HashSet};
+
+use super::*;
+
+/// Boolean operation for [polygon_boolean].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BooleanOp2d {
+    /// Area covered by either region.
+    Union,
+    /// Area covered by both regions.
+    Intersection,
+    /// Area of the first region not covered by the second one.
+    Difference,
+}
+
+/// Combines two regions bounded by simple rings, e.g. to carve explosions out of terrain.
+///
+/// Regions are lists of rings, outer boundaries counter-clockwise and holes clockwise, such as
+/// [Poly2d] outlines, [Bitmap2d::contours] or a previous result. The result uses the same
+/// convention and can be split into colliders with [convex_colliders].
+///
+/// Edges of both regions are split where they cross or touch, and each piece is kept or dropped
+/// depending on which side of the other region it lies on. Overlapping edges are matched exactly,
+/// so shared and collinear edges as well as regions touching at a vertex are handled. Regions
+/// touching at a single point stay separate rings.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// let terrain = vec![vec![
+///     Vec2::new(0.0, 0.0),
+///     Vec2::new(10.0, 0.0),
+///     Vec2::new(10.0, 4.0),
+///     Vec2::new(0.0, 4.0),
+/// ]];
+/// let explosion = Poly2d::regular(16, 2.0);
+/// let crater = vec![explosion
+///     .outline()
+///     .iter()
+///     .map(|&i| explosion.points[i] + Vec2::new(5.0, 4.0))
+///     .collect()];
+///
+/// let carved = polygon_boolean(&terrain, &crater, BooleanOp2d::Difference);
+/// assert_eq!(carved.len(), 1);
+/// let area: f32 = carved.iter().map(|ring| signed_area(ring)).sum();
+/// assert!(area < 40.0 && area > 30.0);
+///
+/// let colliders = convex_colliders(&carved);
+/// assert!(colliders.len() > 1);
+/// ```
+pub fn polygon_boolean(a: &[Vec<Vec2>], b: &[Vec<Vec2>], op: BooleanOp2d) -> Vec<Vec<Vec2>> {
+    let epsilon = 1e-5
+        * a.iter()
+            .chain(b)
+            .flatten()
+            .map(|p| p.abs().max_element())
+            .fold(1.0, f32::max);
+
+    // Vertices of `b` that almost coincide with vertices of `a` are snapped onto them, everything
+    // after that relies on shared points being bit-identical.
+    let a_vertices: Vec<Vec2> = a.iter().flatten().copied().collect();
+    let b: Vec<Vec<Vec2>> = b
+        .iter()
+        .map(|ring| {
+            ring.iter()
+                .map(|&p| {
+                    a_vertices
+                        .iter()
+                        .copied()
+                        .find(|q| q.distance_squared(p) <= epsilon * epsilon)
+                        .unwrap_or(p)
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut a_edges = ring_edges(a);
+    let mut b_edges = ring_edges(&b);
+    for ea in &mut a_edges {
+        for eb in &mut b_edges {
+            split_crossing(ea, eb, epsilon);
+        }
+    }
+    let a_parts = split_edges(&a_edges);
+    let b_parts = split_edges(&b_edges);
+
+    let b_keys: HashSet<_> = b_parts.iter().map(|&(p, q)| (key(p), key(q))).collect();
+    let a_keys: HashSet<_> = a_parts.iter().map(|&(p, q)| (key(p), key(q))).collect();
+
+    let mut kept = Vec::new();
+    for &(p, q) in &a_parts {
+        let same = b_keys.contains(&(key(p), key(q)));
+        let opposite = b_keys.contains(&(key(q), key(p)));
+        let keep = match op {
+            BooleanOp2d::Union | BooleanOp2d::Intersection if same => true,
+            BooleanOp2d::Difference if opposite => true,
+            _ if same || opposite => false,
+            BooleanOp2d::Union | BooleanOp2d::Difference => !region_contains(&b, (p + q) / 2.0),
+            BooleanOp2d::Intersection => region_contains(&b, (p + q) / 2.0),
+        };
+        if keep {
+            kept.push((p, q));
+        }
+    }
+    for &(p, q) in &b_parts {
+        // Shared edges were decided above.
+        if a_keys.contains(&(key(p), key(q))) || a_keys.contains(&(key(q), key(p))) {
+            continue;
+        }
+        let inside = region_contains(a, (p + q) / 2.0);
+        match op {
+            BooleanOp2d::Union if !inside => kept.push((p, q)),
+            BooleanOp2d::Intersection if inside => kept.push((p, q)),
+            BooleanOp2d::Difference if inside => kept.push((q, p)),
+            _ => {}
+        }
+    }
+
+    link_rings(kept)
+}
+
+struct Edge {
+    from: Vec2,
+    to: Vec2,
+    splits: Vec<Vec2>,
+}
+
+fn ring_edges(rings: &[Vec<Vec2>]) -> Vec<Edge> {
+    rings
+        .iter()
+        .flat_map(|ring| {
+            (0..ring.len()).map(|i| Edge {
+                from: ring[i],
+                to: ring[(i + 1) % ring.len()],
+                splits: Vec::new(),
+            })
+        })
+        .filter(|edge| edge.from != edge.to)
+        .collect()
+}
+
+/// Records the points where two edges cross, touch or overlap on both of them.
+fn split_crossing(a: &mut Edge, b: &mut Edge, epsilon: f32) {
+    let (da, db) = (a.to - a.from, b.to - b.from);
+    let denominator = da.perp_dot(db);
+    let offset = b.from - a.from;
+
+    if denominator.abs() <= epsilon * da.length() * db.length() {
+        // Parallel, overlapping only if collinear, then endpoints of one split the other.
+        if da.perp_dot(offset).abs() > epsilon * da.length() {
+            return;
+        }
+        for p in [b.from, b.to] {
+            if strictly_within(a, p, epsilon) {
+                a.splits.push(p);
+            }
+        }
+        for p in [a.from, a.to] {
+            if strictly_within(b, p, epsilon) {
+                b.splits.push(p);
+            }
+        }
+        return;
+    }
+
+    let t = offset.perp_dot(db) / denominator;
+    let u = offset.perp_dot(da) / denominator;
+    let (ta, tb) = (epsilon / da.length(), epsilon / db.length());
+    if t < -ta || t > 1.0 + ta || u < -tb || u > 1.0 + tb {
+        return;
+    }
+    let p = a.from + t * da;
+    let p = [a.from, a.to, b.from, b.to]
+        .into_iter()
+        .find(|q| q.distance_squared(p) <= epsilon * epsilon)
+        .unwrap_or(p);
+    a.splits.push(p);
+    b.splits.push(p);
+}
+
+fn strictly_within(edge: &Edge, p: Vec2, epsilon: f32) -> bool {
+    let d = edge.to - edge.from;
+    let t = (p - edge.from).dot(d) / d.length_squared();
+    let margin = epsilon / d.length();
+    t > margin && t < 1.0 - margin
+}
+
+fn split_edges(edges: &[Edge]) -> Vec<(Vec2, Vec2)> {
+    let mut parts = Vec::new();
+    for edge in edges {
+        let d = edge.to - edge.from;
+        let mut points: Vec<Vec2> = edge
+            .splits
+            .iter()
+            .copied()
+            .filter(|&p| p != edge.from && p != edge.to)
+            .collect();
+        points.sort_by(|p, q| (*p - edge.from).dot(d).total_cmp(&(*q - edge.from).dot(d)));
+        points.dedup();
+        let mut from = edge.from;
+        for to in points.into_iter().chain([edge.to]) {
+            parts.push((from, to));
+            from = to;
+        }
+    }
+    parts
+}
+
+/// Bit pattern of a point, `-0.0` and `0.0` map to the same key.
+fn key(p: Vec2) -> (u32, u32) {
+    ((p.x + 0.0).to_bits(), (p.y + 0.0).to_bits())
+}
+
+fn region_contains(rings: &[Vec<Vec2>], point: Vec2) -> bool {
+    rings
+        .iter()
+        .filter(|ring| polygon_contains_point(ring, point))
+        .count()
+        % 2
+        == 1
+}
+
+/// Chains directed edges into closed rings.
+fn link_rings(edges: Vec<(Vec2, Vec2)>) -> Vec<Vec<Vec2>> {
+    let mut outgoing: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+    for (i, &(p, _)) in edges.iter().enumerate() {
+        outgoing.entry(key(p)).or_default().push(i);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut rings = Vec::new();
+    for start in 0..edges.len() {
+        if used[start] {
+            continue;
+        }
+        let mut ring = Vec::new();
+        let mut current = start;
+        loop {
+            used[current] = true;
+            let (p, q) = edges[current];
+            ring.push(p);
+            // Where rings touch at a vertex, taking the leftmost turn keeps them apart instead of
+            // joining them into one self-touching ring.
+            let direction = q - p;
+            let next = outgoing.get(&key(q)).and_then(|candidates| {
+                candidates
+                    .iter()
+                    .copied()
+                    .filter(|&i| !used[i] || i == start)
+                    .max_by(|&i, &j| {
+                        let turn = |i: usize| {
+                            let d = edges[i].1 - edges[i].0;
+                            direction.perp_dot(d).atan2(direction.dot(d))
+                        };
+                        turn(i).total_cmp(&turn(j))
+                    })
+            });
+            match next {
+                Some(next) if next == start => break,
+                Some(next) => current = next,
+                None => {
+                    crate::warn!("open chain in polygon boolean result");
+                    ring.clear();
+                    break;
+                }
+            }
+        }
+
+        let ring = without_collinear(&ring);
+        if ring.len() >= 3 && signed_area(&ring).abs() > f32::EPSILON {
+            rings.push(ring);
+        }
+    }
+    rings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(min: Vec2, max: Vec2) -> Vec<Vec<Vec2>> {
+        vec![vec![
+            min,
+            Vec2::new(max.x, min.y),
+            max,
+            Vec2::new(min.x, max.y),
+        ]]
+    }
+
+    fn area(rings: &[Vec<Vec2>]) -> f32 {
+        rings.iter().map(|ring| signed_area(ring)).sum()
+    }
+
+    #[test_log::test]
+    fn overlapping_squares() {
+        let a = rect(Vec2::ZERO, Vec2::splat(2.0));
+        let b = rect(Vec2::ONE, Vec2::splat(3.0));
+
+        let union = polygon_boolean(&a, &b, BooleanOp2d::Union);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].len(), 8);
+        assert_eq!(area(&union), 7.0);
+
+        let intersection = polygon_boolean(&a, &b, BooleanOp2d::Intersection);
+        assert_eq!(intersection.len(), 1);
+        assert_eq!(area(&intersection), 1.0);
+
+        let difference = polygon_boolean(&a, &b, BooleanOp2d::Difference);
+        assert_eq!(difference.len(), 1);
+        assert_eq!(difference[0].len(), 6);
+        assert_eq!(area(&difference), 3.0);
+    }
+
+    #[test_log::test]
+    fn identical() {
+        let a = rect(Vec2::ZERO, Vec2::ONE);
+        assert_eq!(area(&polygon_boolean(&a, &a, BooleanOp2d::Union)), 1.0);
+        assert_eq!(
+            area(&polygon_boolean(&a, &a, BooleanOp2d::Intersection)),
+            1.0
+        );
+        assert!(polygon_boolean(&a, &a, BooleanOp2d::Difference).is_empty());
+    }
+
+    #[test_log::test]
+    fn shared_edge() {
+        let a = rect(Vec2::ZERO, Vec2::ONE);
+        let b = rect(Vec2::new(1.0, 0.0), Vec2::new(2.0, 1.0));
+
+        let union = polygon_boolean(&a, &b, BooleanOp2d::Union);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].len(), 4);
+        assert_eq!(area(&union), 2.0);
+
+        assert!(polygon_boolean(&a, &b, BooleanOp2d::Intersection).is_empty());
+        assert_eq!(area(&polygon_boolean(&a, &b, BooleanOp2d::Difference)), 1.0);
+    }
+
+    #[test_log::test]
+    fn partially_collinear_edges() {
+        let a = rect(Vec2::ZERO, Vec2::new(2.0, 1.0));
+        let b = rect(Vec2::new(1.0, 1.0), Vec2::new(3.0, 2.0));
+
+        let union = polygon_boolean(&a, &b, BooleanOp2d::Union);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].len(), 8);
+        assert_eq!(area(&union), 4.0);
+        assert!(polygon_boolean(&a, &b, BooleanOp2d::Intersection).is_empty());
+    }
+
+    #[test_log::test]
+    fn touching_corners() {
+        let a = rect(Vec2::ZERO, Vec2::ONE);
+        let b = rect(Vec2::ONE, Vec2::splat(2.0));
+
+        let union = polygon_boolean(&a, &b, BooleanOp2d::Union);
+        assert_eq!(union.len(), 2);
+        assert!(union.iter().all(|ring| ring.len() == 4));
+        assert_eq!(area(&union), 2.0);
+
+        assert!(polygon_boolean(&a, &b, BooleanOp2d::Intersection).is_empty());
+        assert_eq!(area(&polygon_boolean(&a, &b, BooleanOp2d::Difference)), 1.0);
+    }
+
+    #[test_log::test]
+    fn nearly_coincident_vertices() {
+        let a = rect(Vec2::ZERO, Vec2::ONE);
+        let b = rect(Vec2::new(1.0 + 1e-7, -1e-7), Vec2::new(2.0, 1.0));
+
+        let union = polygon_boolean(&a, &b, BooleanOp2d::Union);
+        assert_eq!(union.len(), 1);
+        assert!((area(&union) - 2.0).abs() < 1e-5);
+    }
+
+    #[test_log::test]
+    fn notch_on_boundary() {
+        let a = rect(Vec2::ZERO, Vec2::splat(4.0));
+        let b = rect(Vec2::new(1.0, 0.0), Vec2::new(3.0, 1.0));
+
+        let difference = polygon_boolean(&a, &b, BooleanOp2d::Difference);
+        assert_eq!(difference.len(), 1);
+        assert_eq!(difference[0].len(), 8);
+        assert_eq!(area(&difference), 14.0);
+    }
+
+    #[test_log::test]
+    fn hole() {
+        let a = rect(Vec2::ZERO, Vec2::splat(4.0));
+        let b = rect(Vec2::ONE, Vec2::splat(2.0));
+
+        let difference = polygon_boolean(&a, &b, BooleanOp2d::Difference);
+        assert_eq!(difference.len(), 2);
+        assert_eq!(area(&difference), 15.0);
+
+        // Filling the hole again.
+        let union = polygon_boolean(&difference, &b, BooleanOp2d::Union);
+        assert_eq!(union.len(), 1);
+        assert_eq!(area(&union), 16.0);
+
+        let colliders = convex_colliders(&difference);
+        let total: f32 = colliders
+            .iter()
+            .map(|(poly, _)| poly.mass_properties(1.0).mass)
+            .sum();
+        assert!((total - 15.0).abs() < 1e-4);
+    }
+
+    #[test_log::test]
+    fn repeated_craters() {
+        let mut terrain = rect(Vec2::ZERO, Vec2::new(10.0, 4.0));
+        let explosion = Poly2d::regular(12, 1.5);
+        for center in [(3.0, 4.0), (4.0, 3.5), (7.0, 0.0), (5.0, 2.0)] {
+            let crater: Vec<Vec<Vec2>> = vec![explosion
+                .outline()
+                .iter()
+                .map(|&i| explosion.points[i] + Vec2::from(center))
+                .collect()];
+            let before = area(&terrain);
+            let removed = area(&polygon_boolean(
+                &terrain,
+                &crater,
+                BooleanOp2d::Intersection,
+            ));
+            terrain = polygon_boolean(&terrain, &crater, BooleanOp2d::Difference);
+            assert!((area(&terrain) - (before - removed)).abs() < 1e-3);
+        }
+
+        let total: f32 = convex_colliders(&terrain)
+            .iter()
+            .map(|(poly, _)| poly.mass_properties(1.0).mass)
+            .sum();
+        assert!((total - area(&terrain)).abs() < 1e-3);
+    }
+}
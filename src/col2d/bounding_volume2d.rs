@@ -0,0 +1,335 @@
+use super::*;
+
+/// Oriented bounding box, a [Box2d] rotated by `angle` and centered at `center`.
+///
+/// Plugs back into the colliders through [Obb2d::shape] and [Obb2d::transform].
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub struct Obb2d {
+    pub center: Vec2,
+    pub halfsize: Vec2,
+    pub angle: f32,
+}
+
+impl Obb2d {
+    pub const fn new(center: Vec2, halfsize: Vec2, angle: f32) -> Self {
+        Self {
+            center,
+            halfsize,
+            angle,
+        }
+    }
+
+    /// Minimum-area box containing all `points`, `None` if there are none.
+    ///
+    /// Uses rotating calipers over the convex hull, one of the box's sides is always collinear
+    /// with a hull edge.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// // Thin diagonal sprite, its axis-aligned box would be mostly empty.
+    /// let points = [
+    ///     Vec2::new(0.0, 0.0),
+    ///     Vec2::new(4.0, 4.0),
+    ///     Vec2::new(4.5, 3.5),
+    ///     Vec2::new(0.5, -0.5),
+    /// ];
+    /// let obb = Obb2d::from_points(&points).unwrap();
+    /// assert!((obb.area() - 4.0).abs() < 1e-4);
+    /// let (shape, transform) = (obb.shape(), obb.transform());
+    /// let inside = Transform2d::from_translation(Vec2::new(2.2, 1.8));
+    /// assert!((&shape, &transform).collides((&Point, &inside)));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_points(points: &[Vec2]) -> Option<Self> {
+        let hull = convex_hull(points);
+        let n = hull.len();
+        match n {
+            0 => return None,
+            1 => return Some(Self::new(hull[0], Vec2::ZERO, 0.0)),
+            _ => {}
+        }
+
+        let next = |i: usize| (i + 1) % n;
+        // Advances `i` while the projection onto `axis` keeps growing, bounded by a full turn.
+        let advance = |mut i: usize, axis: Vec2| {
+            for _ in 0..n {
+                if axis.dot(hull[next(i)]) > axis.dot(hull[i]) {
+                    i = next(i);
+                } else {
+                    break;
+                }
+            }
+            i
+        };
+
+        let argmax = |axis: Vec2| {
+            (0..n)
+                .max_by(|&i, &j| axis.dot(hull[i]).total_cmp(&axis.dot(hull[j])))
+                .unwrap_or(0)
+        };
+
+        let mut best: Option<(f32, Self)> = None;
+        let (mut right, mut top, mut left) = (0, 0, 0);
+        for i in 0..n {
+            let a = hull[i];
+            let u = (hull[next(i)] - a).normalize();
+            // The hull is counter-clockwise, so `v` points inside.
+            let v = u.perp();
+
+            if i == 0 {
+                // Only the first edge needs a full scan, the extreme vertices of the following
+                // edges are never behind the previous ones.
+                (right, top, left) = (argmax(u), argmax(v), argmax(-u));
+            } else {
+                right = advance(right, u);
+                top = advance(top, v);
+                left = advance(left, -u);
+            }
+
+            let min = Vec2::new(u.dot(hull[left]), v.dot(a));
+            let max = Vec2::new(u.dot(hull[right]), v.dot(hull[top]));
+            let size = max - min;
+            let area = size.x * size.y;
+            if best.is_none_or(|(best_area, _)| area < best_area) {
+                let center = 0.5 * (min + max);
+                let obb = Self::new(center.x * u + center.y * v, 0.5 * size, u.y.atan2(u.x));
+                best = Some((area, obb));
+            }
+        }
+        best.map(|(_, obb)| obb)
+    }
+
+    pub fn area(&self) -> f32 {
+        4.0 * self.halfsize.x * self.halfsize.y
+    }
+
+    /// Box part of the collider, see [Obb2d::transform].
+    pub fn shape(&self) -> Box2d {
+        Box2d::new(self.halfsize)
+    }
+
+    /// Transform placing [Obb2d::shape] in the space the box was computed in.
+    pub fn transform(&self) -> Transform2d {
+        Transform2d::from_translation(self.center).with_angle(self.angle)
+    }
+
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        let local = Rotor2d::from_angle(-self.angle) * (point - self.center);
+        local.abs().cmple(self.halfsize).all()
+    }
+}
+
+/// Bounding circle, a [Ball] centered at `center`.
+///
+/// `center` is a [Transformation2d] on its own, the collider is `(&circle.shape(), &circle.center)`.
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub struct BoundingCircle2d {
+    pub center: Vec2,
+    pub radius: f32,
+}
+
+impl BoundingCircle2d {
+    pub const fn new(center: Vec2, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    /// Smallest circle containing all `points`, `None` if there are none.
+    ///
+    /// Uses Welzl's algorithm, expected linear time.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// let points = [
+    ///     Vec2::new(-1.0, 0.0),
+    ///     Vec2::new(1.0, 0.0),
+    ///     Vec2::new(0.0, 0.5),
+    ///     Vec2::new(0.2, -0.3),
+    /// ];
+    /// let circle = BoundingCircle2d::from_points(&points).unwrap();
+    /// assert_eq!(circle, BoundingCircle2d::new(Vec2::ZERO, 1.0));
+    /// let ball = circle.shape();
+    /// assert!((&ball, &circle.center).collides((&Point, &Vec2::new(0.0, 0.9))));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_points(points: &[Vec2]) -> Option<Self> {
+        let mut points = points.to_vec();
+        // Welzl's algorithm is only expected-linear for a random order. A fixed-seed shuffle keeps
+        // the result deterministic.
+        let mut seed: u32 = 0x9e37_79b9;
+        for i in (1..points.len()).rev() {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            points.swap(i, seed as usize % (i + 1));
+        }
+
+        let mut circle = Self::new(*points.first()?, 0.0);
+        for i in 1..points.len() {
+            if circle.contains_point(points[i]) {
+                continue;
+            }
+            circle = Self::new(points[i], 0.0);
+            for j in 0..i {
+                if circle.contains_point(points[j]) {
+                    continue;
+                }
+                circle = Self::from_diameter(points[i], points[j]);
+                for k in 0..j {
+                    if !circle.contains_point(points[k]) {
+                        circle = Self::from_triangle(points[i], points[j], points[k]);
+                    }
+                }
+            }
+        }
+        Some(circle)
+    }
+
+    fn from_diameter(a: Vec2, b: Vec2) -> Self {
+        Self::new(0.5 * (a + b), 0.5 * a.distance(b))
+    }
+
+    /// Circumcircle of the triangle, the largest diameter circle if it is degenerate.
+    fn from_triangle(a: Vec2, b: Vec2, c: Vec2) -> Self {
+        let ab = b - a;
+        let ac = c - a;
+        let d = 2.0 * ab.perp_dot(ac);
+        if d.abs() <= f32::EPSILON * ab.length_squared().max(ac.length_squared()) {
+            return [
+                Self::from_diameter(a, b),
+                Self::from_diameter(a, c),
+                Self::from_diameter(b, c),
+            ]
+            .into_iter()
+            .max_by(|x, y| x.radius.total_cmp(&y.radius))
+            .unwrap();
+        }
+        let offset = Vec2::new(
+            ac.y * ab.length_squared() - ab.y * ac.length_squared(),
+            ab.x * ac.length_squared() - ac.x * ab.length_squared(),
+        ) / d;
+        Self::new(a + offset, offset.length())
+    }
+
+    /// Ball part of the collider, placed by [BoundingCircle2d::center].
+    pub fn shape(&self) -> Ball {
+        Ball::with_radius(self.radius)
+    }
+
+    /// Whether `point` is inside, with a small relative tolerance for points on the circle.
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        point.distance(self.center) <= self.radius * (1.0 + 1e-5) + 1e-6
+    }
+}
+
+/// Trait for computing tight bounding volumes of a shape in `self`-centric space.
+///
+/// # See also
+/// * [BoundingBox2d] - world-space axis-aligned bounding box
+pub trait BoundingVolume2d {
+    /// Computes the minimum-area oriented bounding box.
+    fn oriented_bounding_box(&self) -> Obb2d;
+
+    /// Computes the minimum bounding circle.
+    fn bounding_circle(&self) -> BoundingCircle2d;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn obb_of_rotated_box() {
+        let t = Transform2d::from_translation(Vec2::new(3.0, -1.0)).with_angle(0.4);
+        let points: Vec<Vec2> = [
+            Vec2::new(-2.0, -1.0),
+            Vec2::new(2.0, -1.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(-2.0, 1.0),
+            Vec2::new(0.5, 0.5),
+        ]
+        .into_iter()
+        .map(|p| t.apply(p))
+        .collect();
+        let obb = Obb2d::from_points(&points).unwrap();
+        assert!((obb.area() - 8.0).abs() < 1e-4);
+        assert!(obb.center.abs_diff_eq(Vec2::new(3.0, -1.0), 1e-5));
+        assert!(points
+            .iter()
+            .all(|p| obb.contains_point(*p + 1e-4 * (obb.center - *p))));
+        // Pi-periodic in the angle and the halfsize can be swapped.
+        let mut halfsize = [obb.halfsize.x, obb.halfsize.y];
+        halfsize.sort_by(f32::total_cmp);
+        assert!((halfsize[0] - 1.0).abs() < 1e-4 && (halfsize[1] - 2.0).abs() < 1e-4);
+    }
+
+    #[test_log::test]
+    fn obb_is_never_larger_than_aabb() {
+        let poly = Poly2d::regular(5, 1.0);
+        let obb = poly.oriented_bounding_box();
+        let aabb = Aabb2d::from_points(poly.points.iter().copied()).unwrap();
+        assert!(obb.area() <= aabb.size().x * aabb.size().y + 1e-5);
+        for p in &poly.points {
+            assert!(obb.contains_point(*p + 1e-4 * (obb.center - *p)));
+        }
+    }
+
+    #[test_log::test]
+    fn degenerate_obb() {
+        assert_eq!(Obb2d::from_points(&[]), None);
+        let obb = Obb2d::from_points(&[Vec2::ONE]).unwrap();
+        assert_eq!(obb, Obb2d::new(Vec2::ONE, Vec2::ZERO, 0.0));
+        let obb = Obb2d::from_points(&[Vec2::ZERO, Vec2::new(2.0, 2.0), Vec2::ONE]).unwrap();
+        assert_eq!(obb.area(), 0.0);
+        assert!(obb.center.abs_diff_eq(Vec2::ONE, 1e-5));
+    }
+
+    #[test_log::test]
+    fn circle_through_three_points() {
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(4.0, 0.0),
+            Vec2::new(0.0, 3.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(2.0, 0.5),
+        ];
+        let circle = BoundingCircle2d::from_points(&points).unwrap();
+        assert!(circle.center.abs_diff_eq(Vec2::new(2.0, 1.5), 1e-5));
+        assert!((circle.radius - 2.5).abs() < 1e-5);
+        assert!(points.iter().all(|p| circle.contains_point(*p)));
+    }
+
+    #[test_log::test]
+    fn circle_of_many_points() {
+        let poly = Poly2d::regular(64, 3.0);
+        let shifted: Vec<Vec2> = poly
+            .points
+            .iter()
+            .map(|p| *p + Vec2::new(1.0, 2.0))
+            .collect();
+        let circle = BoundingCircle2d::from_points(&shifted).unwrap();
+        assert!(circle.center.abs_diff_eq(Vec2::new(1.0, 2.0), 1e-4));
+        assert!((circle.radius - 3.0).abs() < 1e-4);
+        assert_eq!(BoundingCircle2d::from_points(&[]), None);
+    }
+
+    #[test_log::test]
+    fn shapes() {
+        let b = Box2d::with_halfdims(3.0, 4.0);
+        assert_eq!(b.bounding_circle(), BoundingCircle2d::new(Vec2::ZERO, 5.0));
+        assert_eq!(
+            b.oriented_bounding_box(),
+            Obb2d::new(Vec2::ZERO, b.halfsize, 0.0)
+        );
+        let rounded = RoundedBox2d::new(Vec2::new(3.0, 4.0), 1.0);
+        assert_eq!(rounded.bounding_circle().radius, 6.0);
+        assert_eq!(
+            Shape2d::ball(2.0).oriented_bounding_box().area(),
+            Ball::with_radius(2.0).oriented_bounding_box().area()
+        );
+        let poly = Poly2d::from(b);
+        assert!((poly.bounding_circle().radius - 5.0).abs() < 1e-5);
+        assert!((poly.oriented_bounding_box().area() - 48.0).abs() < 1e-4);
+    }
+}
@@ -13,12 +13,20 @@ impl<'a, S, T: Transformation2d> Collider2d<'a, S, T> {
     }
 }
 
+impl<S, T> Collider2d<'_, S, T>
+where
+    S: BoundingBox2d<T>,
+    T: Transformation2d,
+{
+    /// World-space bounding box of the collider.
+    pub fn bounding_box(&self) -> Aabb2d {
+        self.shape.bounding_box_t(self.transform)
+    }
+}
+
 impl<'a, S, T: Transformation2d> Clone for Collider2d<'a, S, T> {
     fn clone(&self) -> Self {
-        Self {
-            shape: self.shape,
-            transform: self.transform,
-        }
+        *self
     }
 }
 
@@ -42,6 +50,6 @@ where
     T: Transformation2d,
 {
     fn extreme_point(&self, direction: Vec2) -> Vec2 {
-        self.shape.extreme_point_t(&self.transform, direction)
+        self.shape.extreme_point_t(self.transform, direction)
     }
 }
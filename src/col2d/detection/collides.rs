@@ -2,6 +2,9 @@ use super::*;
 
 /// Trait for checking collision between `Self` and `T` given relative transform between them.
 ///
+/// Queries that fail to converge report no collision. Use [CollidesRel2d::collides_rel_result]
+/// to tell the two apart.
+///
 /// # See also
 /// * [SymmetricBoundingBox2d]
 /// * [PenetratesRel2d]
@@ -24,6 +27,47 @@ pub trait CollidesRel2d<B, T: Transformation2d> {
     /// # See also
     /// * [Penetrates2d::penetrates].
     fn collides_rel(&self, b: &B, rel: &T) -> bool;
+
+    /// Checks whether objects collide, reporting failed queries as [QueryResult2d::Unknown].
+    ///
+    /// Only the variant of the result is meaningful, the *vector* signed-distance is not computed.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// let a = Box2d::with_halfdims(1.0, 1.0);
+    /// let b = Box2d::with_halfdims(1.0, 1.0);
+    /// let rel = Translate2d::from(Vec2::new(1.0, 0.0));
+    /// assert_eq!(a.collides_rel_result(&b, &rel).collides(), Some(true));
+    /// ```
+    fn collides_rel_result(&self, b: &B, rel: &T) -> QueryResult2d {
+        if self.collides_rel(b, rel) {
+            QueryResult2d::penetrating_unmeasured()
+        } else {
+            QueryResult2d::separated_unmeasured()
+        }
+    }
+
+    /// Same as [CollidesRel2d::collides_rel], running GJK/EPA with `config` instead of
+    /// [GjkEpaConfig::global].
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// let a = Poly2d::regular(16, 1.0);
+    /// let b = Poly2d::regular(8, 1.0);
+    /// let rel = Translate2d::from(Vec2::new(1.5, 0.3));
+    /// let config = GjkEpaConfig {
+    ///     gjk_iteration_limit: 64,
+    ///     direction_epsilon: 1e-6,
+    ///     ..GjkEpaConfig::DEFAULT
+    /// };
+    /// assert!(a.collides_rel_with(&b, &rel, &config));
+    /// ```
+    fn collides_rel_with(&self, b: &B, rel: &T, config: &GjkEpaConfig) -> bool {
+        let _ = config;
+        self.collides_rel(b, rel)
+    }
 }
 
 //
@@ -65,6 +109,12 @@ where
     /// # See also
     /// * [Penetrates2d::penetrates].
     fn collides(self, b: BB) -> bool;
+
+    /// Checks whether objects collide, reporting failed queries as [QueryResult2d::Unknown].
+    ///
+    /// # See also
+    /// * [CollidesRel2d::collides_rel_result].
+    fn collides_result(self, b: BB) -> QueryResult2d;
 }
 
 impl<'a, A: 'a, B: 'a, T, AA, BB> Collides2d<'a, A, B, T, BB> for AA
@@ -82,4 +132,11 @@ where
         let rel = a.transform.delta_transform(b.transform);
         a.shape.collides_rel(b.shape, &rel)
     }
+
+    fn collides_result(self, bb: BB) -> QueryResult2d {
+        let a: Collider2d<'a, A, T> = self.into();
+        let b: Collider2d<'a, B, T> = bb.into();
+        let rel = a.transform.delta_transform(b.transform);
+        a.shape.collides_rel_result(b.shape, &rel)
+    }
 }
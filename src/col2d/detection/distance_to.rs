@@ -2,6 +2,9 @@ use super::*;
 
 /// Trait for computing distance between `Self` and `T`.
 ///
+/// Queries that fail to converge report no distance. Use [DistanceToRel2d::distance_to_rel_result]
+/// to tell the two apart.
+///
 /// # See also
 /// * [Collides2d]
 pub trait DistanceToRel2d<B, T: Transformation2d> {
@@ -25,6 +28,33 @@ pub trait DistanceToRel2d<B, T: Transformation2d> {
     /// # See also
     /// * [Collides2d::collides].
     fn distance_to_rel(&self, t: &B, rel: &T) -> Option<Vec2>;
+
+    /// Computes the distance, reporting failed queries as [QueryResult2d::Unknown].
+    ///
+    /// The penetration of colliding objects is not computed.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// let a = Box2d::with_halfdims(1.0, 1.0);
+    /// let b = Box2d::with_halfdims(1.0, 1.0);
+    /// let rel = Translate2d::from(Vec2::new(3.0, 0.0));
+    /// let result = a.distance_to_rel_result(&b, &rel);
+    /// assert_eq!(result.distance(), Some(Vec2::new(1.0, 0.0)));
+    /// ```
+    fn distance_to_rel_result(&self, t: &B, rel: &T) -> QueryResult2d {
+        match self.distance_to_rel(t, rel) {
+            Some(distance) => QueryResult2d::separated(distance),
+            None => QueryResult2d::penetrating_unmeasured(),
+        }
+    }
+
+    /// Same as [DistanceToRel2d::distance_to_rel], running GJK/EPA with `config` instead of
+    /// [GjkEpaConfig::global].
+    fn distance_to_rel_with(&self, t: &B, rel: &T, config: &GjkEpaConfig) -> Option<Vec2> {
+        let _ = config;
+        self.distance_to_rel(t, rel)
+    }
 }
 
 /// Trait for computing distance between `Self` and `B`.
@@ -66,6 +96,12 @@ where
     /// # See also
     /// * [Collides2d::collides].
     fn distance_to(self, b: BB) -> Option<Vec2>;
+
+    /// Computes the distance, reporting failed queries as [QueryResult2d::Unknown].
+    ///
+    /// # See also
+    /// * [DistanceToRel2d::distance_to_rel_result].
+    fn distance_to_result(self, b: BB) -> QueryResult2d;
 }
 
 impl<'a, A: 'a, B: 'a, T, AA, BB> DistanceTo2d<'a, A, B, T, BB> for AA
@@ -81,4 +117,11 @@ where
         let rel = a.transform.delta_transform(b.transform);
         a.shape.distance_to_rel(b.shape, &rel)
     }
+
+    fn distance_to_result(self, bb: BB) -> QueryResult2d {
+        let a: Collider2d<'a, A, T> = self.into();
+        let b: Collider2d<'a, B, T> = bb.into();
+        let rel = a.transform.delta_transform(b.transform);
+        a.shape.distance_to_rel_result(b.shape, &rel)
+    }
 }
@@ -4,6 +4,9 @@ pub mod collides;
 pub mod distance_to;
 pub mod extreme_point;
 pub mod penetrates;
+pub mod point_query;
+pub mod query_result;
+pub mod ray_cast;
 pub mod sdf;
 pub mod sdfv;
 pub mod sdfv_common;
@@ -13,6 +16,9 @@ pub use collides::*;
 pub use distance_to::*;
 pub use extreme_point::*;
 pub use penetrates::*;
+pub use point_query::*;
+pub use query_result::*;
+pub use ray_cast::*;
 pub use sdf::*;
 pub use sdfv::*;
 pub use sdfv_common::*;
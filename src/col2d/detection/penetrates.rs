@@ -2,6 +2,9 @@ use super::*;
 
 /// Trait for computing smallest penetration vector between `Self` and `B`.
 ///
+/// Queries that fail to converge report no penetration. Use
+/// [PenetratesRel2d::penetrates_rel_result] to tell the two apart.
+///
 /// # See also
 /// * [Collides2d]
 pub trait PenetratesRel2d<B, T: Transformation2d> {
@@ -23,6 +26,34 @@ pub trait PenetratesRel2d<B, T: Transformation2d> {
     /// # See also
     /// * [Collides2d::collides].
     fn penetrates_rel(&self, t: &B, rel: &T) -> Option<Vec2>;
+
+    /// Computes the smallest penetration vector, reporting failed queries as
+    /// [QueryResult2d::Unknown].
+    ///
+    /// The distance of separated objects is not computed.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// let a = Box2d::with_halfdims(1.0, 1.0);
+    /// let b = Box2d::with_halfdims(1.0, 1.0);
+    /// let rel = Translate2d::from(Vec2::new(1.0, 0.0));
+    /// let result = a.penetrates_rel_result(&b, &rel);
+    /// assert_eq!(result.penetration(), Some(Vec2::new(-1.0, 0.0)));
+    /// ```
+    fn penetrates_rel_result(&self, t: &B, rel: &T) -> QueryResult2d {
+        match self.penetrates_rel(t, rel) {
+            Some(penetration) => QueryResult2d::penetrating(penetration),
+            None => QueryResult2d::separated_unmeasured(),
+        }
+    }
+
+    /// Same as [PenetratesRel2d::penetrates_rel], running GJK/EPA with `config` instead of
+    /// [GjkEpaConfig::global].
+    fn penetrates_rel_with(&self, t: &B, rel: &T, config: &GjkEpaConfig) -> Option<Vec2> {
+        let _ = config;
+        self.penetrates_rel(t, rel)
+    }
 }
 
 /// Trait for computing smallest penetration vector.
@@ -58,6 +89,13 @@ where
     /// # See also
     /// * [Collides2d::collides].
     fn penetrates(self, b: BB) -> Option<Vec2>;
+
+    /// Computes the smallest penetration vector, reporting failed queries as
+    /// [QueryResult2d::Unknown].
+    ///
+    /// # See also
+    /// * [PenetratesRel2d::penetrates_rel_result].
+    fn penetrates_result(self, b: BB) -> QueryResult2d;
 }
 
 impl<'a, A: 'a, B: 'a, T, AA, BB> Penetrates2d<'a, A, B, T, BB> for AA
@@ -73,4 +111,11 @@ where
         let rel = a.transform.delta_transform(b.transform);
         a.shape.penetrates_rel(b.shape, &rel)
     }
+
+    fn penetrates_result(self, bb: BB) -> QueryResult2d {
+        let a: Collider2d<'a, A, T> = self.into();
+        let b: Collider2d<'a, B, T> = bb.into();
+        let rel = a.transform.delta_transform(b.transform);
+        a.shape.penetrates_rel_result(b.shape, &rel)
+    }
 }
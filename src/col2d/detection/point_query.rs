@@ -0,0 +1,156 @@
+use super::*;
+
+/// Part of a shape's boundary closest to a queried point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature2d {
+    /// Vertex with the given index, see the shape for how vertices are numbered.
+    Vertex(usize),
+    /// Edge starting at the vertex with the given index.
+    Edge(usize),
+    /// Smooth part of the boundary, e.g. of a [Ball].
+    Curve,
+    /// Boundary of the tile at the given position.
+    Tile { x: i32, y: i32 },
+    /// The shape has no boundary, e.g. an empty tilemap.
+    Unknown,
+}
+
+/// Closest point on a shape's boundary.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointProjection2d {
+    /// Closest point on the boundary.
+    pub point: Vec2,
+    /// Whether the queried point is inside the shape.
+    pub is_inside: bool,
+    pub feature: Feature2d,
+}
+
+/// Trait for querying a shape with a single point.
+///
+/// Simpler than building a [Point] collider and interpreting the sign convention of
+/// [Sdfv2d::sdfv].
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// let wall = Box2d::with_halfdims(1.0, 4.0);
+/// let t = Vec2::new(10.0, 0.0);
+/// let mouse = Vec2::new(8.0, 1.0);
+/// assert!(!wall.contains_point_t(&t, mouse));
+/// let projection = wall.project_point_t(&t, mouse);
+/// assert_eq!(projection.point, Vec2::new(9.0, 1.0));
+/// assert_eq!(projection.feature, Feature2d::Edge(3));
+/// ```
+pub trait PointQuery2d {
+    /// Projects `point` onto the boundary, in `self`-centric space.
+    fn project_point(&self, point: Vec2) -> PointProjection2d;
+
+    /// Checks whether `point` is inside, in `self`-centric space.
+    fn contains_point(&self, point: Vec2) -> bool {
+        self.project_point(point).is_inside
+    }
+
+    /// Projects a world-space `point` onto the boundary of the shape transformed by `t`.
+    ///
+    /// # Important
+    /// Under non-uniform scale the projection is not necessarily the closest point.
+    fn project_point_t(&self, t: &impl Transformation2d, point: Vec2) -> PointProjection2d {
+        let projection = self.project_point(t.unapply(point));
+        PointProjection2d {
+            point: t.apply(projection.point),
+            ..projection
+        }
+    }
+
+    /// Checks whether a world-space `point` is inside the shape transformed by `t`.
+    fn contains_point_t(&self, t: &impl Transformation2d, point: Vec2) -> bool {
+        self.contains_point(t.unapply(point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn ball() {
+        let ball = Ball::with_radius(2.0);
+        let projection = ball.project_point(Vec2::new(0.0, 1.0));
+        assert_eq!(
+            projection,
+            PointProjection2d {
+                point: Vec2::new(0.0, 2.0),
+                is_inside: true,
+                feature: Feature2d::Curve,
+            }
+        );
+        assert!(!ball.contains_point_t(&Vec2::new(1.0, 0.0), Vec2::new(-1.5, 0.0)));
+        assert!(ball.project_point(Vec2::ZERO).is_inside);
+    }
+
+    #[test_log::test]
+    fn box_features() {
+        let b = Box2d::with_halfdims(2.0, 1.0);
+        let projection = b.project_point(Vec2::new(3.0, 3.0));
+        assert_eq!(projection.point, Vec2::new(2.0, 1.0));
+        assert_eq!(projection.feature, Feature2d::Vertex(2));
+        assert!(!projection.is_inside);
+
+        let projection = b.project_point(Vec2::new(0.5, -0.75));
+        assert_eq!(projection.point, Vec2::new(0.5, -1.0));
+        assert_eq!(projection.feature, Feature2d::Edge(0));
+        assert!(projection.is_inside);
+    }
+
+    #[test_log::test]
+    fn rotated_box() {
+        let b = Box2d::with_halfdims(2.0, 1.0);
+        let t = Transform2d::from_translation(Vec2::new(5.0, 0.0))
+            .with_angle(core::f32::consts::FRAC_PI_2);
+        assert!(b.contains_point_t(&t, Vec2::new(5.5, 1.5)));
+        assert!(!b.contains_point_t(&t, Vec2::new(6.5, 0.0)));
+        let projection = b.project_point_t(&t, Vec2::new(7.0, 0.0));
+        assert!(projection.point.abs_diff_eq(Vec2::new(6.0, 0.0), 1e-5));
+    }
+
+    #[test_log::test]
+    fn poly_matches_box() {
+        let b = Box2d::with_halfdims(2.0, 1.0);
+        let poly = Poly2d::from(b);
+        for point in [
+            Vec2::new(3.0, 3.0),
+            Vec2::new(0.5, -0.75),
+            Vec2::new(-1.9, 0.2),
+            Vec2::new(-3.0, 0.2),
+        ] {
+            let expected = b.project_point(point);
+            let actual = poly.project_point(point);
+            assert_eq!(actual.is_inside, expected.is_inside);
+            assert!(actual.point.abs_diff_eq(expected.point, 1e-5));
+        }
+        let projection = poly.project_point(Vec2::new(3.0, 3.0));
+        assert_eq!(poly.points[1], Vec2::new(2.0, 1.0));
+        assert_eq!(projection.feature, Feature2d::Vertex(1));
+    }
+
+    #[test_log::test]
+    fn rounded_box() {
+        let b = RoundedBox2d::new(Vec2::new(1.0, 1.0), 0.5);
+        let projection = b.project_point(Vec2::new(3.0, 3.0));
+        let corner = Vec2::ONE + 0.5 * Vec2::ONE.normalize();
+        assert!(projection.point.abs_diff_eq(corner, 1e-5));
+        assert_eq!(projection.feature, Feature2d::Vertex(2));
+        let projection = b.project_point(Vec2::new(0.2, 0.9));
+        assert_eq!(projection.point, Vec2::new(0.2, 1.5));
+        assert!(projection.is_inside);
+        assert!(b.contains_point(Vec2::new(1.3, 1.3)));
+        assert!(!b.contains_point(Vec2::new(1.4, 1.4)));
+    }
+
+    #[test_log::test]
+    fn point() {
+        assert!(Point.contains_point(Vec2::ZERO));
+        assert!(!Point.contains_point_t(&Vec2::X, Vec2::ZERO));
+        assert_eq!(Point.project_point_t(&Vec2::X, Vec2::Y).point, Vec2::X);
+    }
+}
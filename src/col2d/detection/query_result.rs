@@ -0,0 +1,249 @@
+use super::*;
+
+/// Outcome of a signed-distance query between two shapes.
+///
+/// Both answers carry the *vector* signed-distance `sdfv`. For separated shapes it points from
+/// `a` towards `b` and spans the gap between them, for penetrating ones it is the smallest
+/// translation that moves `a` out of `b`.
+///
+/// # Important
+/// Queries only compute what was requested (see [SdfvCommonRel2d]). Vectors that were not
+/// requested are `NaN`, e.g. `sdfv` of [QueryResult2d::Separated] in a penetration-only query.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// let a = Box2d::with_halfdims(1.0, 1.0);
+/// let b = Box2d::with_halfdims(1.0, 1.0);
+/// let rel = Translate2d::from(Vec2::new(3.0, 0.0));
+/// let result = a.sdfv_rel(&b, &rel);
+/// assert_eq!(
+///     result,
+///     QueryResult2d::Separated {
+///         sdfv: Vec2::new(1.0, 0.0),
+///     }
+/// );
+/// assert_eq!(result.sdf(), Some(1.0));
+/// assert_eq!(result.normal(), Some(Vec2::X));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueryResult2d {
+    /// Shapes do not overlap.
+    Separated { sdfv: Vec2 },
+    /// Shapes overlap.
+    Penetrating { sdfv: Vec2 },
+    /// The query could not determine the answer.
+    Unknown { reason: UnknownReason },
+}
+
+/// Reason why a query returned [QueryResult2d::Unknown].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownReason {
+    /// Iterative solver did not converge within the iteration limit.
+    NotConverged { iterations: usize },
+    /// Solver reached an inconsistent state, typically due to numerical issues.
+    Degenerate,
+    /// The query is not implemented for this pair of shapes.
+    Unsupported,
+}
+
+impl QueryResult2d {
+    /// Separated result from a *vector* signed-distance pointing from `a` towards `b`.
+    pub const fn separated(sdfv: Vec2) -> Self {
+        Self::Separated { sdfv }
+    }
+
+    /// Penetrating result from a *vector* signed-distance (i.e. the vector that moves `a` out of
+    /// `b`).
+    pub const fn penetrating(sdfv: Vec2) -> Self {
+        Self::Penetrating { sdfv }
+    }
+
+    /// Separated result where the distance was not computed.
+    pub const fn separated_unmeasured() -> Self {
+        Self::separated(Vec2::NAN)
+    }
+
+    /// Penetrating result where the depth was not computed.
+    pub const fn penetrating_unmeasured() -> Self {
+        Self::penetrating(Vec2::NAN)
+    }
+
+    /// Builds a result from the legacy `(collides, sdfv)` pair.
+    pub const fn from_sdfv(collides: bool, sdfv: Vec2) -> Self {
+        if collides {
+            Self::penetrating(sdfv)
+        } else {
+            Self::separated(sdfv)
+        }
+    }
+
+    /// Whether the shapes collide, `None` if unknown.
+    pub fn collides(&self) -> Option<bool> {
+        match self {
+            Self::Separated { .. } => Some(false),
+            Self::Penetrating { .. } => Some(true),
+            Self::Unknown { .. } => None,
+        }
+    }
+
+    /// Whether the query failed to produce an answer.
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Self::Unknown { .. })
+    }
+
+    /// Signed distance, negative when penetrating.
+    pub fn sdf(&self) -> Option<f32> {
+        match *self {
+            Self::Separated { sdfv } => Some(sdfv.length()),
+            Self::Penetrating { sdfv } => Some(-sdfv.length()),
+            Self::Unknown { .. } => None,
+        }
+    }
+
+    /// *Vector* signed-distance, see [Sdfv2d::sdfv].
+    pub fn sdfv(&self) -> Option<Vec2> {
+        match *self {
+            Self::Separated { sdfv } | Self::Penetrating { sdfv } => Some(sdfv),
+            Self::Unknown { .. } => None,
+        }
+    }
+
+    /// Unit normal pointing from `a` towards `b`, zero if the shapes are just touching.
+    pub fn normal(&self) -> Option<Vec2> {
+        match *self {
+            Self::Separated { sdfv } => Some(unit_or_self(sdfv)),
+            Self::Penetrating { sdfv } => Some(-unit_or_self(sdfv)),
+            Self::Unknown { .. } => None,
+        }
+    }
+
+    /// Penetration vector if the shapes are penetrating.
+    pub fn penetration(&self) -> Option<Vec2> {
+        match *self {
+            Self::Penetrating { sdfv } => Some(sdfv),
+            _ => None,
+        }
+    }
+
+    /// Distance vector if the shapes are separated.
+    pub fn distance(&self) -> Option<Vec2> {
+        match *self {
+            Self::Separated { sdfv } => Some(sdfv),
+            _ => None,
+        }
+    }
+
+    /// Transforms the *vector* signed-distance, e.g. from local to world space.
+    pub fn map_sdfv(self, f: impl FnOnce(Vec2) -> Vec2) -> Self {
+        match self {
+            Self::Separated { sdfv } => Self::Separated { sdfv: f(sdfv) },
+            Self::Penetrating { sdfv } => Self::Penetrating { sdfv: f(sdfv) },
+            unknown => unknown,
+        }
+    }
+
+    /// Swaps the roles of `a` and `b`.
+    pub fn reversed(self) -> Self {
+        self.map_sdfv(|sdfv| -sdfv)
+    }
+}
+
+fn unit_or_self(v: Vec2) -> Vec2 {
+    let length = v.length();
+    if length > 0.0 {
+        v / length
+    } else {
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn ball_v_ball_separated() {
+        let a = Ball::with_radius(1.0);
+        let b = Ball::with_radius(1.0);
+        let rel = Vec2::new(0.0, 3.0);
+        let result = a.sdfv_rel(&b, &rel);
+        assert_eq!(
+            result,
+            QueryResult2d::Separated {
+                sdfv: Vec2::new(0.0, 1.0),
+            }
+        );
+        assert_eq!(result.normal(), Some(Vec2::Y));
+        assert_eq!(result.collides(), Some(false));
+        assert_eq!(result.sdf(), Some(1.0));
+        assert_eq!(result.penetration(), None);
+    }
+
+    #[test_log::test]
+    fn ball_v_ball_penetrating() {
+        let a = Ball::with_radius(1.0);
+        let b = Ball::with_radius(1.0);
+        let rel = Vec2::new(-1.5, 0.0);
+        let result = a.sdfv_rel(&b, &rel);
+        assert_eq!(
+            result,
+            QueryResult2d::Penetrating {
+                sdfv: Vec2::new(0.5, 0.0),
+            }
+        );
+        assert_eq!(result.normal(), Some(-Vec2::X));
+        assert_eq!(result.sdf(), Some(-0.5));
+        assert_eq!(result.penetration(), Some(Vec2::new(0.5, 0.0)));
+        assert_eq!(result.distance(), None);
+    }
+
+    #[test_log::test]
+    fn reversed_flips_normal() {
+        let a = Box2d::with_halfdims(1.0, 1.0);
+        let rel = Vec2::new(0.5, 0.0);
+        let forward = a.sdfv_rel(&Point, &rel);
+        let backward = Point.sdfv_rel(&a, &-rel);
+        assert_eq!(forward, backward.reversed());
+    }
+
+    #[test_log::test]
+    fn unknown_has_no_answer() {
+        let result = QueryResult2d::Unknown {
+            reason: UnknownReason::NotConverged { iterations: 20 },
+        };
+        assert!(result.is_unknown());
+        assert_eq!(result.collides(), None);
+        assert_eq!(result.sdfv(), None);
+    }
+
+    struct Unsolvable;
+
+    impl<const P: bool, const D: bool, T: Transformation2d> SdfvCommonRel2d<P, D, Point, T>
+        for Unsolvable
+    {
+        fn sdfv_common_rel(&self, _: &Point, _: &T) -> QueryResult2d {
+            QueryResult2d::Unknown {
+                reason: UnknownReason::Degenerate,
+            }
+        }
+    }
+
+    #[test_log::test]
+    fn result_queries_report_unknown() {
+        let rel = Vec2::ZERO;
+        assert!(Unsolvable.collides_rel_result(&Point, &rel).is_unknown());
+        assert!(Unsolvable.penetrates_rel_result(&Point, &rel).is_unknown());
+        assert!(Unsolvable.distance_to_rel_result(&Point, &rel).is_unknown());
+        assert!(!Unsolvable.collides_rel(&Point, &rel));
+    }
+
+    #[test_log::test]
+    fn collision_only_query_does_not_measure() {
+        let a = Box2d::with_halfdims(1.0, 1.0);
+        let rel = Vec2::new(0.5, 0.0);
+        let result = SdfvCommonRel2d::<false, false, _, _>::sdfv_common_rel(&a, &Point, &rel);
+        assert_eq!(result.collides(), Some(true));
+        assert!(result.sdf().unwrap().is_nan());
+    }
+}
@@ -0,0 +1,183 @@
+use super::*;
+
+/// Half-line starting at `origin` going along `direction`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray2d {
+    pub origin: Vec2,
+    /// Direction of the ray, distances along the ray are measured in multiples of its length.
+    pub direction: Vec2,
+}
+
+impl Ray2d {
+    pub const fn new(origin: Vec2, direction: Vec2) -> Self {
+        Self { origin, direction }
+    }
+
+    /// Point at `distance` along the ray.
+    pub fn at(&self, distance: f32) -> Vec2 {
+        self.origin + distance * self.direction
+    }
+}
+
+/// First intersection of a ray with a shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit2d {
+    /// Distance along the ray, see [Ray2d::direction].
+    pub distance: f32,
+    pub point: Vec2,
+    /// Unit normal of the boundary at [RayHit2d::point], zero if the ray starts inside the shape.
+    pub normal: Vec2,
+}
+
+impl RayHit2d {
+    /// Hit of a ray starting inside the shape.
+    pub fn inside(ray: &Ray2d) -> Self {
+        Self {
+            distance: 0.0,
+            point: ray.origin,
+            normal: Vec2::ZERO,
+        }
+    }
+}
+
+/// Trait for casting rays against a shape.
+///
+/// Shapes are solid, a ray starting inside hits at distance `0.0`.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// let wall = Box2d::with_halfdims(1.0, 4.0);
+/// let t = Vec2::new(10.0, 0.0);
+/// let ray = Ray2d::new(Vec2::ZERO, Vec2::X);
+/// let hit = wall.cast_ray_t(&t, &ray, 100.0).unwrap();
+/// assert_eq!(hit.distance, 9.0);
+/// assert_eq!(hit.normal, -Vec2::X);
+/// assert_eq!(wall.cast_ray_t(&t, &ray, 5.0), None);
+/// ```
+pub trait RayCast2d {
+    /// Casts `ray` in `self`-centric space, ignoring hits farther than `max_distance`.
+    fn cast_ray(&self, ray: &Ray2d, max_distance: f32) -> Option<RayHit2d>;
+
+    /// Casts a world-space `ray` against the shape transformed by `t`.
+    ///
+    /// # Important
+    /// Under non-uniform scale the normal is not necessarily perpendicular to the boundary.
+    fn cast_ray_t(
+        &self,
+        t: &impl Transformation2d,
+        ray: &Ray2d,
+        max_distance: f32,
+    ) -> Option<RayHit2d> {
+        // Distances along the ray are preserved by affine transformations as long as the direction
+        // is transformed along with the origin.
+        let origin = t.unapply(ray.origin);
+        let direction = t.unapply(ray.origin + ray.direction) - origin;
+        let hit = self.cast_ray(&Ray2d::new(origin, direction), max_distance)?;
+        Some(RayHit2d {
+            distance: hit.distance,
+            point: t.apply(hit.point),
+            normal: t.apply_normal(hit.normal),
+        })
+    }
+}
+
+/// Clips `ray` against the convex polygon given by its edges' outward normals and offsets.
+pub(crate) fn cast_ray_convex(
+    ray: &Ray2d,
+    max_distance: f32,
+    planes: impl IntoIterator<Item = (Vec2, f32)>,
+) -> Option<RayHit2d> {
+    let mut enter = 0.0;
+    let mut exit = max_distance;
+    let mut normal = Vec2::ZERO;
+    for (plane_normal, offset) in planes {
+        let distance = plane_normal.dot(ray.origin) - offset;
+        let speed = plane_normal.dot(ray.direction);
+        if speed == 0.0 {
+            if distance > 0.0 {
+                return None;
+            }
+            continue;
+        }
+        let t = -distance / speed;
+        if speed < 0.0 {
+            if t > enter {
+                enter = t;
+                normal = plane_normal;
+            }
+        } else {
+            exit = exit.min(t);
+        }
+        if enter > exit {
+            return None;
+        }
+    }
+    Some(RayHit2d {
+        distance: enter,
+        point: ray.at(enter),
+        normal,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn ball() {
+        let ball = Ball::with_radius(1.0);
+        let hit = ball
+            .cast_ray(&Ray2d::new(Vec2::new(-3.0, 0.0), Vec2::X), 10.0)
+            .unwrap();
+        assert_eq!(hit.distance, 2.0);
+        assert_eq!(hit.normal, -Vec2::X);
+        assert_eq!(
+            ball.cast_ray(&Ray2d::new(Vec2::new(-3.0, 1.5), Vec2::X), 10.0),
+            None
+        );
+        assert_eq!(
+            ball.cast_ray(&Ray2d::new(Vec2::new(-3.0, 0.0), -Vec2::X), 10.0),
+            None
+        );
+        let ray = Ray2d::new(Vec2::new(0.5, 0.0), Vec2::Y);
+        assert_eq!(ball.cast_ray(&ray, 10.0), Some(RayHit2d::inside(&ray)));
+    }
+
+    #[test_log::test]
+    fn rotated_box() {
+        let b = Box2d::with_halfdims(2.0, 1.0);
+        let t = Transform2d::from_translation(Vec2::new(0.0, 5.0))
+            .with_angle(core::f32::consts::FRAC_PI_2);
+        let hit = b
+            .cast_ray_t(&t, &Ray2d::new(Vec2::ZERO, Vec2::Y), 10.0)
+            .unwrap();
+        assert!((hit.distance - 3.0).abs() < 1e-5);
+        assert!(hit.normal.abs_diff_eq(-Vec2::Y, 1e-5));
+        assert!(hit.point.abs_diff_eq(Vec2::new(0.0, 3.0), 1e-5));
+    }
+
+    #[test_log::test]
+    fn scaled_ray() {
+        let ball = Ball::with_radius(1.0);
+        let t = Transform2d::from_scale(Vec2::splat(2.0));
+        let ray = Ray2d::new(Vec2::new(-10.0, 0.0), 2.0 * Vec2::X);
+        let hit = ball.cast_ray_t(&t, &ray, 10.0).unwrap();
+        assert!((hit.distance - 4.0).abs() < 1e-5);
+        assert!(hit.point.abs_diff_eq(Vec2::new(-2.0, 0.0), 1e-5));
+    }
+
+    #[test_log::test]
+    fn poly_matches_box() {
+        let b = Box2d::with_halfdims(2.0, 1.0);
+        let poly = Poly2d::from(b);
+        for ray in [
+            Ray2d::new(Vec2::new(-5.0, 0.5), Vec2::X),
+            Ray2d::new(Vec2::new(3.0, 3.0), Vec2::new(-1.0, -1.0)),
+            Ray2d::new(Vec2::new(0.0, -3.0), Vec2::new(0.1, 1.0)),
+            Ray2d::new(Vec2::new(0.0, -3.0), Vec2::new(1.0, 0.0)),
+        ] {
+            assert_eq!(poly.cast_ray(&ray, 10.0), b.cast_ray(&ray, 10.0));
+        }
+    }
+}
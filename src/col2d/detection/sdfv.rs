@@ -17,12 +17,24 @@ pub trait SdfvRel2d<B, T: Transformation2d> {
     /// let a = Box2d::with_halfdims(1.0, 1.0);
     /// let b = Box2d::with_halfdims(1.0, 1.0);
     /// let rel = Translate2d::from(Vec2::new(1.0, 0.0));
-    /// assert_eq!(a.sdfv_rel(&b, &rel), (true, Vec2::new(-1.0, 0.0)));
+    /// assert_eq!(
+    ///     a.sdfv_rel(&b, &rel),
+    ///     QueryResult2d::Penetrating {
+    ///         sdfv: Vec2::new(-1.0, 0.0),
+    ///     }
+    /// );
     /// ```
     ///
     /// # See also
     /// * [Sdf2d::sdf].
-    fn sdfv_rel(&self, t: &B, rel: &T) -> (bool, Vec2);
+    fn sdfv_rel(&self, t: &B, rel: &T) -> QueryResult2d;
+
+    /// Same as [SdfvRel2d::sdfv_rel], running GJK/EPA with `config` instead of
+    /// [GjkEpaConfig::global].
+    fn sdfv_rel_with(&self, t: &B, rel: &T, config: &GjkEpaConfig) -> QueryResult2d {
+        let _ = config;
+        self.sdfv_rel(t, rel)
+    }
 }
 
 /// Trait for computing the *vector* signed-distance between `Self` and `B`.
@@ -52,12 +64,12 @@ where
     ///     shape: &Box2d::with_halfdims(1.0, 1.0),
     ///     transform: &Vec2::new(1.0, 0.0),
     /// };
-    /// assert_eq!(a.sdfv(b), (true, Vec2::new(-1.0, 0.0)));
+    /// assert_eq!(a.sdfv(b).sdfv(), Some(Vec2::new(-1.0, 0.0)));
     /// ```
     ///
     /// # See also
     /// * [Sdf2d::sdf].
-    fn sdfv(self, b: BB) -> (bool, Vec2);
+    fn sdfv(self, b: BB) -> QueryResult2d;
 }
 
 impl<'a, A: 'a, B: 'a, T, AA, BB> Sdfv2d<'a, A, B, T, BB> for AA
@@ -67,12 +79,12 @@ where
     Collider2d<'a, A, T>: From<AA>,
     Collider2d<'a, B, T>: From<BB>,
 {
-    fn sdfv(self, bb: BB) -> (bool, Vec2) {
+    fn sdfv(self, bb: BB) -> QueryResult2d {
         let a: Collider2d<'a, A, T> = self.into();
         let b: Collider2d<'a, B, T> = bb.into();
         let rel = a.transform.delta_transform(b.transform);
-        let (collides, sdfv_local) = a.shape.sdfv_rel(b.shape, &rel);
-        let sdfv = a.transform.apply_normal(sdfv_local);
-        (collides, sdfv)
+        a.shape
+            .sdfv_rel(b.shape, &rel)
+            .map_sdfv(|sdfv| a.transform.apply_normal(sdfv))
     }
 }
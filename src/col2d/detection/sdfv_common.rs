@@ -1,3 +1,5 @@
+use crate::warn;
+
 use super::*;
 
 /// Trait for computing the *vector* signed-distance between `Self` and `B`.
@@ -13,6 +15,10 @@ pub trait SdfvCommonRel2d<
 {
     /// Computes *vector* signed-distance between `self` and `t` in `self`-centric space.
     ///
+    /// Penetration depth is only computed when `COMPUTE_PENETRATION` is set and separation
+    /// distance only when `COMPUTE_DISTANCE` is set, otherwise the respective fields of
+    /// [QueryResult2d] are `NaN`.
+    ///
     /// # Arguments
     /// * `b` - The object to compute distance to
     /// * `rel` - The *relative* transform from `self` to `b`
@@ -23,12 +29,22 @@ pub trait SdfvCommonRel2d<
     /// let a = Box2d::with_halfdims(1.0, 1.0);
     /// let b = Box2d::with_halfdims(1.0, 1.0);
     /// let rel = Translate2d::from(Vec2::new(1.0, 0.0));
-    /// assert_eq!(a.sdfv_rel(&b, &rel), (true, Vec2::new(-1.0, 0.0)));
+    /// let result = SdfvCommonRel2d::<false, false, _, _>::sdfv_common_rel(&a, &b, &rel);
+    /// assert_eq!(result.collides(), Some(true));
     /// ```
     ///
     /// # See also
     /// * [Sdf2d::sdf].
-    fn sdfv_common_rel(&self, b: &B, rel: &T) -> (bool, Vec2);
+    fn sdfv_common_rel(&self, b: &B, rel: &T) -> QueryResult2d;
+
+    /// Same as [SdfvCommonRel2d::sdfv_common_rel], running GJK/EPA with `config` instead of
+    /// [GjkEpaConfig::global].
+    ///
+    /// Implementations that don't use GJK/EPA can ignore `config`.
+    fn sdfv_common_rel_with(&self, b: &B, rel: &T, config: &GjkEpaConfig) -> QueryResult2d {
+        let _ = config;
+        self.sdfv_common_rel(b, rel)
+    }
 }
 
 //
@@ -39,7 +55,19 @@ where
     T: Transformation2d,
 {
     fn collides_rel(&self, b: &B, rel: &T) -> bool {
-        SdfvCommonRel2d::<false, false, B, T>::sdfv_common_rel(self, b, rel).0
+        collides(SdfvCommonRel2d::<false, false, B, T>::sdfv_common_rel(
+            self, b, rel,
+        ))
+    }
+
+    fn collides_rel_with(&self, b: &B, rel: &T, config: &GjkEpaConfig) -> bool {
+        collides(SdfvCommonRel2d::<false, false, B, T>::sdfv_common_rel_with(
+            self, b, rel, config,
+        ))
+    }
+
+    fn collides_rel_result(&self, b: &B, rel: &T) -> QueryResult2d {
+        SdfvCommonRel2d::<false, false, B, T>::sdfv_common_rel(self, b, rel)
     }
 }
 
@@ -49,12 +77,24 @@ where
     T: Transformation2d,
 {
     fn penetrates_rel(&self, b: &B, rel: &T) -> Option<Vec2> {
-        let (collides, sdfv) = SdfvCommonRel2d::<true, false, B, T>::sdfv_common_rel(self, b, rel);
-        if collides {
-            Some(sdfv)
-        } else {
-            None
+        let result = SdfvCommonRel2d::<true, false, B, T>::sdfv_common_rel(self, b, rel);
+        if result.is_unknown() {
+            warn_unknown_result(&result);
+        }
+        result.penetration()
+    }
+
+    fn penetrates_rel_result(&self, b: &B, rel: &T) -> QueryResult2d {
+        SdfvCommonRel2d::<true, false, B, T>::sdfv_common_rel(self, b, rel)
+    }
+
+    fn penetrates_rel_with(&self, b: &B, rel: &T, config: &GjkEpaConfig) -> Option<Vec2> {
+        let result =
+            SdfvCommonRel2d::<true, false, B, T>::sdfv_common_rel_with(self, b, rel, config);
+        if result.is_unknown() {
+            warn_unknown_result(&result);
         }
+        result.penetration()
     }
 }
 
@@ -64,12 +104,24 @@ where
     T: Transformation2d,
 {
     fn distance_to_rel(&self, b: &B, rel: &T) -> Option<Vec2> {
-        let (collides, sdfv) = SdfvCommonRel2d::<false, true, B, T>::sdfv_common_rel(self, b, rel);
-        if collides {
-            None
-        } else {
-            Some(sdfv)
+        let result = SdfvCommonRel2d::<false, true, B, T>::sdfv_common_rel(self, b, rel);
+        if result.is_unknown() {
+            warn_unknown_result(&result);
         }
+        result.distance()
+    }
+
+    fn distance_to_rel_result(&self, b: &B, rel: &T) -> QueryResult2d {
+        SdfvCommonRel2d::<false, true, B, T>::sdfv_common_rel(self, b, rel)
+    }
+
+    fn distance_to_rel_with(&self, b: &B, rel: &T, config: &GjkEpaConfig) -> Option<Vec2> {
+        let result =
+            SdfvCommonRel2d::<false, true, B, T>::sdfv_common_rel_with(self, b, rel, config);
+        if result.is_unknown() {
+            warn_unknown_result(&result);
+        }
+        result.distance()
     }
 }
 
@@ -78,9 +130,24 @@ where
     A: SdfvCommonRel2d<true, true, B, T>,
     T: Transformation2d,
 {
-    fn sdfv_rel(&self, b: &B, rel: &T) -> (bool, Vec2) {
+    fn sdfv_rel(&self, b: &B, rel: &T) -> QueryResult2d {
         SdfvCommonRel2d::<true, true, B, T>::sdfv_common_rel(self, b, rel)
     }
+
+    fn sdfv_rel_with(&self, b: &B, rel: &T, config: &GjkEpaConfig) -> QueryResult2d {
+        SdfvCommonRel2d::<true, true, B, T>::sdfv_common_rel_with(self, b, rel, config)
+    }
+}
+
+fn collides(result: QueryResult2d) -> bool {
+    result.collides().unwrap_or_else(|| {
+        warn_unknown_result(&result);
+        false
+    })
+}
+
+fn warn_unknown_result(result: &QueryResult2d) {
+    warn!("query result is unknown: {:?}", result);
 }
 
 //
@@ -95,4 +162,3 @@ where
 //         SdfvCommonRel2d::<false, false, B, T>::sdfv_common_rel(self, b, rel).0
 //     }
 // }
-
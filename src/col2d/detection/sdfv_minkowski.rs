@@ -1,5 +1,7 @@
+use crate::warn;
+
 use super::*;
-use crate::col2d::shape::local_minkowski_diff::LocalMinkowskiDiff2d;
+use crate::col2d::shape::local_minkowski_diff::{GjkEpaConfig, LocalMinkowskiDiff2d, SolverStats};
 
 pub trait DefaultMinkowski<T: ExtremePoint2d>: ExtremePoint2d {}
 
@@ -10,7 +12,28 @@ pub trait DefaultMinkowski<T: ExtremePoint2d>: ExtremePoint2d {}
 pub trait SdfvMinkowski2d<const COMPUTE_PENETRATION: bool, const COMPUTE_DISTANCE: bool> {
     /// # Important
     /// Calcuates positions in A's local space.
-    fn sdfv_minkowski(&self) -> (bool, Vec2);
+    fn sdfv_minkowski(&self) -> QueryResult2d {
+        self.sdfv_minkowski_stats(&mut SolverStats::default())
+    }
+
+    /// Same as [SdfvMinkowski2d::sdfv_minkowski], additionally accumulating solver diagnostics
+    /// into `stats`.
+    fn sdfv_minkowski_stats(&self, stats: &mut SolverStats) -> QueryResult2d;
+}
+
+impl<A, B> SdfRel2d<B> for A
+where
+    A: DefaultMinkowski<B>,
+    B: ExtremePoint2d,
+{
+    fn sdf_rel(&self, other: &B, rel: &impl Transformation2d) -> f32 {
+        let diff = LocalMinkowskiDiff2d::raw(self, other, rel);
+        let result = SdfvMinkowski2d::<true, true>::sdfv_minkowski(&diff);
+        result.sdf().unwrap_or_else(|| {
+            warn!("query result is unknown: {:?}", result);
+            f32::NAN
+        })
+    }
 }
 
 impl<A, B, T> SdfvCommonRel2d<false, false, B, T> for A
@@ -19,10 +42,15 @@ where
     B: ExtremePoint2d,
     T: Transformation2d,
 {
-    fn sdfv_common_rel(&self, other: &B, rel: &T) -> (bool, Vec2) {
+    fn sdfv_common_rel(&self, other: &B, rel: &T) -> QueryResult2d {
         let diff = LocalMinkowskiDiff2d::raw(self, other, rel);
         SdfvMinkowski2d::<false, false>::sdfv_minkowski(&diff)
     }
+
+    fn sdfv_common_rel_with(&self, other: &B, rel: &T, config: &GjkEpaConfig) -> QueryResult2d {
+        let diff = LocalMinkowskiDiff2d::new(self, other, rel, *config);
+        SdfvMinkowski2d::<false, false>::sdfv_minkowski(&diff)
+    }
 }
 
 impl<A, B, T> SdfvCommonRel2d<false, true, B, T> for A
@@ -31,10 +59,15 @@ where
     B: ExtremePoint2d,
     T: Transformation2d,
 {
-    fn sdfv_common_rel(&self, other: &B, rel: &T) -> (bool, Vec2) {
+    fn sdfv_common_rel(&self, other: &B, rel: &T) -> QueryResult2d {
         let diff = LocalMinkowskiDiff2d::raw(self, other, rel);
         SdfvMinkowski2d::<false, true>::sdfv_minkowski(&diff)
     }
+
+    fn sdfv_common_rel_with(&self, other: &B, rel: &T, config: &GjkEpaConfig) -> QueryResult2d {
+        let diff = LocalMinkowskiDiff2d::new(self, other, rel, *config);
+        SdfvMinkowski2d::<false, true>::sdfv_minkowski(&diff)
+    }
 }
 
 impl<A, B, T> SdfvCommonRel2d<true, false, B, T> for A
@@ -43,10 +76,15 @@ where
     B: ExtremePoint2d,
     T: Transformation2d,
 {
-    fn sdfv_common_rel(&self, other: &B, rel: &T) -> (bool, Vec2) {
+    fn sdfv_common_rel(&self, other: &B, rel: &T) -> QueryResult2d {
         let diff = LocalMinkowskiDiff2d::raw(self, other, rel);
         SdfvMinkowski2d::<true, false>::sdfv_minkowski(&diff)
     }
+
+    fn sdfv_common_rel_with(&self, other: &B, rel: &T, config: &GjkEpaConfig) -> QueryResult2d {
+        let diff = LocalMinkowskiDiff2d::new(self, other, rel, *config);
+        SdfvMinkowski2d::<true, false>::sdfv_minkowski(&diff)
+    }
 }
 
 impl<A, B, T> SdfvCommonRel2d<true, true, B, T> for A
@@ -55,8 +93,13 @@ where
     B: ExtremePoint2d,
     T: Transformation2d,
 {
-    fn sdfv_common_rel(&self, other: &B, rel: &T) -> (bool, Vec2) {
+    fn sdfv_common_rel(&self, other: &B, rel: &T) -> QueryResult2d {
         let diff = LocalMinkowskiDiff2d::raw(self, other, rel);
         SdfvMinkowski2d::<true, true>::sdfv_minkowski(&diff)
     }
+
+    fn sdfv_common_rel_with(&self, other: &B, rel: &T, config: &GjkEpaConfig) -> QueryResult2d {
+        let diff = LocalMinkowskiDiff2d::new(self, other, rel, *config);
+        SdfvMinkowski2d::<true, true>::sdfv_minkowski(&diff)
+    }
 }
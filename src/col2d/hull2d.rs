@@ -0,0 +1,88 @@
+use super::*;
+
+/// Convex hull of `points` in counter-clockwise order, without collinear points.
+///
+/// Uses Andrew's monotone chain. Fewer than three distinct points are returned as they are (sorted
+/// and deduplicated), collinear points reduce to the two extremes.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// let hull = convex_hull(&[
+///     Vec2::new(0.0, 0.0),
+///     Vec2::new(1.0, 1.0),
+///     Vec2::new(2.0, 0.0),
+///     Vec2::new(1.0, 0.5),
+///     Vec2::new(1.0, -1.0),
+/// ]);
+/// assert_eq!(
+///     hull,
+///     vec![
+///         Vec2::new(0.0, 0.0),
+///         Vec2::new(1.0, -1.0),
+///         Vec2::new(2.0, 0.0),
+///         Vec2::new(1.0, 1.0),
+///     ]
+/// );
+/// ```
+pub fn convex_hull(points: &[Vec2]) -> Vec<Vec2> {
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    let turns_left = |hull: &[Vec2], p: Vec2| {
+        let [.., a, b] = hull else {
+            return true;
+        };
+        (*b - *a).perp_dot(p - *a) > 0.0
+    };
+
+    let mut hull: Vec<Vec2> = Vec::with_capacity(2 * points.len());
+    for &p in points.iter() {
+        while hull.len() >= 2 && !turns_left(&hull, p) {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    let lower_len = hull.len() + 1;
+    for &p in points.iter().rev().skip(1) {
+        while hull.len() >= lower_len && !turns_left(&hull, p) {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    hull.pop();
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn degenerate() {
+        assert_eq!(convex_hull(&[]), vec![]);
+        assert_eq!(convex_hull(&[Vec2::ONE, Vec2::ONE]), vec![Vec2::ONE]);
+        assert_eq!(
+            convex_hull(&[Vec2::ZERO, Vec2::new(2.0, 2.0), Vec2::ONE]),
+            vec![Vec2::ZERO, Vec2::new(2.0, 2.0)]
+        );
+    }
+
+    #[test_log::test]
+    fn regular_poly_is_its_own_hull() {
+        let poly = Poly2d::regular(7, 1.0);
+        let mut inner = poly.points.clone();
+        inner.extend(poly.points.iter().map(|p| 0.5 * *p));
+        let hull = convex_hull(&inner);
+        assert_eq!(hull.len(), 7);
+        assert!(hull.iter().all(|p| poly.points.contains(p)));
+        let area: f32 = (0..hull.len())
+            .map(|i| 0.5 * hull[i].perp_dot(hull[(i + 1) % hull.len()]))
+            .sum();
+        assert!(area > 0.0);
+    }
+}
@@ -0,0 +1,202 @@
+use core::iter::Sum;
+use core::ops::Add;
+
+use super::*;
+
+/// Mass, center of mass and rotational inertia of a body.
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub struct Mass2d {
+    pub mass: f32,
+    /// Center of mass in the space of the shape (or world space once transformed).
+    pub center_of_mass: Vec2,
+    /// Rotational inertia about [Mass2d::center_of_mass].
+    pub inertia: f32,
+}
+
+impl Mass2d {
+    pub const ZERO: Self = Self {
+        mass: 0.0,
+        center_of_mass: Vec2::ZERO,
+        inertia: 0.0,
+    };
+
+    pub const fn new(mass: f32, center_of_mass: Vec2, inertia: f32) -> Self {
+        Self {
+            mass,
+            center_of_mass,
+            inertia,
+        }
+    }
+
+    /// Rotational inertia about `point` using the parallel-axis theorem.
+    pub fn inertia_about(&self, point: Vec2) -> f32 {
+        self.inertia + self.mass * self.center_of_mass.distance_squared(point)
+    }
+
+    /// Mass properties of the body transformed by `t`.
+    ///
+    /// # Important
+    /// Scale is assumed to be uniform, see [Transformation2d::scaling_factor].
+    pub fn transformed(&self, t: &impl Transformation2d) -> Self {
+        let scale = t.scaling_factor();
+        let scale_squared = scale * scale;
+        Self {
+            mass: self.mass * scale_squared,
+            center_of_mass: t.apply(self.center_of_mass),
+            inertia: self.inertia * scale_squared * scale_squared,
+        }
+    }
+}
+
+impl Add for Mass2d {
+    type Output = Self;
+
+    /// Combines two bodies into one rigid compound.
+    fn add(self, other: Self) -> Self {
+        let mass = self.mass + other.mass;
+        if mass <= 0.0 {
+            return Self::ZERO;
+        }
+        let center_of_mass =
+            (self.mass * self.center_of_mass + other.mass * other.center_of_mass) / mass;
+        Self {
+            mass,
+            center_of_mass,
+            inertia: self.inertia_about(center_of_mass) + other.inertia_about(center_of_mass),
+        }
+    }
+}
+
+impl Sum for Mass2d {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+/// Trait for computing mass properties of a shape with uniform density.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// // A dumbbell made of two balls connected by a bar.
+/// let ball = Ball::with_radius(1.0);
+/// let bar = Box2d::with_halfdims(2.0, 0.25);
+/// let dumbbell: Mass2d = [
+///     ball.mass_properties_t(1.0, &Vec2::new(-3.0, 0.0)),
+///     ball.mass_properties_t(1.0, &Vec2::new(3.0, 0.0)),
+///     bar.mass_properties(1.0),
+/// ]
+/// .into_iter()
+/// .sum();
+/// assert_eq!(dumbbell.center_of_mass, Vec2::ZERO);
+/// assert!(dumbbell.inertia > 2.0 * ball.mass_properties(1.0).inertia_about(Vec2::new(3.0, 0.0)));
+/// ```
+pub trait MassProperties2d {
+    /// Computes mass properties in `self`-centric space.
+    fn mass_properties(&self, density: f32) -> Mass2d;
+
+    /// Computes mass properties of the shape transformed by `t`.
+    fn mass_properties_t(&self, density: f32, t: &impl Transformation2d) -> Mass2d {
+        self.mass_properties(density).transformed(t)
+    }
+
+    fn area(&self) -> f32 {
+        self.mass_properties(1.0).mass
+    }
+
+    fn centroid(&self) -> Vec2 {
+        self.mass_properties(1.0).center_of_mass
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn parallel_axis() {
+        let ball = Ball::with_radius(2.0).mass_properties(1.0);
+        let shifted = ball.transformed(&Vec2::new(3.0, 4.0));
+        assert_eq!(shifted.inertia, ball.inertia);
+        assert_eq!(
+            shifted.inertia_about(Vec2::ZERO),
+            ball.inertia + 25.0 * ball.mass
+        );
+    }
+
+    #[test_log::test]
+    fn halves_make_whole() {
+        let whole = Box2d::with_halfdims(2.0, 1.0).mass_properties(3.0);
+        let half = Box2d::with_halfdims(1.0, 1.0);
+        let halves = half.mass_properties_t(3.0, &Vec2::new(-1.0, 0.0))
+            + half.mass_properties_t(3.0, &Vec2::new(1.0, 0.0));
+        assert_eq!(halves.mass, whole.mass);
+        assert_eq!(halves.center_of_mass, whole.center_of_mass);
+        assert!((halves.inertia - whole.inertia).abs() < 1e-4);
+    }
+
+    #[test_log::test]
+    fn scaled() {
+        let b = Box2d::with_halfdims(1.0, 1.0);
+        let t = Transform2d::from_scale(Vec2::splat(2.0));
+        let expected = Box2d::with_halfdims(2.0, 2.0).mass_properties(1.0);
+        assert_eq!(b.mass_properties_t(1.0, &t), expected);
+    }
+
+    #[test_log::test]
+    fn poly_matches_box() {
+        let b = Box2d::with_halfdims(1.5, 0.5);
+        let poly = Poly2d::from(b);
+        let expected = b.mass_properties(2.0);
+        let actual = poly.mass_properties(2.0);
+        assert!((actual.mass - expected.mass).abs() < 1e-5);
+        assert!(actual
+            .center_of_mass
+            .abs_diff_eq(expected.center_of_mass, 1e-5));
+        assert!((actual.inertia - expected.inertia).abs() < 1e-5);
+    }
+
+    #[test_log::test]
+    fn offset_triangle() {
+        // Clockwise winding on purpose.
+        let poly = Poly2d::new(&[
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 3.0),
+            Vec2::new(3.0, 0.0),
+        ]);
+        let mass = poly.mass_properties(1.0);
+        assert!((mass.mass - 4.5).abs() < 1e-5);
+        assert!(mass.center_of_mass.abs_diff_eq(Vec2::new(1.0, 1.0), 1e-5));
+        // Right triangle with legs `a`: I = a^4 / 18 about its centroid.
+        assert!((mass.inertia - 81.0 / 18.0).abs() < 1e-4);
+    }
+
+    #[test_log::test]
+    fn rounded_box_approaches_parts() {
+        let rounded = RoundedBox2d::new(Vec2::new(1.0, 0.5), 0.25);
+        let mass = rounded.mass_properties(1.0);
+        let expected_area =
+            4.0 * 1.0 * 0.5 + 2.0 * 0.25 * (2.0 + 1.0) + core::f32::consts::PI * 0.25 * 0.25;
+        assert!((mass.mass - expected_area).abs() < 1e-5);
+        assert!(mass.center_of_mass.abs_diff_eq(Vec2::ZERO, 1e-6));
+        let outer = Box2d::with_halfdims(1.25, 0.75).mass_properties(1.0);
+        let inner = Box2d::with_halfdims(1.0, 0.5).mass_properties(1.0);
+        assert!(inner.inertia < mass.inertia && mass.inertia < outer.inertia);
+
+        // Without the box part only the corners remain, making up a ball.
+        let corners = RoundedBox2d::new(Vec2::ZERO, 0.5).mass_properties(1.0);
+        let ball = Ball::with_radius(0.5).mass_properties(1.0);
+        assert!((corners.mass - ball.mass).abs() < 1e-5);
+        assert!((corners.inertia - ball.inertia).abs() < 1e-5);
+    }
+
+    #[test_log::test]
+    fn shape_union() {
+        let shape = Shape2d::ball(1.0);
+        assert_eq!(
+            shape.mass_properties(1.0),
+            Ball::with_radius(1.0).mass_properties(1.0)
+        );
+        assert_eq!(Point.area(), 0.0);
+    }
+}
@@ -4,13 +4,38 @@
 //! * [Ball] - ball
 //! * [Box2d] - 2D box
 //! * [RoundedBox2d] - 2D rounded box
+//! * [Poly2d] - 2D convex polygon
+//! * [Shape2d] - any of the above (except [RoundedBox2d]) chosen at runtime
+//! * [HalfSpace2d] - infinite floor, wall or kill plane (queried against all bounded shapes)
+//! * [Heightfield2d] - side-scroller terrain (queried against all bounded shapes)
 //! * [Tilemap] - 2D tilemap (requires feature `"tilemap"`)
 //!
 //! # Collision and Resolution:
 //! * [Collides2d::collides]
 //! * [Penetrates2d::penetrates]
 //! * [Sdf2d::sdf]
-//! * [Sdf2dVector::sdfv].
+//! * [Sdfv2d::sdfv] - returns [QueryResult2d]
+//! * [PointQuery2d::project_point_t] - closest boundary point and whether a point is inside
+//! * [RayCast2d::cast_ray_t] - first hit of a [Ray2d]
+//! * [visibility_polygon] - region lit from a point, for lighting and fog of war
+//! * [NavMeshBuilder2d] - navigation mesh around obstacles with [NavMesh2d::find_path]
+//!
+//! # Bounding Volumes:
+//! * [BoundingBox2d::bounding_box_t] - world-space [Aabb2d]
+//! * [BoundingVolume2d] - minimum-area [Obb2d] and minimum [BoundingCircle2d]
+//! * [convex_hull] - convex hull of a point set
+//!
+//! # Collider Generation:
+//! * [Bitmap2d::colliders] - convex pieces covering a sprite alpha mask or tilemap chunk
+//! * [simplify_polygon] - Douglas–Peucker polygon simplification
+//! * [convex_decomposition] - splits a polygon with holes into convex pieces
+//! * [convex_colliders] - convex [Poly2d] pieces of regions with holes
+//! * [polygon_boolean] - union, intersection and difference of regions, e.g. destructible terrain
+//!
+//! All queries are implemented for every pair of [Point], [Ball], [Box2d], [RoundedBox2d],
+//! [Poly2d] and [Shape2d].
+//! Custom convex shapes join in through [convex_shape](crate::convex_shape) and
+//! [shape_union](crate::shape_union).
 //!
 //! # Transformations:
 //! * [Translate2d] - translation
@@ -21,21 +46,52 @@
 
 pub use crate::Vec2;
 
+mod aabb2d;
+#[cfg(all(feature = "poly", feature = "std"))]
+mod bitmap2d;
+#[cfg(feature = "std")]
+mod boolean2d;
+mod bounding_volume2d;
 mod collider2d;
 mod detection;
+#[cfg(feature = "std")]
+mod hull2d;
+mod mass2d;
+#[cfg(all(feature = "poly", feature = "std"))]
+mod navmesh2d;
+#[cfg(feature = "std")]
+mod polygon2d;
 mod shape;
 mod transformation2d;
+#[cfg(feature = "std")]
+mod visibility2d;
 
 pub use crate::col::*;
+pub use aabb2d::*;
+#[cfg(all(feature = "poly", feature = "std"))]
+pub use bitmap2d::*;
+#[cfg(feature = "std")]
+pub use boolean2d::*;
+pub use bounding_volume2d::*;
 pub use collider2d::*;
 pub use detection::*;
+#[cfg(feature = "std")]
+pub use hull2d::*;
+pub use mass2d::*;
+#[cfg(all(feature = "poly", feature = "std"))]
+pub use navmesh2d::*;
+#[cfg(feature = "std")]
+pub use polygon2d::*;
 pub use shape::*;
 pub use transformation2d::*;
+#[cfg(feature = "std")]
+pub use visibility2d::*;
 
 /// Trait for computing bounding box of a shape.
 ///
 /// # See also
 /// * [Collides2d]
+/// * [BoundingBox2d] - world-space bounding box of a transformed shape
 pub trait SymmetricBoundingBox2d {
     /// Computes the bounding box.
     ///
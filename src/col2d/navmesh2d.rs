@@ -0,0 +1,696 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use super::*;
+
+/// Builds a [NavMesh2d] from walkable bounds and obstacle colliders.
+///
+/// Obstacles are grown by the agent radius and cut out of the bounds, the remaining free space is
+/// split into a constrained Delaunay triangulation. Curved shapes such as [Ball] are approximated
+/// within [NavMeshBuilder2d::with_tolerance].
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// let room = Aabb2d::new(Vec2::new(-5.0, -5.0), Vec2::new(5.0, 5.0));
+/// let pillar = Box2d::with_halfdims(1.0, 3.0);
+/// let navmesh = NavMeshBuilder2d::new(room)
+///     .with_agent_radius(0.5)
+///     .add_obstacle(&pillar, &Vec2::ZERO)
+///     .build();
+///
+/// let path = navmesh
+///     .find_path(Vec2::new(-4.0, 0.0), Vec2::new(4.0, 0.0))
+///     .unwrap();
+/// assert!(path.len() > 2);
+/// // Around the top or the bottom of the pillar.
+/// assert!(path.iter().any(|p| p.y.abs() > 3.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct NavMeshBuilder2d {
+    bounds: Aabb2d,
+    agent_radius: f32,
+    tolerance: f32,
+    obstacles: Vec<Vec<Vec2>>,
+}
+
+impl NavMeshBuilder2d {
+    pub fn new(bounds: Aabb2d) -> Self {
+        Self {
+            bounds,
+            agent_radius: 0.0,
+            tolerance: 0.05,
+            obstacles: Vec::new(),
+        }
+    }
+
+    /// Distance the agent keeps from obstacles and from the bounds.
+    pub fn with_agent_radius(mut self, agent_radius: f32) -> Self {
+        self.agent_radius = agent_radius;
+        self
+    }
+
+    /// Largest distance between curved boundaries and their approximation.
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Adds a convex obstacle, its outline is traced with extreme points.
+    pub fn add_obstacle<S, T>(mut self, shape: &S, t: &T) -> Self
+    where
+        S: ExtremePointT2d<T>,
+        T: Transformation2d,
+    {
+        let outline = support_outline(&Collider2d::new(shape, t), self.tolerance);
+        self.obstacles.push(outline);
+        self
+    }
+
+    /// Adds a convex obstacle given by its outline in world space.
+    pub fn add_outline(mut self, outline: &[Vec2]) -> Self {
+        let mut outline = outline.to_vec();
+        if signed_area(&outline) < 0.0 {
+            outline.reverse();
+        }
+        self.obstacles.push(outline);
+        self
+    }
+
+    /// Adds the tiles inside the bounds as obstacles, shaped by the tilemap's
+    /// [TileShapeTable2d], see [Tilemap::merged_colliders].
+    ///
+    /// Sensor and one-way tiles don't block agents.
+    #[cfg(feature = "tilemap")]
+    pub fn add_tilemap(mut self, tilemap: &Tilemap) -> Self {
+        use lk_math::vector::V2i32;
+
+        let rect = tilemap.quantize_aabb(&self.bounds);
+        let (min, max) = (
+            tilemap.tile_to_chunk_pos(&rect.min),
+            tilemap.tile_to_chunk_pos(&rect.max),
+        );
+        for y in min.y()..=max.y() {
+            for x in min.x()..=max.x() {
+                for collider in tilemap.merged_colliders(&V2i32::from_xy(x, y)) {
+                    if !collider.sensor && !collider.one_way {
+                        self = self.add_obstacle(&collider.shape, &collider.pos);
+                    }
+                }
+            }
+        }
+        self
+    }
+
+    pub fn build(&self) -> NavMesh2d {
+        let bounds = self.bounds.expanded(-self.agent_radius);
+        let mut free = vec![vec![
+            bounds.min,
+            Vec2::new(bounds.max.x, bounds.min.y),
+            bounds.max,
+            Vec2::new(bounds.min.x, bounds.max.y),
+        ]];
+        if bounds.min.x >= bounds.max.x || bounds.min.y >= bounds.max.y {
+            return NavMesh2d::default();
+        }
+        for outline in &self.obstacles {
+            let Some(inflated) = inflate(outline, self.agent_radius, self.tolerance) else {
+                continue;
+            };
+            let overlaps = Aabb2d::from_points(inflated.iter().copied())
+                .is_some_and(|aabb| aabb.intersects(&bounds));
+            if overlaps {
+                free = polygon_boolean(&free, &[inflated], BooleanOp2d::Difference);
+            }
+        }
+        NavMesh2d::triangulate(&free)
+    }
+}
+
+/// Outline of a convex shape, counter-clockwise, found by refining extreme points until the
+/// boundary between them is within `tolerance`.
+fn support_outline(shape: &impl ExtremePoint2d, tolerance: f32) -> Vec<Vec2> {
+    let mut corners: Vec<Vec2> = Vec::with_capacity(4);
+    for direction in [Vec2::X, Vec2::Y, Vec2::NEG_X, Vec2::NEG_Y] {
+        let p = shape.extreme_point(direction);
+        if corners.last() != Some(&p) && corners.first() != Some(&p) {
+            corners.push(p);
+        }
+    }
+    let mut outline = Vec::new();
+    for (i, &a) in corners.iter().enumerate() {
+        outline.push(a);
+        refine_outline(
+            shape,
+            a,
+            corners[(i + 1) % corners.len()],
+            tolerance,
+            16,
+            &mut outline,
+        );
+    }
+    outline
+}
+
+fn refine_outline(
+    shape: &impl ExtremePoint2d,
+    a: Vec2,
+    b: Vec2,
+    tolerance: f32,
+    depth: usize,
+    outline: &mut Vec<Vec2>,
+) {
+    let normal = -(b - a).perp();
+    if depth == 0 || normal == Vec2::ZERO {
+        return;
+    }
+    let c = shape.extreme_point(normal);
+    if (c - a).dot(normal) <= tolerance.max(1e-6) * normal.length() {
+        return;
+    }
+    refine_outline(shape, a, c, tolerance, depth - 1, outline);
+    outline.push(c);
+    refine_outline(shape, c, b, tolerance, depth - 1, outline);
+}
+
+/// Grows a convex outline by `radius` with round corners.
+fn inflate(outline: &[Vec2], radius: f32, tolerance: f32) -> Option<Vec<Vec2>> {
+    if outline.len() < 3 {
+        return None;
+    }
+    if radius <= 0.0 {
+        return Some(outline.to_vec());
+    }
+    let center = outline.iter().sum::<Vec2>() / outline.len() as f32;
+    let local: Vec<Vec2> = outline.iter().map(|&p| p - center).collect();
+    let tolerance = tolerance.max(1e-6);
+    let grown = Poly2d::new(&local).offset(radius, OffsetJoin2d::Round { tolerance })?;
+    Some(grown.points.into_iter().map(|p| p + center).collect())
+}
+
+/// Triangulated free space for pathfinding, see [NavMeshBuilder2d].
+#[derive(Debug, Clone, Default)]
+pub struct NavMesh2d {
+    vertices: Vec<Vec2>,
+    /// Counter-clockwise triangles indexing [NavMesh2d::vertices].
+    triangles: Vec<[usize; 3]>,
+    /// Triangle across edge `k`, which goes from vertex `k` to vertex `k + 1`.
+    neighbours: Vec<[Option<usize>; 3]>,
+}
+
+impl NavMesh2d {
+    pub fn vertices(&self) -> &[Vec2] {
+        &self.vertices
+    }
+
+    pub fn triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+
+    /// Triangles sharing an edge with `triangle`, edge `k` goes from vertex `k` to `k + 1`.
+    pub fn neighbours(&self, triangle: usize) -> [Option<usize>; 3] {
+        self.neighbours[triangle]
+    }
+
+    /// Triangle containing `point`, on the boundary included.
+    pub fn find_triangle(&self, point: Vec2) -> Option<usize> {
+        self.triangles.iter().position(|triangle| {
+            let [a, b, c] = triangle.map(|i| self.vertices[i]);
+            let epsilon = 1e-5 * (b - a).length_squared().max((c - a).length_squared());
+            (b - a).perp_dot(point - a) >= -epsilon
+                && (c - b).perp_dot(point - b) >= -epsilon
+                && (a - c).perp_dot(point - c) >= -epsilon
+        })
+    }
+
+    /// Shortest path through the mesh, found with A* over triangles and straightened with the
+    /// funnel algorithm.
+    ///
+    /// Returns `None` if either point is outside of the mesh or the goal is unreachable.
+    pub fn find_path(&self, start: Vec2, goal: Vec2) -> Option<Vec<Vec2>> {
+        let from = self.find_triangle(start)?;
+        let to = self.find_triangle(goal)?;
+        let corridor = self.find_corridor(from, to, start, goal)?;
+
+        let mut portals = Vec::with_capacity(corridor.len() + 1);
+        portals.push((start, start));
+        for &(triangle, k) in &corridor {
+            let t = self.triangles[triangle];
+            // Walking out of a counter-clockwise triangle, the end of the edge is on the left.
+            portals.push((self.vertices[t[(k + 1) % 3]], self.vertices[t[k]]));
+        }
+        portals.push((goal, goal));
+        Some(string_pull(&portals))
+    }
+
+    /// Triangles and exit edges from `from` to `to`.
+    fn find_corridor(
+        &self,
+        from: usize,
+        to: usize,
+        start: Vec2,
+        goal: Vec2,
+    ) -> Option<Vec<(usize, usize)>> {
+        let n = self.triangles.len();
+        let mut cost = vec![f32::INFINITY; n];
+        let mut position = vec![start; n];
+        let mut came_from: Vec<Option<(usize, usize)>> = vec![None; n];
+        let mut open = BinaryHeap::new();
+        cost[from] = 0.0;
+        open.push(Open {
+            estimate: start.distance(goal),
+            triangle: from,
+        });
+
+        while let Some(Open { estimate, triangle }) = open.pop() {
+            if triangle == to {
+                break;
+            }
+            if estimate > cost[triangle] + position[triangle].distance(goal) {
+                continue;
+            }
+            let t = self.triangles[triangle];
+            for (k, neighbour) in self.neighbours[triangle].into_iter().enumerate() {
+                let Some(neighbour) = neighbour else {
+                    continue;
+                };
+                // Triangles are entered at the midpoint of the shared edge, which keeps the search
+                // cheap, the funnel straightens the corridor afterwards.
+                let entry = (self.vertices[t[k]] + self.vertices[t[(k + 1) % 3]]) / 2.0;
+                let next_cost = cost[triangle] + position[triangle].distance(entry);
+                if next_cost < cost[neighbour] {
+                    cost[neighbour] = next_cost;
+                    position[neighbour] = entry;
+                    came_from[neighbour] = Some((triangle, k));
+                    open.push(Open {
+                        estimate: next_cost + entry.distance(goal),
+                        triangle: neighbour,
+                    });
+                }
+            }
+        }
+
+        if from != to && came_from[to].is_none() {
+            return None;
+        }
+        let mut corridor = Vec::new();
+        let mut current = to;
+        while let Some((previous, k)) = came_from[current] {
+            corridor.push((previous, k));
+            current = previous;
+        }
+        corridor.reverse();
+        Some(corridor)
+    }
+
+    fn triangulate(free: &[Vec<Vec2>]) -> Self {
+        let mut mesh = Self::default();
+        let mut ids: HashMap<(u32, u32), usize> = HashMap::new();
+        let mut id = |vertices: &mut Vec<Vec2>, p: Vec2| {
+            *ids.entry((p.x.to_bits(), p.y.to_bits()))
+                .or_insert_with(|| {
+                    vertices.push(p);
+                    vertices.len() - 1
+                })
+        };
+
+        let mut constrained = HashSet::new();
+        for (outer, holes) in group_holes(free) {
+            for ring in core::iter::once(&outer).chain(&holes) {
+                for (i, &p) in ring.iter().enumerate() {
+                    let a = id(&mut mesh.vertices, p);
+                    let b = id(&mut mesh.vertices, ring[(i + 1) % ring.len()]);
+                    constrained.insert((a, b));
+                    constrained.insert((b, a));
+                }
+            }
+            let (polygon, triangles) = triangulate_with_holes(&outer, &holes);
+            for triangle in triangles {
+                let t = triangle.map(|i| id(&mut mesh.vertices, polygon[i]));
+                if t[0] != t[1] && t[1] != t[2] && t[2] != t[0] {
+                    mesh.triangles.push(t);
+                }
+            }
+        }
+
+        mesh.make_delaunay(&constrained);
+
+        let edges: HashMap<(usize, usize), usize> = mesh
+            .triangles
+            .iter()
+            .enumerate()
+            .flat_map(|(i, t)| (0..3).map(move |k| ((t[k], t[(k + 1) % 3]), i)))
+            .collect();
+        mesh.neighbours = mesh
+            .triangles
+            .iter()
+            .map(|t| [0, 1, 2].map(|k| edges.get(&(t[(k + 1) % 3], t[k])).copied()))
+            .collect();
+        mesh
+    }
+
+    /// Flips edges that are not constrained until every triangle is locally Delaunay.
+    fn make_delaunay(&mut self, constrained: &HashSet<(usize, usize)>) {
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+        for (i, t) in self.triangles.iter().enumerate() {
+            for k in 0..3 {
+                edges.insert((t[k], t[(k + 1) % 3]), i);
+            }
+        }
+        let mut stack: Vec<(usize, usize)> = edges.keys().copied().collect();
+        // Lawson's flips terminate, the limit only guards against rounding.
+        let mut flips_left = 8 * self.triangles.len() * self.triangles.len() + 64;
+
+        while let Some((a, b)) = stack.pop() {
+            if constrained.contains(&(a, b)) {
+                continue;
+            }
+            let (Some(&t1), Some(&t2)) = (edges.get(&(a, b)), edges.get(&(b, a))) else {
+                continue;
+            };
+            let c = opposite(self.triangles[t1], a, b);
+            let d = opposite(self.triangles[t2], b, a);
+            let [pa, pb, pc, pd] = [a, b, c, d].map(|i| self.vertices[i]);
+            if !in_circle(pa, pb, pc, pd) || flips_left == 0 {
+                continue;
+            }
+            flips_left -= 1;
+
+            for edge in [(a, b), (b, c), (c, a), (b, a), (a, d), (d, b)] {
+                edges.remove(&edge);
+            }
+            self.triangles[t1] = [a, d, c];
+            self.triangles[t2] = [d, b, c];
+            for (edge, t) in [
+                ((a, d), t1),
+                ((d, c), t1),
+                ((c, a), t1),
+                ((d, b), t2),
+                ((b, c), t2),
+                ((c, d), t2),
+            ] {
+                edges.insert(edge, t);
+            }
+            stack.extend([(a, d), (d, b), (b, c), (c, a)]);
+        }
+    }
+}
+
+/// Vertex of `triangle` other than `a` and `b`.
+fn opposite(triangle: [usize; 3], a: usize, b: usize) -> usize {
+    triangle.into_iter().find(|&i| i != a && i != b).unwrap()
+}
+
+/// Whether `d` is strictly inside the circumcircle of the counter-clockwise triangle `abc`.
+fn in_circle(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> bool {
+    let [a, b, c] = [a, b, c].map(|p| (p - d).as_dvec2());
+    let det = a.length_squared() * b.perp_dot(c)
+        + b.length_squared() * c.perp_dot(a)
+        + c.length_squared() * a.perp_dot(b);
+    let scale = a
+        .length_squared()
+        .max(b.length_squared())
+        .max(c.length_squared());
+    det > 1e-9 * scale * scale
+}
+
+/// Straightens a corridor of `(left, right)` portals, see Mikko Mononen, "Simple Stupid Funnel
+/// Algorithm".
+fn string_pull(portals: &[(Vec2, Vec2)]) -> Vec<Vec2> {
+    let cross = |a: Vec2, b: Vec2, c: Vec2| (b - a).perp_dot(c - a);
+    let mut path = vec![portals[0].0];
+    let (mut apex, mut left, mut right) = (portals[0].0, portals[0].0, portals[0].1);
+    let (mut left_index, mut right_index) = (0, 0);
+
+    let mut i = 1;
+    while i < portals.len() {
+        let (next_left, next_right) = portals[i];
+
+        if cross(apex, right, next_right) >= 0.0 {
+            if apex == right || cross(apex, left, next_right) < 0.0 {
+                right = next_right;
+                right_index = i;
+            } else {
+                // The right side crossed the left one, the left corner is on the path.
+                apex = left;
+                if path.last() != Some(&apex) {
+                    path.push(apex);
+                }
+                (right, right_index) = (apex, left_index);
+                i = left_index + 1;
+                continue;
+            }
+        }
+
+        if cross(apex, left, next_left) <= 0.0 {
+            if apex == left || cross(apex, right, next_left) > 0.0 {
+                left = next_left;
+                left_index = i;
+            } else {
+                apex = right;
+                if path.last() != Some(&apex) {
+                    path.push(apex);
+                }
+                (left, left_index) = (apex, right_index);
+                i = right_index + 1;
+                continue;
+            }
+        }
+        i += 1;
+    }
+
+    let goal = portals[portals.len() - 1].0;
+    if path.last() != Some(&goal) {
+        path.push(goal);
+    }
+    path
+}
+
+/// Entry of the A* open set, ordered so that [BinaryHeap] pops the lowest estimate first.
+struct Open {
+    estimate: f32,
+    triangle: usize,
+}
+
+impl PartialEq for Open {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Open {}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Open {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room() -> Aabb2d {
+        Aabb2d::new(Vec2::splat(-5.0), Vec2::splat(5.0))
+    }
+
+    fn length(path: &[Vec2]) -> f32 {
+        path.windows(2).map(|w| w[0].distance(w[1])).sum()
+    }
+
+    #[test_log::test]
+    fn open_room() {
+        let navmesh = NavMeshBuilder2d::new(room()).build();
+        assert_eq!(navmesh.triangles().len(), 2);
+        let path = navmesh
+            .find_path(Vec2::new(-4.0, -3.0), Vec2::new(4.0, 2.0))
+            .unwrap();
+        assert_eq!(path, [Vec2::new(-4.0, -3.0), Vec2::new(4.0, 2.0)]);
+        assert_eq!(navmesh.find_path(Vec2::ZERO, Vec2::new(6.0, 0.0)), None);
+    }
+
+    #[test_log::test]
+    fn around_ball() {
+        let ball = Ball::with_radius(1.5);
+        let agent_radius = 0.5;
+        let navmesh = NavMeshBuilder2d::new(room())
+            .with_agent_radius(agent_radius)
+            .with_tolerance(0.01)
+            .add_obstacle(&ball, &Vec2::new(0.0, 0.5))
+            .build();
+
+        let (start, goal) = (Vec2::new(-4.0, 0.0), Vec2::new(4.0, 0.0));
+        let path = navmesh.find_path(start, goal).unwrap();
+        assert!(path.len() > 2);
+        assert!(length(&path) > start.distance(goal));
+        for w in path.windows(2) {
+            for i in 0..=20 {
+                let p = w[0].lerp(w[1], i as f32 / 20.0);
+                let clearance = p.distance(Vec2::new(0.0, 0.5)) - 1.5;
+                assert!(clearance > agent_radius - 0.02, "{p}");
+            }
+        }
+        // Going below the ball is shorter.
+        assert!(path.iter().all(|p| p.y <= 0.0), "{path:?}");
+    }
+
+    #[test_log::test]
+    fn wall_blocks_path() {
+        let wall = Box2d::with_halfdims(0.5, 6.0);
+        let navmesh = NavMeshBuilder2d::new(room())
+            .with_agent_radius(0.25)
+            .add_obstacle(&wall, &Vec2::ZERO)
+            .build();
+        assert!(navmesh
+            .find_path(Vec2::new(-3.0, 0.0), Vec2::new(-3.0, 4.0))
+            .is_some());
+        assert_eq!(
+            navmesh.find_path(Vec2::new(-3.0, 0.0), Vec2::new(3.0, 0.0)),
+            None
+        );
+        assert_eq!(navmesh.find_triangle(Vec2::ZERO), None);
+    }
+
+    #[test_log::test]
+    fn constrained_delaunay() {
+        let pillar = Box2d::with_halfdims(0.5, 0.5);
+        let t = Transform2d::from_translation(Vec2::new(1.0, -1.0)).with_angle(0.3);
+        let navmesh = NavMeshBuilder2d::new(room())
+            .with_agent_radius(0.3)
+            .add_obstacle(&pillar, &t)
+            .add_obstacle(&Poly2d::regular(5, 1.0), &Vec2::new(-2.0, 2.0))
+            .add_outline(&[
+                Vec2::new(3.0, 3.0),
+                Vec2::new(3.5, 3.0),
+                Vec2::new(3.5, 4.5),
+            ])
+            .build();
+
+        for (i, triangle) in navmesh.triangles().iter().enumerate() {
+            for (k, neighbour) in navmesh.neighbours(i).into_iter().enumerate() {
+                let Some(neighbour) = neighbour else {
+                    continue;
+                };
+                let (a, b) = (triangle[k], triangle[(k + 1) % 3]);
+                let c = opposite(*triangle, a, b);
+                let d = opposite(navmesh.triangles()[neighbour], b, a);
+                let [pa, pb, pc, pd] = [a, b, c, d].map(|i| navmesh.vertices()[i]);
+                assert!(!in_circle(pa, pb, pc, pd), "{i} {neighbour}");
+            }
+        }
+
+        let area: f32 = navmesh
+            .triangles()
+            .iter()
+            .map(|t| signed_area(&t.map(|i| navmesh.vertices()[i])))
+            .sum();
+        assert!(area > 0.0 && area < 9.4 * 9.4);
+    }
+
+    #[test_log::test]
+    fn funnel_hugs_corners() {
+        // Two walls forming a zig-zag corridor.
+        let navmesh = NavMeshBuilder2d::new(room())
+            .add_outline(&[
+                Vec2::new(-2.0, -5.0),
+                Vec2::new(-1.0, -5.0),
+                Vec2::new(-1.0, 3.0),
+                Vec2::new(-2.0, 3.0),
+            ])
+            .add_outline(&[
+                Vec2::new(1.0, -3.0),
+                Vec2::new(2.0, -3.0),
+                Vec2::new(2.0, 5.0),
+                Vec2::new(1.0, 5.0),
+            ])
+            .build();
+        let path = navmesh
+            .find_path(Vec2::new(-4.0, -4.0), Vec2::new(4.0, 4.0))
+            .unwrap();
+        assert_eq!(
+            path,
+            [
+                Vec2::new(-4.0, -4.0),
+                Vec2::new(-2.0, 3.0),
+                Vec2::new(-1.0, 3.0),
+                Vec2::new(1.0, -3.0),
+                Vec2::new(2.0, -3.0),
+                Vec2::new(4.0, 4.0),
+            ]
+        );
+    }
+
+    #[cfg(feature = "tilemap")]
+    #[test_log::test]
+    fn tilemap_wall_with_door() {
+        let mut tilemap = Tilemap::default();
+        for y in -5..=5 {
+            if y != 3 {
+                tilemap.set_tile(lk_math::vector::V2i32::from_xy(0, y), 1);
+            }
+        }
+        let navmesh = NavMeshBuilder2d::new(room())
+            .with_agent_radius(0.25)
+            .add_tilemap(&tilemap)
+            .build();
+        let path = navmesh
+            .find_path(Vec2::new(-3.0, 0.0), Vec2::new(3.0, 0.0))
+            .unwrap();
+        assert!(
+            path.iter().any(|p| p.x.abs() <= 0.75 && p.y > 2.0),
+            "{path:?}"
+        );
+
+        tilemap.set_tile(lk_math::vector::V2i32::from_xy(0, 3), 1);
+        let navmesh = NavMeshBuilder2d::new(room())
+            .with_agent_radius(0.25)
+            .add_tilemap(&tilemap)
+            .build();
+        assert_eq!(
+            navmesh.find_path(Vec2::new(-3.0, 0.0), Vec2::new(3.0, 0.0)),
+            None
+        );
+    }
+
+    #[cfg(feature = "tilemap")]
+    #[test_log::test]
+    fn tilemap_uses_tile_shapes() {
+        use lk_math::vector::V2i32;
+
+        let shapes = TileShapeTable2d::default()
+            .with(2, TileShape2d::Half(TileSide2d::Bottom))
+            .with(
+                3,
+                TileProperties2d::new(TileShape2d::Full).with_sensor(true),
+            );
+        let mut tilemap = Tilemap::default().with_tile_shapes(shapes);
+        let navmesh = |tilemap: &Tilemap| {
+            NavMeshBuilder2d::new(room())
+                .with_agent_radius(0.2)
+                .add_tilemap(tilemap)
+                .build()
+        };
+        let (from, to) = (Vec2::new(-3.0, 0.0), Vec2::new(3.0, 0.0));
+
+        // A wall of sensors blocks nothing.
+        for y in -5..=5 {
+            tilemap.set_tile(V2i32::from_xy(0, y), 3);
+        }
+        let path = navmesh(&tilemap).find_path(from, to).unwrap();
+        assert_eq!(path.len(), 2, "{path:?}");
+
+        // The door is half a tile tall, a full tile would close it.
+        for y in -5..=5 {
+            tilemap.set_tile(V2i32::from_xy(0, y), 1);
+        }
+        tilemap.set_tile(V2i32::from_xy(0, 3), 2);
+        let path = navmesh(&tilemap).find_path(from, to).unwrap();
+        assert!(path.iter().any(|p| p.y > 3.0), "{path:?}");
+    }
+}
@@ -0,0 +1,538 @@
+use std::collections::HashMap;
+
+use super::*;
+
+/// Signed area of a polygon, positive if counter-clockwise.
+pub fn signed_area(points: &[Vec2]) -> f32 {
+    let n = points.len();
+    0.5 * (0..n)
+        .map(|i| points[i].perp_dot(points[(i + 1) % n]))
+        .sum::<f32>()
+}
+
+/// Whether `point` is inside the polygon, using the even-odd rule.
+pub fn polygon_contains_point(points: &[Vec2], point: Vec2) -> bool {
+    let n = points.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (a, b) = (points[i], points[(i + 1) % n]);
+        if (a.y > point.y) != (b.y > point.y) {
+            let x = a.x + (point.y - a.y) * (b.x - a.x) / (b.y - a.y);
+            if point.x < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Simplifies a closed polygon with the Douglas–Peucker algorithm.
+///
+/// Every removed point is within `epsilon` of the simplified outline, collinear points are
+/// removed even for `epsilon == 0.0`.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// let noisy_square = [
+///     Vec2::new(0.0, 0.0),
+///     Vec2::new(1.0, 0.02),
+///     Vec2::new(2.0, 0.0),
+///     Vec2::new(2.0, 1.0),
+///     Vec2::new(2.0, 2.0),
+///     Vec2::new(1.0, 2.0),
+///     Vec2::new(0.0, 2.0),
+///     Vec2::new(-0.03, 1.0),
+/// ];
+/// let square = simplify_polygon(&noisy_square, 0.1);
+/// assert_eq!(
+///     square,
+///     [
+///         Vec2::new(0.0, 0.0),
+///         Vec2::new(2.0, 0.0),
+///         Vec2::new(2.0, 2.0),
+///         Vec2::new(0.0, 2.0),
+///     ]
+/// );
+/// ```
+pub fn simplify_polygon(points: &[Vec2], epsilon: f32) -> Vec<Vec2> {
+    let n = points.len();
+    if n < 3 {
+        return points.to_vec();
+    }
+    let at = |i: usize| points[i % n];
+
+    // A closed polygon has no endpoints, the first point and the point farthest from it are kept as
+    // anchors and the two chains between them are simplified separately.
+    let far = (1..n)
+        .max_by(|&i, &j| {
+            let (di, dj) = (
+                points[i].distance_squared(at(0)),
+                points[j].distance_squared(at(0)),
+            );
+            di.total_cmp(&dj)
+        })
+        .unwrap_or(0);
+
+    let mut keep = vec![false; n];
+    keep[0] = true;
+    keep[far] = true;
+    let mut stack = vec![(0, far), (far, n)];
+    while let Some((first, last)) = stack.pop() {
+        let (a, b) = (at(first), at(last));
+        let mut best = (f32::NEG_INFINITY, first);
+        for i in first + 1..last {
+            let distance = distance_to_segment(at(i), a, b);
+            if distance > best.0 {
+                best = (distance, i);
+            }
+        }
+        if best.0 > epsilon {
+            keep[best.1] = true;
+            stack.push((first, best.1));
+            stack.push((best.1, last));
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(&p, keep)| keep.then_some(p))
+        .collect()
+}
+
+fn distance_to_segment(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let t = if ab == Vec2::ZERO {
+        0.0
+    } else {
+        ((p - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0)
+    };
+    p.distance(a + t * ab)
+}
+
+/// Splits a simple polygon with holes into convex pieces.
+///
+/// The outline and the holes may be given in either winding, the pieces are counter-clockwise
+/// without collinear points. Holes are bridged into the outline, the result is ear-clipped and
+/// triangles are merged back together while they stay convex (Hertel–Mehlhorn), which produces at
+/// most four times the optimal number of pieces.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// let l_shape = [
+///     Vec2::new(0.0, 0.0),
+///     Vec2::new(2.0, 0.0),
+///     Vec2::new(2.0, 1.0),
+///     Vec2::new(1.0, 1.0),
+///     Vec2::new(1.0, 2.0),
+///     Vec2::new(0.0, 2.0),
+/// ];
+/// let pieces = convex_decomposition(&l_shape, &[]);
+/// assert_eq!(pieces.len(), 2);
+/// let area: f32 = pieces.iter().map(|piece| signed_area(piece)).sum();
+/// assert_eq!(area, 3.0);
+/// ```
+pub fn convex_decomposition(outline: &[Vec2], holes: &[Vec<Vec2>]) -> Vec<Vec<Vec2>> {
+    let (polygon, triangles) = triangulate_with_holes(outline, holes);
+    merge_convex(&polygon, triangles)
+        .into_iter()
+        .map(|piece| {
+            let points: Vec<Vec2> = piece.into_iter().map(|i| polygon[i]).collect();
+            without_collinear(&points)
+        })
+        .filter(|piece| piece.len() >= 3)
+        .collect()
+}
+
+/// Ear-clips a polygon with holes, returning the counter-clockwise outline with the holes
+/// bridged in and triangles indexing into it.
+pub(crate) fn triangulate_with_holes(
+    outline: &[Vec2],
+    holes: &[Vec<Vec2>],
+) -> (Vec<Vec2>, Vec<[usize; 3]>) {
+    if outline.len() < 3 {
+        return (Vec::new(), Vec::new());
+    }
+    let mut polygon = with_winding(outline, true);
+    let mut holes: Vec<Vec<Vec2>> = holes
+        .iter()
+        .filter(|hole| hole.len() >= 3)
+        .map(|hole| with_winding(hole, false))
+        .collect();
+    // Bridging the rightmost hole first keeps the bridges of the other holes from crossing it.
+    let max_x = |hole: &Vec<Vec2>| hole.iter().map(|p| p.x).fold(f32::NEG_INFINITY, f32::max);
+    holes.sort_by(|a, b| max_x(b).total_cmp(&max_x(a)));
+    for hole in &holes {
+        bridge_hole(&mut polygon, hole);
+    }
+
+    let triangles = triangulate(&polygon);
+    (polygon, triangles)
+}
+
+/// Pairs counter-clockwise outer boundaries with the clockwise holes inside them.
+///
+/// Each hole belongs to the smallest outer boundary containing it.
+pub(crate) fn group_holes(rings: &[Vec<Vec2>]) -> Vec<(Vec<Vec2>, Vec<Vec<Vec2>>)> {
+    let mut outers = Vec::new();
+    let mut holes = Vec::new();
+    for ring in rings.iter().filter(|ring| ring.len() >= 3) {
+        let area = signed_area(ring);
+        if area > f32::EPSILON {
+            outers.push((area, ring.clone(), Vec::new()));
+        } else if area < -f32::EPSILON {
+            holes.push(ring.clone());
+        }
+    }
+
+    for hole in holes {
+        let outer = outers
+            .iter_mut()
+            .filter(|(_, outer, _)| polygon_contains_point(outer, hole[0]))
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+        match outer {
+            Some((_, _, outer_holes)) => outer_holes.push(hole),
+            None => {
+                crate::warn!("hole outside of every outer boundary");
+            }
+        }
+    }
+
+    outers
+        .into_iter()
+        .map(|(_, outer, holes)| (outer, holes))
+        .collect()
+}
+
+/// Splits regions into convex [Poly2d] colliders, each with its offset.
+///
+/// Outer boundaries are counter-clockwise and holes clockwise, as returned by
+/// [Bitmap2d::contours] or [polygon_boolean]. Each hole belongs to the smallest outer boundary
+/// containing it. Pieces are centred at the mean of their vertices, so they contain the origin.
+#[cfg(feature = "poly")]
+pub fn convex_colliders(rings: &[Vec<Vec2>]) -> Vec<(Poly2d, Vec2)> {
+    group_holes(rings)
+        .iter()
+        .flat_map(|(outer, holes)| convex_decomposition(outer, holes))
+        .map(|piece| {
+            let offset = piece.iter().sum::<Vec2>() / piece.len() as f32;
+            let points: Vec<Vec2> = piece.iter().map(|&p| p - offset).collect();
+            (Poly2d::new(&points), offset)
+        })
+        .collect()
+}
+
+fn with_winding(points: &[Vec2], counter_clockwise: bool) -> Vec<Vec2> {
+    let mut points = points.to_vec();
+    if (signed_area(&points) > 0.0) != counter_clockwise {
+        points.reverse();
+    }
+    points
+}
+
+pub(crate) fn without_collinear(points: &[Vec2]) -> Vec<Vec2> {
+    let n = points.len();
+    (0..n)
+        .filter(|&i| {
+            let (a, b, c) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+            (b - a).perp_dot(c - b) != 0.0
+        })
+        .map(|i| points[i])
+        .collect()
+}
+
+/// Whether `p` is inside or on the boundary of the counter-clockwise triangle `abc`.
+fn triangle_contains(a: Vec2, b: Vec2, c: Vec2, p: Vec2) -> bool {
+    (b - a).perp_dot(p - a) >= 0.0
+        && (c - b).perp_dot(p - b) >= 0.0
+        && (a - c).perp_dot(p - c) >= 0.0
+}
+
+/// Connects a clockwise `hole` to the counter-clockwise `polygon` with a zero-width bridge.
+///
+/// See David Eberly, "Triangulation by Ear Clipping".
+fn bridge_hole(polygon: &mut Vec<Vec2>, hole: &[Vec2]) {
+    let Some(m) = (0..hole.len()).max_by(|&i, &j| hole[i].x.total_cmp(&hole[j].x)) else {
+        return;
+    };
+    let hole_point = hole[m];
+
+    // Closest edge hit by a ray going right from the hole.
+    let n = polygon.len();
+    let mut hit: Option<(f32, usize)> = None;
+    for i in 0..n {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        if (a.y > hole_point.y) == (b.y > hole_point.y) {
+            continue;
+        }
+        let x = a.x + (hole_point.y - a.y) * (b.x - a.x) / (b.y - a.y);
+        if x >= hole_point.x && hit.is_none_or(|(best, _)| x < best) {
+            hit = Some((x, i));
+        }
+    }
+    let Some((x, edge)) = hit else {
+        crate::warn!("hole is not inside the polygon");
+        return;
+    };
+    let intersection = Vec2::new(x, hole_point.y);
+    let (a, b) = (edge, (edge + 1) % n);
+    let mut visible = if polygon[a].x > polygon[b].x { a } else { b };
+
+    // The endpoint may be hidden behind other parts of the polygon, the reflex vertex inside the
+    // triangle making the smallest angle with the ray is visible then.
+    let candidate = polygon[visible];
+    let (t0, t1, t2) = if (intersection - hole_point).perp_dot(candidate - hole_point) >= 0.0 {
+        (hole_point, intersection, candidate)
+    } else {
+        (hole_point, candidate, intersection)
+    };
+    let mut best_angle = (f32::INFINITY, f32::INFINITY);
+    for i in 0..n {
+        if i == visible {
+            continue;
+        }
+        let (prev, p, next) = (polygon[(i + n - 1) % n], polygon[i], polygon[(i + 1) % n]);
+        let reflex = (p - prev).perp_dot(next - p) < 0.0;
+        if !reflex || !triangle_contains(t0, t1, t2, p) {
+            continue;
+        }
+        let d = p - hole_point;
+        let angle = (
+            d.y.abs() / d.length().max(f32::MIN_POSITIVE),
+            d.length_squared(),
+        );
+        if angle < best_angle {
+            best_angle = angle;
+            visible = i;
+        }
+    }
+
+    let mut bridged = Vec::with_capacity(n + hole.len() + 2);
+    bridged.extend_from_slice(&polygon[..=visible]);
+    bridged.extend(hole[m..].iter().chain(&hole[..=m]));
+    bridged.extend_from_slice(&polygon[visible..]);
+    *polygon = bridged;
+}
+
+/// Ear-clips a counter-clockwise polygon, which may contain zero-width bridges.
+fn triangulate(polygon: &[Vec2]) -> Vec<[usize; 3]> {
+    let mut remaining: Vec<usize> = (0..polygon.len()).collect();
+    let mut triangles = Vec::with_capacity(polygon.len().saturating_sub(2));
+    while remaining.len() > 3 {
+        let m = remaining.len();
+        let corner = |k: usize| {
+            [
+                remaining[(k + m - 1) % m],
+                remaining[k],
+                remaining[(k + 1) % m],
+            ]
+        };
+        let is_ear = |k: usize| {
+            let [a, b, c] = corner(k).map(|i| polygon[i]);
+            if (b - a).perp_dot(c - b) <= 0.0 {
+                return false;
+            }
+            // Bridge endpoints appear twice, copies of the corners don't block it.
+            remaining.iter().all(|&i| {
+                let p = polygon[i];
+                p == a || p == b || p == c || !triangle_contains(a, b, c, p)
+            })
+        };
+        if let Some(k) = (0..m).find(|&k| is_ear(k)) {
+            triangles.push(corner(k));
+            remaining.remove(k);
+            continue;
+        }
+        // Only degenerate corners are left, e.g. collinear points or spikes.
+        let degenerate = (0..m).find(|&k| {
+            let [a, b, c] = corner(k).map(|i| polygon[i]);
+            (b - a).perp_dot(c - b) == 0.0
+        });
+        match degenerate {
+            Some(k) => {
+                remaining.remove(k);
+            }
+            None => {
+                crate::warn!("polygon is not simple, {m} vertices left untriangulated");
+                return triangles;
+            }
+        }
+    }
+    if let [a, b, c] = remaining[..] {
+        let [pa, pb, pc] = [a, b, c].map(|i| polygon[i]);
+        if (pb - pa).perp_dot(pc - pb) > 0.0 {
+            triangles.push([a, b, c]);
+        }
+    }
+    triangles
+}
+
+/// Merges counter-clockwise pieces sharing an edge while the result stays convex.
+fn merge_convex(polygon: &[Vec2], triangles: Vec<[usize; 3]>) -> Vec<Vec<usize>> {
+    let mut pieces: Vec<Option<Vec<usize>>> =
+        triangles.into_iter().map(|t| Some(t.to_vec())).collect();
+    let mut owners: HashMap<(usize, usize), usize> = HashMap::new();
+    for (p, piece) in pieces.iter().enumerate() {
+        for (u, v) in edges(piece.as_ref().unwrap()) {
+            owners.insert((u, v), p);
+        }
+    }
+
+    for p in 0..pieces.len() {
+        let mut k = 0;
+        while let Some(piece) = pieces[p].clone() {
+            let Some((u, v)) = edges(&piece).nth(k) else {
+                break;
+            };
+            k += 1;
+            let Some(&q) = owners.get(&(v, u)) else {
+                continue;
+            };
+            if q == p {
+                continue;
+            }
+            let other = pieces[q].take().unwrap();
+            let merged = merge_along(&piece, &other, u, v);
+            if !is_convex(polygon, &merged) {
+                pieces[q] = Some(other);
+                continue;
+            }
+            for edge in edges(&other) {
+                owners.insert(edge, p);
+            }
+            owners.remove(&(u, v));
+            owners.remove(&(v, u));
+            pieces[p] = Some(merged);
+            k = 0;
+        }
+    }
+    pieces.into_iter().flatten().collect()
+}
+
+fn edges(piece: &[usize]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    (0..piece.len()).map(|i| (piece[i], piece[(i + 1) % piece.len()]))
+}
+
+/// Joins two loops sharing the edge `u -> v` of `a` (`v -> u` of `b`).
+fn merge_along(a: &[usize], b: &[usize], u: usize, v: usize) -> Vec<usize> {
+    let rotate = |piece: &[usize], start: usize| {
+        let i = piece.iter().position(|&x| x == start).unwrap();
+        piece[i..]
+            .iter()
+            .chain(&piece[..i])
+            .copied()
+            .collect::<Vec<_>>()
+    };
+    // `a` from `v` around to `u`, then `b` from `u` around to just before `v`.
+    let mut merged = rotate(a, v);
+    merged.extend(rotate(b, u).into_iter().skip(1).take(b.len() - 2));
+    merged
+}
+
+fn is_convex(polygon: &[Vec2], piece: &[usize]) -> bool {
+    let n = piece.len();
+    (0..n).all(|i| {
+        let (a, b, c) = (
+            polygon[piece[(i + n - 1) % n]],
+            polygon[piece[i]],
+            polygon[piece[(i + 1) % n]],
+        );
+        (b - a).perp_dot(c - b) >= 0.0
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn total_area(pieces: &[Vec<Vec2>]) -> f32 {
+        pieces.iter().map(|piece| signed_area(piece)).sum()
+    }
+
+    fn assert_convex(pieces: &[Vec<Vec2>]) {
+        for piece in pieces {
+            let n = piece.len();
+            for i in 0..n {
+                let (a, b, c) = (piece[i], piece[(i + 1) % n], piece[(i + 2) % n]);
+                assert!((b - a).perp_dot(c - b) > 0.0, "{piece:?}");
+            }
+        }
+    }
+
+    #[test_log::test]
+    fn simplify_keeps_corners() {
+        let square = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(1.0, 2.01),
+            Vec2::new(0.0, 2.0),
+        ];
+        assert_eq!(simplify_polygon(&square, 0.0).len(), 5);
+        assert_eq!(
+            simplify_polygon(&square, 0.1),
+            [
+                Vec2::new(0.0, 0.0),
+                Vec2::new(2.0, 0.0),
+                Vec2::new(2.0, 2.0),
+                Vec2::new(0.0, 2.0),
+            ]
+        );
+    }
+
+    #[test_log::test]
+    fn decomposition_of_clockwise_comb() {
+        // Comb with three teeth pointing up, given clockwise.
+        let mut comb = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(5.0, 0.0),
+            Vec2::new(5.0, 3.0),
+            Vec2::new(4.0, 3.0),
+            Vec2::new(4.0, 1.0),
+            Vec2::new(3.0, 1.0),
+            Vec2::new(3.0, 3.0),
+            Vec2::new(2.0, 3.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.0, 3.0),
+            Vec2::new(0.0, 3.0),
+        ];
+        comb.reverse();
+        let pieces = convex_decomposition(&comb, &[]);
+        assert_convex(&pieces);
+        assert!((total_area(&pieces) - 11.0).abs() < 1e-5);
+        assert!(pieces.len() <= 6, "{pieces:?}");
+    }
+
+    #[test_log::test]
+    fn decomposition_with_holes() {
+        let outline = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(10.0, 4.0),
+            Vec2::new(0.0, 4.0),
+        ];
+        let square = |x: f32| {
+            vec![
+                Vec2::new(x, 1.0),
+                Vec2::new(x + 2.0, 1.0),
+                Vec2::new(x + 2.0, 3.0),
+                Vec2::new(x, 3.0),
+            ]
+        };
+        let holes = [square(1.0), square(6.0)];
+        let pieces = convex_decomposition(&outline, &holes);
+        assert_convex(&pieces);
+        assert!((total_area(&pieces) - 32.0).abs() < 1e-4);
+        let inside_hole = Vec2::new(2.0, 2.0);
+        assert!(pieces
+            .iter()
+            .all(|piece| !polygon_contains_point(piece, inside_hole)));
+        assert!(pieces
+            .iter()
+            .any(|piece| polygon_contains_point(piece, Vec2::new(4.0, 2.0))));
+    }
+}
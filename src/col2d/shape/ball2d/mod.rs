@@ -4,14 +4,71 @@ mod v_ball2d;
 mod v_point;
 // mod v_box2d;
 
-impl DefaultMinkowski<Box2d> for Ball {}
-
 impl SymmetricBoundingBox2d for Ball {
     fn symmetric_bounding_box(&self) -> Box2d {
         Box2d::with_halfdims(self.radius, self.radius)
     }
 }
 
+impl MassProperties2d for Ball {
+    fn mass_properties(&self, density: f32) -> Mass2d {
+        let radius_squared = self.radius * self.radius;
+        let mass = density * core::f32::consts::PI * radius_squared;
+        Mass2d::new(mass, Vec2::ZERO, 0.5 * mass * radius_squared)
+    }
+}
+
+impl BoundingVolume2d for Ball {
+    fn oriented_bounding_box(&self) -> Obb2d {
+        Obb2d::new(Vec2::ZERO, Vec2::splat(self.radius), 0.0)
+    }
+
+    fn bounding_circle(&self) -> BoundingCircle2d {
+        BoundingCircle2d::new(Vec2::ZERO, self.radius)
+    }
+}
+
+impl PointQuery2d for Ball {
+    fn project_point(&self, point: Vec2) -> PointProjection2d {
+        let distance = point.length();
+        let direction = if distance > 0.0 {
+            point / distance
+        } else {
+            Vec2::X
+        };
+        PointProjection2d {
+            point: self.radius * direction,
+            is_inside: distance <= self.radius,
+            feature: Feature2d::Curve,
+        }
+    }
+}
+
+impl RayCast2d for Ball {
+    fn cast_ray(&self, ray: &Ray2d, max_distance: f32) -> Option<RayHit2d> {
+        let a = ray.direction.length_squared();
+        let b = ray.origin.dot(ray.direction);
+        let c = ray.origin.length_squared() - self.radius * self.radius;
+        if c <= 0.0 {
+            return Some(RayHit2d::inside(ray));
+        }
+        let discriminant = b * b - a * c;
+        if a == 0.0 || discriminant < 0.0 {
+            return None;
+        }
+        let distance = (-b - discriminant.sqrt()) / a;
+        if distance < 0.0 || distance > max_distance {
+            return None;
+        }
+        let point = ray.at(distance);
+        Some(RayHit2d {
+            distance,
+            point,
+            normal: point / self.radius,
+        })
+    }
+}
+
 impl ExtremePoint2d for Ball {
     fn extreme_point(&self, direction: Vec2) -> Vec2 {
         self.radius * direction.normalize_or_zero()
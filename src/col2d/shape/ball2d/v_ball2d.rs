@@ -1,19 +1,12 @@
+use super::v_point::ball_v_point_sdfv;
 use super::*;
 
 impl<const COMPUTE_PENETRATION: bool, const COMPUTE_DISTANCE: bool, T: Transformation2d>
     SdfvCommonRel2d<COMPUTE_PENETRATION, COMPUTE_DISTANCE, Ball, T> for Ball
 {
-    fn sdfv_common_rel(&self, b: &Ball, rel: &T) -> (bool, Vec2) {
+    fn sdfv_common_rel(&self, b: &Ball, rel: &T) -> QueryResult2d {
         let radius = self.radius + b.radius;
-        let delta = rel.apply_origin();
-        let length = delta.length();
-        if length > 0.0 {
-            let collides = length < radius;
-            let new_length = length - radius;
-            (collides, delta * (new_length / length))
-        } else {
-            (true, radius * Vec2::X)
-        }
+        ball_v_point_sdfv(radius, rel.apply_origin())
     }
 }
 
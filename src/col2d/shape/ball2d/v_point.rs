@@ -3,16 +3,18 @@ use super::*;
 impl<const COMPUTE_PENETRATION: bool, const COMPUTE_DISTANCE: bool, T: Transformation2d>
     SdfvCommonRel2d<COMPUTE_PENETRATION, COMPUTE_DISTANCE, Point, T> for Ball
 {
-    fn sdfv_common_rel(&self, _: &Point, rel: &T) -> (bool, Vec2) {
-        let delta = rel.apply_origin();
-        let length = delta.length();
-        if length > 0.0 {
-            let collides = length < self.radius;
-            let new_length = length - self.radius;
-            (collides, delta * (new_length / length))
-        } else {
-            (true, self.radius * Vec2::X)
-        }
+    fn sdfv_common_rel(&self, _: &Point, rel: &T) -> QueryResult2d {
+        ball_v_point_sdfv(self.radius, rel.apply_origin())
+    }
+}
+
+pub(super) fn ball_v_point_sdfv(radius: f32, delta: Vec2) -> QueryResult2d {
+    let length = delta.length();
+    if length > 0.0 {
+        let sdfv = delta * ((length - radius) / length);
+        QueryResult2d::from_sdfv(length < radius, sdfv)
+    } else {
+        QueryResult2d::penetrating(Vec2::new(radius, 0.0))
     }
 }
 
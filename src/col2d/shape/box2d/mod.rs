@@ -31,6 +31,89 @@ impl SymmetricBoundingBox2d for Box2d {
     }
 }
 
+impl MassProperties2d for Box2d {
+    fn mass_properties(&self, density: f32) -> Mass2d {
+        let mass = density * 4.0 * self.halfsize.x * self.halfsize.y;
+        let inertia = mass * self.halfsize.length_squared() / 3.0;
+        Mass2d::new(mass, Vec2::ZERO, inertia)
+    }
+}
+
+impl BoundingVolume2d for Box2d {
+    fn oriented_bounding_box(&self) -> Obb2d {
+        Obb2d::new(Vec2::ZERO, self.halfsize, 0.0)
+    }
+
+    fn bounding_circle(&self) -> BoundingCircle2d {
+        BoundingCircle2d::new(Vec2::ZERO, self.halfsize.length())
+    }
+}
+
+/// Vertices are numbered counter-clockwise starting at `-halfsize`, edge `i` goes from vertex `i`
+/// to vertex `i + 1`.
+impl PointQuery2d for Box2d {
+    fn project_point(&self, point: Vec2) -> PointProjection2d {
+        let halfsize = self.halfsize;
+        let clamped = point.clamp(-halfsize, halfsize);
+        let clamped_x = clamped.x != point.x;
+        let clamped_y = clamped.y != point.y;
+        if clamped_x || clamped_y {
+            let feature = if clamped_x && clamped_y {
+                Feature2d::Vertex(box_vertex(point))
+            } else if clamped_x {
+                Feature2d::Edge(if point.x > 0.0 { 1 } else { 3 })
+            } else {
+                Feature2d::Edge(if point.y > 0.0 { 2 } else { 0 })
+            };
+            return PointProjection2d {
+                point: clamped,
+                is_inside: false,
+                feature,
+            };
+        }
+
+        let distance = halfsize - point.abs();
+        let (point, edge) = if distance.x < distance.y {
+            let edge = if point.x > 0.0 { 1 } else { 3 };
+            (Vec2::new(halfsize.x.copysign(point.x), point.y), edge)
+        } else {
+            let edge = if point.y > 0.0 { 2 } else { 0 };
+            (Vec2::new(point.x, halfsize.y.copysign(point.y)), edge)
+        };
+        PointProjection2d {
+            point,
+            is_inside: true,
+            feature: Feature2d::Edge(edge),
+        }
+    }
+}
+
+/// Index of the [Box2d] vertex in the quadrant of `point`.
+fn box_vertex(point: Vec2) -> usize {
+    match (point.x > 0.0, point.y > 0.0) {
+        (false, false) => 0,
+        (true, false) => 1,
+        (true, true) => 2,
+        (false, true) => 3,
+    }
+}
+
+impl RayCast2d for Box2d {
+    fn cast_ray(&self, ray: &Ray2d, max_distance: f32) -> Option<RayHit2d> {
+        let halfsize = self.halfsize;
+        cast_ray_convex(
+            ray,
+            max_distance,
+            [
+                (Vec2::NEG_Y, halfsize.y),
+                (Vec2::X, halfsize.x),
+                (Vec2::Y, halfsize.y),
+                (Vec2::NEG_X, halfsize.x),
+            ],
+        )
+    }
+}
+
 impl ExtremePoint2d for Box2d {
     fn extreme_point(&self, direction: Vec2) -> Vec2 {
         Vec2::new(
@@ -41,7 +124,3 @@ impl ExtremePoint2d for Box2d {
 }
 
 impl<T: Transformation2d> ExtremePointT2d<T> for Box2d {}
-
-impl DefaultMinkowski<Box2d> for Box2d {}
-impl DefaultMinkowski<Poly2d> for Box2d {}
-// impl DefaultMinkowski<Ball> for Box2d {}
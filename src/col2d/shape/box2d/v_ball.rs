@@ -5,7 +5,7 @@ use super::*;
 impl<const COMPUTE_PENETRATION: bool, const COMPUTE_DISTANCE: bool, T: Transformation2d>
     SdfvCommonRel2d<COMPUTE_PENETRATION, COMPUTE_DISTANCE, Ball, T> for Box2d
 {
-    fn sdfv_common_rel(&self, t: &Ball, rel: &T) -> (bool, Vec2) {
+    fn sdfv_common_rel(&self, t: &Ball, rel: &T) -> QueryResult2d {
         let rounded_box = RoundedBox2d::new(self.halfsize, t.radius);
         SdfvCommonRel2d::<COMPUTE_PENETRATION, COMPUTE_DISTANCE, _, T>::sdfv_common_rel(
             &rounded_box,
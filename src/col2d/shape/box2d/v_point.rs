@@ -3,33 +3,29 @@ use super::*;
 impl<const COMPUTE_PENETRATION: bool, const COMPUTE_DISTANCE: bool, T: Transformation2d>
     SdfvCommonRel2d<COMPUTE_PENETRATION, COMPUTE_DISTANCE, Point, T> for Box2d
 {
-    fn sdfv_common_rel(&self, _: &Point, rel: &T) -> (bool, Vec2) {
+    fn sdfv_common_rel(&self, _: &Point, rel: &T) -> QueryResult2d {
         let p = rel.apply_origin();
         let px = p.x.abs() - self.halfsize.x;
         let py = p.y.abs() - self.halfsize.y;
 
         if px.is_sign_negative() && py.is_sign_negative() {
-            let mut penetration = Vec2::NAN;
-
-            if COMPUTE_PENETRATION {
-                if px > py {
-                    penetration = Vec2::new(px * p.x.signum(), 0.0);
-                } else {
-                    penetration = Vec2::new(0.0, py * p.y.signum());
-                }
+            if !COMPUTE_PENETRATION {
+                return QueryResult2d::penetrating_unmeasured();
             }
 
-            (true, penetration)
+            if px > py {
+                QueryResult2d::penetrating(Vec2::new(px * p.x.signum(), 0.0))
+            } else {
+                QueryResult2d::penetrating(Vec2::new(0.0, py * p.y.signum()))
+            }
         } else {
-            let mut distance = Vec2::NAN;
-
-            if COMPUTE_DISTANCE {
-                let dx = px.max(0.0);
-                let dy = py.max(0.0);
-                distance = Vec2::new(dx * p.x.signum(), dy * p.y.signum());
+            if !COMPUTE_DISTANCE {
+                return QueryResult2d::separated_unmeasured();
             }
 
-            (false, distance)
+            let dx = px.max(0.0);
+            let dy = py.max(0.0);
+            QueryResult2d::separated(Vec2::new(dx * p.x.signum(), dy * p.y.signum()))
         }
     }
 }
@@ -0,0 +1,181 @@
+use super::*;
+
+/// Infinite solid region `normal · p <= offset`, e.g. a floor or a kill plane.
+///
+/// The half-space is unbounded, so it implements neither [ExtremePoint2d] nor
+/// [SymmetricBoundingBox2d] and is queried analytically instead of through GJK/EPA. The normal
+/// points out of the solid region.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// let floor = HalfSpace2d::from_point_normal(Vec2::ZERO, Vec2::Y);
+/// let ball = Ball::with_radius(1.0);
+/// assert!(floor.collides_rel(&ball, &Vec2::new(100.0, 0.5)));
+/// assert_eq!(floor.sdf_rel(&ball, &Vec2::new(-100.0, 3.0)), 2.0);
+/// assert_eq!(
+///     ball.penetrates_rel(&floor, &Vec2::new(0.0, -0.75)),
+///     Some(Vec2::new(0.0, 0.25))
+/// );
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+pub struct HalfSpace2d {
+    /// Unit normal pointing out of the solid region.
+    pub normal: Vec2,
+    /// Signed distance of the boundary from the origin along [HalfSpace2d::normal].
+    pub offset: f32,
+}
+
+impl HalfSpace2d {
+    /// Half-space `normal · p <= offset`, `normal` does not need to be normalized.
+    ///
+    /// Panics if `normal` is zero.
+    pub fn new(normal: Vec2, offset: f32) -> Self {
+        let length = normal.length();
+        assert!(length > 0.0, "half-space normal must be non-zero");
+        Self {
+            normal: normal / length,
+            offset: offset / length,
+        }
+    }
+
+    /// Half-space whose boundary passes through `point`.
+    ///
+    /// Panics if `normal` is zero.
+    pub fn from_point_normal(point: Vec2, normal: Vec2) -> Self {
+        assert!(normal != Vec2::ZERO, "half-space normal must be non-zero");
+        let normal = normal.normalize();
+        Self {
+            normal,
+            offset: normal.dot(point),
+        }
+    }
+
+    /// Signed distance of `point` from the boundary, negative inside.
+    pub fn signed_distance(&self, point: Vec2) -> f32 {
+        self.normal.dot(point) - self.offset
+    }
+
+    /// Signed distance of the deepest point of `b` transformed by `rel`.
+    fn sdf_extreme_point<B, T>(&self, b: &B, rel: &T) -> f32
+    where
+        B: ExtremePointT2d<T>,
+        T: Transformation2d,
+    {
+        self.signed_distance(b.extreme_point_t(rel, -self.normal))
+    }
+}
+
+impl PointQuery2d for HalfSpace2d {
+    fn project_point(&self, point: Vec2) -> PointProjection2d {
+        let distance = self.signed_distance(point);
+        PointProjection2d {
+            point: point - distance * self.normal,
+            is_inside: distance <= 0.0,
+            feature: Feature2d::Curve,
+        }
+    }
+}
+
+/// Implements queries between a half-space and each of `$b`, using the deepest point of `$b`.
+macro_rules! impl_half_space_v {
+    ($($b:ty),+) => {
+        $(
+            impl<const P: bool, const D: bool, T: Transformation2d> SdfvCommonRel2d<P, D, $b, T>
+                for HalfSpace2d
+            {
+                fn sdfv_common_rel(&self, b: &$b, rel: &T) -> QueryResult2d {
+                    let sdf = self.sdf_extreme_point(b, rel);
+                    QueryResult2d::from_sdfv(sdf < 0.0, sdf * self.normal)
+                }
+            }
+
+            impl SdfRel2d<$b> for HalfSpace2d {
+                fn sdf_rel(&self, b: &$b, rel: &impl Transformation2d) -> f32 {
+                    self.sdf_extreme_point(b, rel)
+                }
+            }
+        )+
+    };
+}
+
+impl_half_space_v!(Point, Ball, Box2d, Poly2d, RoundedBox2d, Shape2d);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn floor_v_ball() {
+        let floor = HalfSpace2d::new(Vec2::new(0.0, 2.0), 2.0);
+        assert_eq!(floor, HalfSpace2d::from_point_normal(Vec2::Y, Vec2::Y));
+        let ball = Ball::with_radius(1.0);
+        assert_eq!(
+            floor.sdfv_rel(&ball, &Vec2::new(5.0, 1.5)),
+            QueryResult2d::Penetrating {
+                sdfv: Vec2::new(0.0, -0.5),
+            }
+        );
+        assert_eq!(
+            floor.sdfv_rel(&ball, &Vec2::new(-5.0, 4.0)),
+            QueryResult2d::Separated {
+                sdfv: Vec2::new(0.0, 2.0),
+            }
+        );
+        assert_eq!(ball.sdf_rel(&floor, &Vec2::new(0.0, -1.0)), -1.0);
+    }
+
+    #[test_log::test]
+    #[should_panic]
+    fn zero_normal() {
+        HalfSpace2d::new(Vec2::ZERO, 1.0);
+    }
+
+    #[test_log::test]
+    fn slope_v_rotated_box() {
+        let slope = HalfSpace2d::from_point_normal(Vec2::ZERO, Vec2::new(-1.0, 1.0));
+        let b = Box2d::with_halfdims(1.0, 1.0);
+        // Box rotated so that one of its edges lies flat on the slope.
+        let t = Transform2d::from_translation(Vec2::new(3.0, 3.0 + 1.5 * 2f32.sqrt()))
+            .with_angle(core::f32::consts::FRAC_PI_4);
+        let sdf = slope.sdf_rel(&b, &t);
+        assert!((sdf - 0.5).abs() < 1e-5);
+        assert!(!slope.collides_rel(&b, &t));
+
+        let t = Transform2d::from_translation(Vec2::new(3.0, 3.0)).with_angle(0.3);
+        let penetration = slope.penetrates_rel(&b, &t).unwrap();
+        let depth = -slope.sdf_rel(&b, &t);
+        assert!(penetration.abs_diff_eq(-depth * slope.normal, 1e-5));
+    }
+
+    #[test_log::test]
+    fn poly_and_rounded_box_match_box() {
+        let ceiling = HalfSpace2d::from_point_normal(Vec2::new(0.0, 10.0), Vec2::NEG_Y);
+        let b = Box2d::with_halfdims(2.0, 1.0);
+        let poly = Poly2d::from(b);
+        let rounded = RoundedBox2d::new(b.halfsize, 0.5);
+        for y in [7.0, 8.5, 9.0, 9.5, 12.0] {
+            let t = Transform2d::from_translation(Vec2::new(1.0, y)).with_angle(0.2);
+            let expected = ceiling.sdf_rel(&b, &t);
+            assert!((ceiling.sdf_rel(&poly, &t) - expected).abs() < 1e-5);
+            assert!((ceiling.sdf_rel(&rounded, &t) - (expected - 0.5)).abs() < 1e-5);
+            assert_eq!(ceiling.collides_rel(&poly, &t), expected < 0.0);
+        }
+    }
+
+    #[test_log::test]
+    fn kill_plane() {
+        let kill_plane = HalfSpace2d::from_point_normal(Vec2::new(0.0, -50.0), Vec2::Y);
+        assert!(kill_plane.contains_point(Vec2::new(1000.0, -51.0)));
+        assert!(!kill_plane.contains_point(Vec2::new(-1000.0, -49.0)));
+        assert_eq!(
+            kill_plane.project_point(Vec2::new(3.0, 0.0)).point,
+            Vec2::new(3.0, -50.0)
+        );
+        assert!(Point.collides_rel(&kill_plane, &Vec2::new(0.0, 51.0)));
+        let shape = Shape2d::rect(1.0, 1.0);
+        assert!(kill_plane.collides_rel(&shape, &Vec2::new(0.0, -49.5)));
+        assert!(!shape.collides_rel(&kill_plane, &Vec2::new(0.0, -48.5)));
+    }
+}
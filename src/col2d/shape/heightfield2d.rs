@@ -0,0 +1,431 @@
+use core::ops::Range;
+
+use crate::warn;
+
+use super::*;
+
+/// Terrain given by a height per column, solid below the surface.
+///
+/// Sample `i` lies at `(i * spacing, samples[i] * scale)`, consecutive samples are connected by
+/// straight segments. The terrain spans `0.0..=width()` horizontally and extends infinitely
+/// downwards, so shapes can not tunnel through thin parts of it.
+///
+/// Queries only test the columns overlapped by the bounding box of the other shape.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// let terrain = Heightfield2d::new(vec![0.0, 1.0, 0.5, 0.5], 2.0, 1.0);
+/// let ball = Ball::with_radius(0.5);
+/// assert_eq!(terrain.height_at(3.0), Some(0.75));
+/// assert!(terrain.collides_rel(&ball, &Vec2::new(3.0, 1.0)));
+/// assert!(!ball.collides_rel(&terrain, &Vec2::new(-5.0, -2.0)));
+///
+/// let ray = Ray2d::new(Vec2::new(5.0, 10.0), Vec2::NEG_Y);
+/// assert_eq!(terrain.cast_ray(&ray, 20.0).unwrap().distance, 9.5);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+pub struct Heightfield2d {
+    pub samples: Vec<f32>,
+    /// Horizontal distance between samples.
+    pub spacing: f32,
+    /// Vertical scale of the samples.
+    pub scale: f32,
+}
+
+impl Heightfield2d {
+    pub fn new(samples: Vec<f32>, spacing: f32, scale: f32) -> Self {
+        Self {
+            samples,
+            spacing,
+            scale,
+        }
+    }
+
+    /// Number of columns, i.e. segments between samples.
+    pub fn columns(&self) -> usize {
+        self.samples.len().saturating_sub(1)
+    }
+
+    pub fn width(&self) -> f32 {
+        self.columns() as f32 * self.spacing
+    }
+
+    /// Surface point of sample `i`.
+    pub fn sample_point(&self, i: usize) -> Vec2 {
+        Vec2::new(i as f32 * self.spacing, self.samples[i] * self.scale)
+    }
+
+    /// Surface segment of column `i`.
+    pub fn segment(&self, i: usize) -> (Vec2, Vec2) {
+        (self.sample_point(i), self.sample_point(i + 1))
+    }
+
+    /// Height of the surface at `x`, `None` outside of the terrain.
+    pub fn height_at(&self, x: f32) -> Option<f32> {
+        if self.columns() == 0 || !(0.0..=self.width()).contains(&x) {
+            return None;
+        }
+        let i = self.column_at(x);
+        let (a, b) = self.segment(i);
+        Some(a.y + (b.y - a.y) * (x - a.x) / self.spacing)
+    }
+
+    /// Columns overlapping `min_x..=max_x`, or the nearest column if there are none.
+    pub fn columns_overlapping(&self, min_x: f32, max_x: f32) -> Range<usize> {
+        if self.columns() == 0 {
+            return 0..0;
+        }
+        self.column_at(min_x)..self.column_at(max_x) + 1
+    }
+
+    fn column_at(&self, x: f32) -> usize {
+        let column = (x / self.spacing).floor();
+        if column.is_nan() || column < 0.0 {
+            0
+        } else {
+            (column as usize).min(self.columns() - 1)
+        }
+    }
+
+    fn column(&self, i: usize, bottom: f32) -> Column {
+        let (a, b) = self.segment(i);
+        Column { a, b, bottom }
+    }
+
+    fn sdfv_common<const P: bool, const D: bool, B, T>(
+        &self,
+        b: &B,
+        rel: &T,
+        config: &GjkEpaConfig,
+    ) -> QueryResult2d
+    where
+        B: ExtremePointT2d<T>,
+        T: Transformation2d,
+    {
+        if self.columns() == 0 || self.spacing <= 0.0 {
+            return QueryResult2d::Unknown {
+                reason: UnknownReason::Degenerate,
+            };
+        }
+
+        let aabb = b.bounding_box_t(rel);
+        let columns = self.columns_overlapping(aabb.min.x, aabb.max.x);
+        // Columns only need to reach below the other shape to act as if they were infinitely deep.
+        let bottom = columns
+            .clone()
+            .map(|i| self.samples[i].min(self.samples[i + 1]) * self.scale)
+            .fold(aabb.min.y, f32::min)
+            - self.spacing;
+
+        let mut penetrating = Vec::new();
+        let mut unknown = None;
+        for i in columns.clone() {
+            let column = self.column(i, bottom);
+            let diff = column.minkowski_diff(b, rel, config);
+            match SdfvMinkowski2d::<false, false>::sdfv_minkowski(&diff) {
+                QueryResult2d::Penetrating { .. } => penetrating.push(column),
+                QueryResult2d::Separated { .. } => {}
+                result @ QueryResult2d::Unknown { .. } => unknown = Some(result),
+            }
+        }
+
+        if !penetrating.is_empty() && !P {
+            return QueryResult2d::penetrating_unmeasured();
+        }
+        // An undecided column could be the deepest or the closest one.
+        if let Some(result) = unknown {
+            return result;
+        }
+
+        if !penetrating.is_empty() {
+            return penetrating
+                .iter()
+                .map(|column| column.normal())
+                .map(|normal| {
+                    let b_min = normal.dot(b.extreme_point_t(rel, -normal));
+                    let depth = penetrating
+                        .iter()
+                        .map(|column| column.a.dot(normal).max(column.b.dot(normal)) - b_min)
+                        .fold(0.0, f32::max);
+                    (depth, normal)
+                })
+                .min_by(|(a, _), (b, _)| a.total_cmp(b))
+                .map(|(depth, normal)| QueryResult2d::penetrating(-depth * normal))
+                .unwrap();
+        }
+
+        if !D {
+            return QueryResult2d::separated_unmeasured();
+        }
+
+        let closest = |columns: Range<usize>| {
+            let mut best: Option<(f32, QueryResult2d)> = None;
+            for i in columns {
+                let column = self.column(i, bottom);
+                let result = SdfvMinkowski2d::<false, true>::sdfv_minkowski(
+                    &column.minkowski_diff(b, rel, config),
+                );
+                let Some(sdf) = result.sdf() else {
+                    return result;
+                };
+                if best.is_none_or(|(best_sdf, _)| sdf < best_sdf) {
+                    best = Some((sdf, result));
+                }
+            }
+            best.unwrap().1
+        };
+        let result = closest(columns);
+        // A closer column can only be within the found distance.
+        match result.sdf() {
+            Some(distance) => {
+                closest(self.columns_overlapping(aabb.min.x - distance, aabb.max.x + distance))
+            }
+            None => result,
+        }
+    }
+}
+
+/// Surface segment of a [Heightfield2d] column together with the solid area below it.
+#[derive(Debug, Clone, Copy)]
+struct Column {
+    a: Vec2,
+    b: Vec2,
+    bottom: f32,
+}
+
+impl Column {
+    /// Unit normal of the surface segment, pointing up.
+    fn normal(&self) -> Vec2 {
+        (self.b - self.a).perp().normalize()
+    }
+
+    fn minkowski_diff<'a, B, T>(
+        &'a self,
+        b: &'a B,
+        rel: &'a T,
+        config: &GjkEpaConfig,
+    ) -> LocalMinkowskiDiff2d<'a, Self, B, T>
+    where
+        B: ExtremePoint2d,
+        T: Transformation2d,
+    {
+        let center = 0.5 * (self.a + self.b);
+        LocalMinkowskiDiff2d::new(self, b, rel, *config)
+            .with_initial_direction(rel.apply_origin() - center)
+    }
+}
+
+impl ExtremePoint2d for Column {
+    fn extreme_point(&self, direction: Vec2) -> Vec2 {
+        [
+            self.a,
+            self.b,
+            Vec2::new(self.b.x, self.bottom),
+            Vec2::new(self.a.x, self.bottom),
+        ]
+        .into_iter()
+        .fold((f32::MIN, self.a), |(best_score, best_p), p| {
+            let score = direction.dot(p);
+            if score > best_score {
+                (score, p)
+            } else {
+                (best_score, best_p)
+            }
+        })
+        .1
+    }
+}
+
+impl RayCast2d for Heightfield2d {
+    fn cast_ray(&self, ray: &Ray2d, max_distance: f32) -> Option<RayHit2d> {
+        if self.columns() == 0 {
+            return None;
+        }
+        if let Some(height) = self.height_at(ray.origin.x) {
+            if ray.origin.y <= height {
+                return Some(RayHit2d::inside(ray));
+            }
+        }
+
+        let end = ray.at(max_distance);
+        let mut best: Option<RayHit2d> = None;
+        let mut consider = |distance: f32, normal: Vec2| {
+            if (0.0..=max_distance).contains(&distance)
+                && best.is_none_or(|best| distance < best.distance)
+            {
+                best = Some(RayHit2d {
+                    distance,
+                    point: ray.at(distance),
+                    normal,
+                });
+            }
+        };
+
+        for i in self.columns_overlapping(ray.origin.x.min(end.x), ray.origin.x.max(end.x)) {
+            let (a, b) = self.segment(i);
+            let edge = b - a;
+            let denominator = ray.direction.perp_dot(edge);
+            if denominator == 0.0 {
+                continue;
+            }
+            let distance = (a - ray.origin).perp_dot(edge) / denominator;
+            let s = (a - ray.origin).perp_dot(ray.direction) / denominator;
+            if (0.0..=1.0).contains(&s) {
+                consider(distance, edge.perp().normalize());
+            }
+        }
+
+        // The terrain extends infinitely downwards, its ends are vertical walls.
+        if ray.direction.x != 0.0 {
+            let walls = [
+                (0.0, self.sample_point(0).y, Vec2::NEG_X),
+                (self.width(), self.sample_point(self.columns()).y, Vec2::X),
+            ];
+            for (x, height, normal) in walls {
+                let distance = (x - ray.origin.x) / ray.direction.x;
+                if normal.x * ray.direction.x < 0.0 && ray.at(distance).y <= height {
+                    consider(distance, normal);
+                }
+            }
+        }
+
+        best
+    }
+}
+
+/// Implements queries between a heightfield and each of `$b`.
+macro_rules! impl_heightfield_v {
+    ($($b:ty),+) => {
+        $(
+            impl<const P: bool, const D: bool, T: Transformation2d> SdfvCommonRel2d<P, D, $b, T>
+                for Heightfield2d
+            {
+                fn sdfv_common_rel(&self, b: &$b, rel: &T) -> QueryResult2d {
+                    self.sdfv_common::<P, D, _, _>(b, rel, &GjkEpaConfig::global())
+                }
+
+                fn sdfv_common_rel_with(
+                    &self,
+                    b: &$b,
+                    rel: &T,
+                    config: &GjkEpaConfig,
+                ) -> QueryResult2d {
+                    self.sdfv_common::<P, D, _, _>(b, rel, config)
+                }
+            }
+
+            impl SdfRel2d<$b> for Heightfield2d {
+                fn sdf_rel(&self, b: &$b, rel: &impl Transformation2d) -> f32 {
+                    let result = self.sdfv_common::<true, true, _, _>(b, rel, &GjkEpaConfig::global());
+                    result.sdf().unwrap_or_else(|| {
+                        warn!("query result is unknown: {:?}", result);
+                        f32::NAN
+                    })
+                }
+            }
+        )+
+    };
+}
+
+impl_heightfield_v!(Point, Ball, Box2d, Poly2d, RoundedBox2d, Shape2d);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hill() -> Heightfield2d {
+        Heightfield2d::new(vec![0.0, 0.0, 1.0, 2.0, 1.0, 0.0, 0.0], 1.0, 1.0)
+    }
+
+    #[test_log::test]
+    fn flat_matches_half_space() {
+        let terrain = Heightfield2d::new(vec![2.0; 11], 1.0, 0.5);
+        let floor = HalfSpace2d::from_point_normal(Vec2::new(0.0, 1.0), Vec2::Y);
+        let b = Box2d::with_halfdims(0.5, 0.5);
+        for y in [0.0, 1.25, 1.75, 3.0] {
+            let t = Transform2d::from_translation(Vec2::new(5.0, y)).with_angle(0.3);
+            let expected = floor.sdfv_rel(&b, &t);
+            let actual = terrain.sdfv_rel(&b, &t);
+            assert_eq!(actual.collides(), expected.collides());
+            assert!((actual.sdf().unwrap() - expected.sdf().unwrap()).abs() < 1e-3);
+            let normal = actual.sdfv().unwrap() / actual.sdf().unwrap();
+            assert!(normal.abs_diff_eq(Vec2::Y, 1e-3));
+        }
+    }
+
+    #[test_log::test]
+    fn slope_penetration() {
+        let terrain = hill();
+        let ball = Ball::with_radius(0.5);
+        let slope_normal = Vec2::new(-1.0, 1.0).normalize();
+        // Ball centered on the rising slope.
+        let result = terrain.sdfv_rel(&ball, &Vec2::new(1.5, 0.5));
+        assert_eq!(result.collides(), Some(true), "{result:?}");
+        assert!((result.sdf().unwrap() + 0.5).abs() < 1e-4);
+        assert!(result.normal().unwrap().abs_diff_eq(slope_normal, 1e-4));
+    }
+
+    #[test_log::test]
+    fn deep_inside_pushes_up() {
+        let terrain = hill();
+        let b = Box2d::with_halfdims(0.25, 0.25);
+        // Far below the flat part, a single column is overlapped.
+        let penetration = terrain.penetrates_rel(&b, &Vec2::new(0.5, -10.0)).unwrap();
+        assert!(penetration.abs_diff_eq(Vec2::new(0.0, -10.25), 1e-3));
+        assert!(b.collides_rel(&terrain, &Vec2::new(-0.5, 10.0)));
+    }
+
+    #[test_log::test]
+    fn distance_across_columns() {
+        let terrain = hill();
+        let ball = Ball::with_radius(0.25);
+        // Right next to the peak, the closest point is on the neighbouring column.
+        let distance = terrain.sdf_rel(&ball, &Vec2::new(3.0, 3.0));
+        assert!((distance - 0.75).abs() < 1e-3);
+        let distance = terrain.sdf_rel(&ball, &Vec2::new(2.75, 2.75));
+        let expected = (Vec2::new(2.75, 2.75) - Vec2::new(3.0, 2.0)).length() - 0.25;
+        assert!(distance < expected + 1e-3);
+        assert!(!terrain.collides_rel(&Poly2d::regular(6, 0.25), &Vec2::new(3.0, 2.5)));
+    }
+
+    #[test_log::test]
+    fn unknown_column_is_reported() {
+        let terrain = hill();
+        let ball = Ball::with_radius(0.25);
+        let config = GjkEpaConfig {
+            gjk_iteration_limit: 0,
+            ..GjkEpaConfig::DEFAULT
+        };
+        for rel in [Vec2::new(3.0, 3.0), Vec2::new(1.5, 0.5)] {
+            let result = terrain.sdfv_rel_with(&ball, &rel, &config);
+            assert!(result.is_unknown(), "{result:?}");
+        }
+    }
+
+    #[test_log::test]
+    fn ray_cast() {
+        let terrain = hill();
+        let hit = terrain
+            .cast_ray(&Ray2d::new(Vec2::new(0.0, 1.5), Vec2::X), 10.0)
+            .unwrap();
+        assert!((hit.distance - 2.5).abs() < 1e-5);
+        assert!(hit
+            .normal
+            .abs_diff_eq(Vec2::new(-1.0, 1.0).normalize(), 1e-5));
+
+        let hit = terrain
+            .cast_ray(&Ray2d::new(Vec2::new(-2.0, -1.0), Vec2::X), 10.0)
+            .unwrap();
+        assert_eq!(hit.distance, 2.0);
+        assert_eq!(hit.normal, Vec2::NEG_X);
+
+        assert_eq!(
+            terrain.cast_ray(&Ray2d::new(Vec2::new(3.0, 3.0), Vec2::Y), 10.0),
+            None
+        );
+        let ray = Ray2d::new(Vec2::new(3.0, 1.0), Vec2::Y);
+        assert_eq!(terrain.cast_ray(&ray, 10.0), Some(RayHit2d::inside(&ray)));
+    }
+}
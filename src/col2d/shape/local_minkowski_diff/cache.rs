@@ -0,0 +1,168 @@
+use super::*;
+use crate::col2d::detection::SdfvMinkowski2d;
+
+/// Warm-start data for GJK, kept between queries on the same pair of shapes.
+///
+/// Persistent pairs (e.g. a character standing on a platform) move very little between frames,
+/// so the separating direction of the last query is an excellent first guess for the next one.
+/// Seeding GJK with it typically lets coherent queries converge in one or two iterations.
+///
+/// The cached direction is in `a`'s local space, the cache must therefore always be used with
+/// the same pair in the same order.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// let a = Box2d::with_halfdims(1.0, 1.0);
+/// let b = Ball::with_radius(0.5);
+/// let mut cache = GjkCache::new();
+/// for frame in 0..3 {
+///     let rel = Translate2d::from(Vec2::new(0.0, 1.6 + 0.01 * frame as f32));
+///     let diff = LocalMinkowskiDiff2d::raw(&a, &b, &rel);
+///     let mut stats = SolverStats::default();
+///     let result = cache.query::<false, false, _, _, _>(diff, &mut stats);
+///     assert_eq!(result.collides(), Some(false));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GjkCache {
+    direction: Option<Vec2>,
+}
+
+impl GjkCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Direction the next query will start from, `None` if the cache is cold.
+    pub fn direction(&self) -> Option<Vec2> {
+        self.direction
+    }
+
+    /// Forgets the cached direction, e.g. after teleporting one of the shapes.
+    pub fn clear(&mut self) {
+        self.direction = None;
+    }
+
+    /// Seeds `diff` with the cached direction.
+    pub fn seed<'a, A, B, T>(
+        &self,
+        diff: LocalMinkowskiDiff2d<'a, A, B, T>,
+    ) -> LocalMinkowskiDiff2d<'a, A, B, T>
+    where
+        A: ExtremePoint2d,
+        B: ExtremePoint2d,
+        T: Transformation2d,
+    {
+        match self.direction {
+            Some(direction) => diff.with_initial_direction(direction),
+            None => diff,
+        }
+    }
+
+    /// Remembers the outcome of a query for the next one.
+    pub fn update(&mut self, result: &QueryResult2d, stats: &SolverStats) {
+        let normal = match result.normal() {
+            Some(normal) => normal,
+            None => {
+                self.clear();
+                return;
+            }
+        };
+
+        // Collision-only queries do not compute the normal, the last search direction is still a
+        // good guess though.
+        self.direction = [normal, stats.direction]
+            .into_iter()
+            .find(|direction| direction.is_finite() && direction.length_squared() > 0.0);
+    }
+
+    /// Runs a seeded query on `diff` and updates the cache with its result.
+    pub fn query<'a, const COMPUTE_PENETRATION: bool, const COMPUTE_DISTANCE: bool, A, B, T>(
+        &mut self,
+        diff: LocalMinkowskiDiff2d<'a, A, B, T>,
+        stats: &mut SolverStats,
+    ) -> QueryResult2d
+    where
+        A: ExtremePoint2d,
+        B: ExtremePoint2d,
+        T: Transformation2d,
+        LocalMinkowskiDiff2d<'a, A, B, T>: SdfvMinkowski2d<COMPUTE_PENETRATION, COMPUTE_DISTANCE>,
+    {
+        let diff = self.seed(diff);
+        let result = SdfvMinkowski2d::<COMPUTE_PENETRATION, COMPUTE_DISTANCE>::sdfv_minkowski_stats(
+            &diff, stats,
+        );
+        self.update(&result, stats);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collides(
+        cache: &mut GjkCache,
+        a: &Poly2d,
+        b: &Poly2d,
+        rel: &Transform2d,
+    ) -> (QueryResult2d, SolverStats) {
+        let diff = LocalMinkowskiDiff2d::raw(a, b, rel);
+        let mut stats = SolverStats::default();
+        let result = cache.query::<false, false, _, _, _>(diff, &mut stats);
+        (result, stats)
+    }
+
+    #[test_log::test]
+    fn warm_separated_converges_fast() {
+        let a = Poly2d::regular(16, 1.0);
+        let b = Poly2d::regular(16, 1.0);
+        let mut cache = GjkCache::new();
+
+        let rel = Transform2d::from_translation(Vec2::new(2.1, 0.4)).with_angle(0.1);
+        let (cold, cold_stats) = collides(&mut cache, &a, &b, &rel);
+        assert_eq!(cold.collides(), Some(false));
+        assert!(cache.direction().is_some());
+
+        let rel = Transform2d::from_translation(Vec2::new(2.11, 0.41)).with_angle(0.11);
+        let (warm, warm_stats) = collides(&mut cache, &a, &b, &rel);
+        assert_eq!(warm.collides(), Some(false));
+        assert!(warm_stats.gjk_iterations <= 2);
+        assert!(warm_stats.gjk_iterations <= cold_stats.gjk_iterations);
+    }
+
+    #[test_log::test]
+    fn warm_result_matches_cold() {
+        let a = Poly2d::regular(8, 1.0);
+        let b = Poly2d::regular(5, 0.5);
+        let mut cache = GjkCache::new();
+        for i in 0..10 {
+            let offset = i as f32 * 0.05;
+            let rel =
+                Transform2d::from_translation(Vec2::new(1.0 + offset, 0.5)).with_angle(offset);
+            let diff = LocalMinkowskiDiff2d::raw(&a, &b, &rel);
+            let mut stats = SolverStats::default();
+            let warm = cache.query::<true, true, _, _, _>(diff, &mut stats);
+            let cold = SdfvMinkowski2d::<true, true>::sdfv_minkowski(&LocalMinkowskiDiff2d::raw(
+                &a, &b, &rel,
+            ));
+            assert_eq!(warm.collides(), cold.collides());
+            assert!((warm.sdf().unwrap() - cold.sdf().unwrap()).abs() < 0.01);
+        }
+    }
+
+    #[test_log::test]
+    fn unknown_clears_cache() {
+        let mut cache = GjkCache::new();
+        cache.update(&QueryResult2d::separated(Vec2::X), &SolverStats::default());
+        assert_eq!(cache.direction(), Some(Vec2::X));
+        cache.update(
+            &QueryResult2d::Unknown {
+                reason: UnknownReason::Degenerate,
+            },
+            &SolverStats::default(),
+        );
+        assert_eq!(cache.direction(), None);
+    }
+}
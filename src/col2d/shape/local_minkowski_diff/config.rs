@@ -0,0 +1,151 @@
+#[cfg(feature = "std")]
+use std::{
+    cell::Cell,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        RwLock,
+    },
+};
+
+use crate::Vec2;
+
+/// Settings of the GJK and EPA solvers used by [LocalMinkowskiDiff2d](super::LocalMinkowskiDiff2d).
+///
+/// Queries use [GjkEpaConfig::global] unless given a config, either per query with
+/// [CollidesRel2d::collides_rel_with](crate::col2d::CollidesRel2d::collides_rel_with) and its
+/// siblings or per difference with
+/// [LocalMinkowskiDiff2d::with_config](super::LocalMinkowskiDiff2d::with_config).
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// let config = GjkEpaConfig {
+///     gjk_iteration_limit: 8,
+///     ..GjkEpaConfig::DEFAULT
+/// };
+/// let a = Box2d::with_halfdims(1.0, 1.0);
+/// let rel = Translate2d::from(Vec2::new(1.0, 0.0));
+/// let diff = LocalMinkowskiDiff2d::raw(&a, &a, &rel).with_config(config);
+/// let mut stats = SolverStats::default();
+/// let result = SdfvMinkowski2d::<true, true>::sdfv_minkowski_stats(&diff, &mut stats);
+/// assert_eq!(result.collides(), Some(true));
+/// assert!(stats.converged);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GjkEpaConfig {
+    /// Maximum number of GJK iterations.
+    pub gjk_iteration_limit: usize,
+    /// Maximum number of EPA iterations.
+    pub epa_iteration_limit: usize,
+    /// Improvement a new support point has to make to keep GJK (distance) and EPA iterating.
+    pub improvement_tolerance: Tolerance,
+    /// Search directions shorter than this are considered degenerate.
+    pub direction_epsilon: f32,
+}
+
+/// Tolerance used to compare solver progress between iterations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tolerance {
+    /// Required improvement relative to the previous value.
+    Relative(f32),
+    /// Required improvement in absolute units.
+    Absolute(f32),
+}
+
+impl Tolerance {
+    /// Whether `a` is sufficiently larger than `b`.
+    pub fn is_improvement(&self, a: f32, b: f32) -> bool {
+        match *self {
+            Self::Relative(t) => {
+                if b > 0.0 {
+                    a > b * (1.0 + t)
+                } else {
+                    a > b * (1.0 - t)
+                }
+            }
+            Self::Absolute(t) => a > b + t,
+        }
+    }
+}
+
+impl GjkEpaConfig {
+    pub const DEFAULT: Self = Self {
+        gjk_iteration_limit: 20,
+        epa_iteration_limit: 20,
+        improvement_tolerance: Tolerance::Relative(0.01),
+        direction_epsilon: f32::EPSILON,
+    };
+
+    /// Config used by queries that were not given one explicitly.
+    ///
+    /// Without the `std` feature this is always [GjkEpaConfig::DEFAULT].
+    ///
+    /// Every thread keeps a copy and only takes the lock again after [GjkEpaConfig::set_global],
+    /// so this is cheap enough to call per query.
+    pub fn global() -> Self {
+        #[cfg(feature = "std")]
+        {
+            let generation = GLOBAL_GENERATION.load(Ordering::Acquire);
+            CACHED_GLOBAL_CONFIG.with(|cached| {
+                let (cached_generation, cached_config) = cached.get();
+                if cached_generation == generation {
+                    return cached_config;
+                }
+                let config = match GLOBAL_CONFIG.read() {
+                    Ok(config) => *config,
+                    Err(poisoned) => *poisoned.into_inner(),
+                };
+                cached.set((generation, config));
+                config
+            })
+        }
+        #[cfg(not(feature = "std"))]
+        Self::DEFAULT
+    }
+
+    /// Changes the config used by queries that were not given one explicitly.
+    #[cfg(feature = "std")]
+    pub fn set_global(config: Self) {
+        match GLOBAL_CONFIG.write() {
+            Ok(mut global) => *global = config,
+            Err(poisoned) => *poisoned.into_inner() = config,
+        }
+        GLOBAL_GENERATION.fetch_add(1, Ordering::Release);
+    }
+}
+
+impl Default for GjkEpaConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[cfg(feature = "std")]
+static GLOBAL_CONFIG: RwLock<GjkEpaConfig> = RwLock::new(GjkEpaConfig::DEFAULT);
+
+/// Bumped by [GjkEpaConfig::set_global] to invalidate [CACHED_GLOBAL_CONFIG].
+#[cfg(feature = "std")]
+static GLOBAL_GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "std")]
+thread_local! {
+    static CACHED_GLOBAL_CONFIG: Cell<(usize, GjkEpaConfig)> =
+        const { Cell::new((0, GjkEpaConfig::DEFAULT)) };
+}
+
+/// Diagnostics collected while running GJK and EPA.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SolverStats {
+    /// Number of GJK iterations performed.
+    pub gjk_iterations: usize,
+    /// Number of EPA iterations performed.
+    pub epa_iterations: usize,
+    /// Number of points in the simplex when GJK finished.
+    pub simplex_size: usize,
+    /// Number of points in the EPA polytope when it finished.
+    pub polytope_size: usize,
+    /// Last GJK search direction, for separated shapes this is a separating axis.
+    pub direction: Vec2,
+    /// Whether the solver reached an answer within its iteration limits.
+    pub converged: bool,
+}
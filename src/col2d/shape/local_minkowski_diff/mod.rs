@@ -1,9 +1,14 @@
+mod cache;
+mod config;
 mod v;
 
 use crate::{error, info, trace, warn};
 
 use super::*;
 
+pub use cache::*;
+pub use config::*;
+
 pub struct LocalMinkowskiDiff2d<'a, A, B, T>
 where
     A: ExtremePoint2d,
@@ -13,6 +18,8 @@ where
     a: &'a A,
     b: &'a B,
     rel: &'a T,
+    config: GjkEpaConfig,
+    initial_direction: Option<Vec2>,
 }
 
 impl<'a, A, B, T> LocalMinkowskiDiff2d<'a, A, B, T>
@@ -21,11 +28,44 @@ where
     B: ExtremePoint2d,
     T: Transformation2d,
 {
+    /// Difference using [GjkEpaConfig::global], see [LocalMinkowskiDiff2d::new].
     pub fn raw(a: &'a A, b: &'a B, rel: &'a T) -> Self {
-        Self { a, b, rel }
+        Self::new(a, b, rel, GjkEpaConfig::global())
+    }
+
+    pub fn new(a: &'a A, b: &'a B, rel: &'a T, config: GjkEpaConfig) -> Self {
+        Self {
+            a,
+            b,
+            rel,
+            config,
+            initial_direction: None,
+        }
+    }
+
+    /// Use `config` instead of [GjkEpaConfig::global] for queries on this difference.
+    pub fn with_config(mut self, config: GjkEpaConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn config(&self) -> &GjkEpaConfig {
+        &self.config
+    }
+
+    /// Start GJK from `direction` instead of the direction towards `b`, see [GjkCache].
+    pub fn with_initial_direction(mut self, direction: Vec2) -> Self {
+        if direction.is_finite() && !self.direction_is_too_small(direction) {
+            self.initial_direction = Some(direction);
+        }
+        self
     }
 
     pub fn initial_direction(&self) -> Vec2 {
+        if let Some(direction) = self.initial_direction {
+            return direction;
+        }
+
         let direction = self.rel.apply_origin();
         if direction.length_squared() < f32::EPSILON {
             Vec2::new(1.0, 0.0)
@@ -34,12 +74,17 @@ where
         }
     }
 
+    fn direction_is_too_small(&self, direction: Vec2) -> bool {
+        let epsilon = self.config.direction_epsilon;
+        direction.length_squared() < epsilon * epsilon
+    }
+
     // fn iteration_limit(&self) -> usize {
     //     self.a.points.len() + self.b.points.len()
     // }
 }
 
-impl<'a, A, B, T> ExtremePoint2d for LocalMinkowskiDiff2d<'_, A, B, T>
+impl<A, B, T> ExtremePoint2d for LocalMinkowskiDiff2d<'_, A, B, T>
 where
     A: ExtremePoint2d,
     B: ExtremePoint2d,
@@ -62,11 +107,11 @@ where
 struct EpaPoly2d(Poly2d);
 
 impl EpaPoly2d {
-    fn calculate_penetration<'a, A, B, T>(
+    fn calculate_penetration<A, B, T>(
         &mut self,
-        diff: &LocalMinkowskiDiff2d<'a, A, B, T>,
-        iteration_limit: usize,
-    ) -> Vec2
+        diff: &LocalMinkowskiDiff2d<'_, A, B, T>,
+        stats: &mut SolverStats,
+    ) -> QueryResult2d
     where
         A: ExtremePoint2d,
         B: ExtremePoint2d,
@@ -79,8 +124,10 @@ impl EpaPoly2d {
             self.0.points
         );
 
+        let iteration_limit = diff.config.epa_iteration_limit;
         for _i in 0..iteration_limit {
             trace!("[EPA] iteration {}", _i);
+            stats.epa_iterations += 1;
             let (edge_index, a, b) = self.find_closest_edge();
             trace!("closest edge: {:?} {:?}", a, b);
             let normal = (b - a).perp_right();
@@ -92,15 +139,17 @@ impl EpaPoly2d {
             let fitness_to_beat = a.dot(normal);
             trace!("fitness to beat: {}", fitness_to_beat);
 
-            if !is_a_sufficiently_better_than_b(
-                fitness,
-                fitness_to_beat,
-                DISTANCE_ITERATION_FITNESS_RELATIVE_IMPROVEMENT_REQUIRED,
-            ) {
+            if !diff
+                .config
+                .improvement_tolerance
+                .is_improvement(fitness, fitness_to_beat)
+            {
                 trace!("EPA converged");
+                stats.polytope_size = self.0.points.len();
+                stats.converged = true;
                 let denom = normal.dot(normal);
                 trace!("denom: {}", denom);
-                return normal * (-fitness / denom);
+                return QueryResult2d::penetrating(normal * (-fitness / denom));
             }
 
             trace!("splitting edge at index {}", edge_index);
@@ -109,8 +158,13 @@ impl EpaPoly2d {
         }
 
         warn!("EPA did not converge in {} iterations", iteration_limit);
+        stats.polytope_size = self.0.points.len();
 
-        Vec2::ZERO
+        QueryResult2d::Unknown {
+            reason: UnknownReason::NotConverged {
+                iterations: iteration_limit,
+            },
+        }
     }
 
     fn find_closest_edge(&self) -> (usize, Vec2, Vec2) {
@@ -167,8 +221,7 @@ impl Simplex2d {
                 let ab = b - a;
                 let ao = -a;
                 if ab.dot(ao) >= 0.0 {
-                    let ab_perp = ab.cross_aba(ao);
-                    (false, ab_perp)
+                    (false, towards_origin(ab, ao))
                 } else {
                     (false, ao)
                 }
@@ -203,12 +256,19 @@ impl Simplex2d {
                         trace!("same direction ab ao");
                         self.0.points = vec![a, b];
                         trace!("points after: {:?}", self.0.points);
-                        (false, ab_perp)
-                    } else {
-                        trace!("opposite direction ab ao");
+                        (false, ab_perp.normalize_or_zero())
+                    } else if same_direction(ac, ao) {
+                        trace!("opposite direction ab ao, same direction ac ao");
                         self.0.points = vec![a, c];
                         trace!("points after: {:?}", self.0.points);
-                        (false, ac_perp)
+                        (false, towards_origin(ac, ao))
+                    } else {
+                        // The origin is closest to `a` itself, e.g. when `a` lies on a curve
+                        // bulging past the previous edge.
+                        trace!("opposite direction ab ao and ac ao");
+                        self.0.points = vec![a];
+                        trace!("points after: {:?}", self.0.points);
+                        (false, ao)
                     }
                     // trace!("same direction ab_perp ao");
                     // self.0.points = vec![a, b];
@@ -218,7 +278,7 @@ impl Simplex2d {
                     trace!("same direction ac_perp ao");
                     self.0.points = vec![a, c];
                     trace!("points after: {:?}", self.0.points);
-                    (false, ac_perp)
+                    (false, ac_perp.normalize_or_zero())
                 } else {
                     trace!("captured the origin");
                     (true, Vec2::NAN)
@@ -290,6 +350,14 @@ impl Simplex2d {
     }
 }
 
+/// Perpendicular from the line along `edge` to the origin, `ao` points from the line to the origin.
+///
+/// The triple product alone scales with the squared length of `edge`, so short edges near
+/// convergence would fall under `direction_epsilon` and end the search early.
+fn towards_origin(edge: Vec2, ao: Vec2) -> Vec2 {
+    edge.cross_aba(ao) / edge.length_squared()
+}
+
 fn same_direction(a: Vec2, b: Vec2) -> bool {
     a.dot(b) >= 0.0
 }
@@ -330,16 +398,6 @@ impl Cross2d for Vec2 {
     }
 }
 
-const DISTANCE_ITERATION_FITNESS_RELATIVE_IMPROVEMENT_REQUIRED: f32 = 0.01;
-
-fn is_a_sufficiently_better_than_b(a: f32, b: f32, t: f32) -> bool {
-    if b > 0.0 {
-        return a > b * (1.0 + t);
-    } else {
-        return a > b * (1.0 - t);
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use crate::utils::approx::Approx;
@@ -401,6 +459,72 @@ mod tests {
         assert_eq!(extreme_point.x, -1.0);
     }
 
+    #[test_log::test]
+    fn iteration_limit_reports_not_converged() {
+        let a = Poly2d::regular(16, 1.0);
+        let b = Poly2d::regular(16, 1.0);
+        let rel = Transform2d::from_translation(Vec2::new(1.5, 0.3)).with_angle(0.1);
+        let config = GjkEpaConfig {
+            gjk_iteration_limit: 1,
+            ..GjkEpaConfig::DEFAULT
+        };
+        let diff = LocalMinkowskiDiff2d::raw(&a, &b, &rel).with_config(config);
+        let mut stats = SolverStats::default();
+        let result = SdfvMinkowski2d::<true, true>::sdfv_minkowski_stats(&diff, &mut stats);
+        assert_eq!(
+            result,
+            QueryResult2d::Unknown {
+                reason: UnknownReason::NotConverged { iterations: 1 }
+            }
+        );
+        assert_eq!(stats.gjk_iterations, 1);
+        assert!(!stats.converged);
+    }
+
+    #[test_log::test]
+    fn per_query_config() {
+        let a = Shape2d::Poly2d(Poly2d::regular(16, 1.0));
+        let b = Shape2d::Ball(Ball::with_radius(1.0));
+        let rel = Transform2d::from_translation(Vec2::new(1.5, 0.3)).with_angle(0.1);
+        let config = GjkEpaConfig {
+            gjk_iteration_limit: 1,
+            ..GjkEpaConfig::DEFAULT
+        };
+        assert!(a.collides_rel(&b, &rel));
+        assert!(!a.collides_rel_with(&b, &rel, &config));
+        assert!(b.sdfv_rel_with(&a, &rel.inverse(), &config).is_unknown());
+        assert!(b
+            .sdfv_rel_with(&a, &rel.inverse(), &GjkEpaConfig::DEFAULT)
+            .collides()
+            .unwrap());
+    }
+
+    #[test_log::test]
+    fn stats_penetration() {
+        let a = Box2d::with_halfdims(1.0, 1.0);
+        let rel = Transform2d::from_translation(Vec2::new(1.5, 0.0)).with_angle(0.3);
+        let diff = LocalMinkowskiDiff2d::raw(&a, &a, &rel);
+        let mut stats = SolverStats::default();
+        let result = SdfvMinkowski2d::<true, false>::sdfv_minkowski_stats(&diff, &mut stats);
+        assert_eq!(result.collides(), Some(true));
+        assert!(stats.converged);
+        assert!(stats.gjk_iterations > 0);
+        assert!(stats.epa_iterations > 0);
+        assert_eq!(stats.simplex_size, 3);
+        assert!(stats.polytope_size >= 3);
+    }
+
+    #[test_log::test]
+    fn absolute_tolerance() {
+        let tolerance = Tolerance::Absolute(0.1);
+        assert!(tolerance.is_improvement(1.2, 1.0));
+        assert!(!tolerance.is_improvement(1.05, 1.0));
+        assert!(!tolerance.is_improvement(-0.95, -1.0));
+        let tolerance = Tolerance::Relative(0.1);
+        assert!(tolerance.is_improvement(-0.85, -1.0));
+        assert!(!tolerance.is_improvement(-0.95, -1.0));
+    }
+
     #[test_log::test]
     fn ground_truth_check_1() {
         let a_angle: f32 = 0.0;
@@ -449,4 +573,44 @@ mod tests {
 
         assert_approx_eq!(m_gt, extreme_point);
     }
+
+    #[test_log::test]
+    fn distance_to_curved_shape_converges() {
+        // Distance GJK on a ball refines the same short edge over and over, which used to be
+        // mistaken for a degenerate search direction.
+        let a = Box2d::with_halfdims(0.5, 0.5);
+        let b = Ball::with_radius(0.25);
+        for offset in [Vec2::new(1.5, 2.5), Vec2::new(-1.5, 2.5)] {
+            let diff = LocalMinkowskiDiff2d::raw(&a, &b, &offset);
+            let mut stats = SolverStats::default();
+            let result = SdfvMinkowski2d::<false, true>::sdfv_minkowski_stats(&diff, &mut stats);
+            let expected = (offset.abs() - Vec2::splat(0.5)).length() - 0.25;
+            assert!(stats.converged);
+            assert!((result.sdf().unwrap() - expected).abs() < 1e-3);
+        }
+    }
+
+    #[test_log::test]
+    fn distance_to_curved_shape_near_vertex() {
+        // The last three support points on the ball are nearly collinear, the triangle case used
+        // to turn the search around and not converge within the iteration limit.
+        let a = Poly2d::new(&[
+            Vec2::new(2.0, 1.0),
+            Vec2::new(3.0, 2.0),
+            Vec2::new(3.0, -5.0),
+            Vec2::new(2.0, -5.0),
+        ]);
+        let b = Ball::with_radius(0.25);
+        let offset = Vec2::new(2.75, 2.75);
+        let diff = LocalMinkowskiDiff2d::raw(&a, &b, &offset)
+            .with_initial_direction(offset - Vec2::new(2.5, 1.5));
+        let mut stats = SolverStats::default();
+        let result = SdfvMinkowski2d::<false, true>::sdfv_minkowski_stats(&diff, &mut stats);
+        let expected = (offset - Vec2::new(3.0, 2.0)).length() - 0.25;
+        assert!(stats.converged, "{stats:?}");
+        assert!(
+            (result.sdf().unwrap() - expected).abs() < 1e-3,
+            "{result:?} {expected}"
+        );
+    }
 }
@@ -16,14 +16,15 @@ where
     B: ExtremePoint2d,
     T: Transformation2d,
 {
-    fn sdfv_minkowski(&self) -> (bool, Vec2) {
-        println!("[ GJK (collision only)]");
+    fn sdfv_minkowski_stats(&self, stats: &mut SolverStats) -> QueryResult2d {
+        trace!("[ GJK (collision only)]");
         let mut direction = self.initial_direction();
         trace!("initial direction: {}", direction);
         let mut simplex = Simplex2d::new();
-        let iteration_limit = 20;
+        let iteration_limit = self.config.gjk_iteration_limit;
 
         for _i in 0..iteration_limit {
+            stats.gjk_iterations += 1;
             let _human_counter = _i + 1;
             trace!("-- iteration {_i}");
             let a = self.extreme_point(direction);
@@ -32,24 +33,27 @@ where
             trace!("M{_human_counter} fitness: {}", a.dot(direction));
             if a.dot(direction) <= 0.0 {
                 trace!("Not colliding");
-                return (false, Vec2::NAN);
+                finish_gjk(stats, &simplex, direction, true);
+                return QueryResult2d::separated_unmeasured();
             }
 
             let (collides, new_direction) = simplex.add_point(a);
             trace!("new direction: {}", new_direction);
-            let new_direction_too_small = direction_is_too_small(new_direction);
+            let new_direction_too_small = self.direction_is_too_small(new_direction);
             trace!("new direction too small: {}", new_direction_too_small);
             if collides || new_direction_too_small {
                 trace!("Simplex collides");
-                return (true, Vec2::NAN);
+                finish_gjk(stats, &simplex, direction, true);
+                return QueryResult2d::penetrating_unmeasured();
             }
 
             direction = new_direction;
         }
 
         warn_simplex_not_converged!(iteration_limit);
+        finish_gjk(stats, &simplex, direction, false);
 
-        (false, Vec2::NAN)
+        not_converged(iteration_limit)
     }
 }
 
@@ -59,29 +63,31 @@ where
     B: ExtremePoint2d,
     T: Transformation2d,
 {
-    fn sdfv_minkowski(&self) -> (bool, Vec2) {
-        println!("[ GJK (distance only)]");
+    fn sdfv_minkowski_stats(&self, stats: &mut SolverStats) -> QueryResult2d {
+        trace!("[ GJK (distance only)]");
         let mut direction = self.initial_direction();
         let mut simplex = Simplex2d::new();
-        let iteration_limit = 20;
+        let iteration_limit = self.config.gjk_iteration_limit;
 
         let mut last_a = self.extreme_point(direction);
         direction = simplex.add_point(last_a).1;
 
         for _ in 0..iteration_limit {
+            stats.gjk_iterations += 1;
             let a = self.extreme_point(direction);
             let fitness = a.dot(direction);
 
             if fitness <= 0.0 {
                 let last_fitness = last_a.dot(direction);
                 if fitness <= last_fitness {
-                    return (false, simplex.distance_to_origin());
+                    finish_gjk(stats, &simplex, direction, true);
+                    return QueryResult2d::separated(simplex.distance_to_origin());
                 }
             }
             last_a = a;
 
             let (collides, new_direction) = simplex.add_point(a);
-            let new_direction_too_small = direction_is_too_small(new_direction);
+            let new_direction_too_small = self.direction_is_too_small(new_direction);
             let new_direction = if new_direction_too_small {
                 // panic!("new direction too small: {new_direction:?}");
                 direction.perp()
@@ -91,15 +97,17 @@ where
 
             if collides {
                 assert_eq!(simplex.0.points.len(), 3);
-                return (true, Vec2::NAN);
+                finish_gjk(stats, &simplex, direction, true);
+                return QueryResult2d::penetrating_unmeasured();
             }
 
             direction = new_direction;
         }
 
         warn_simplex_not_converged!(iteration_limit);
+        finish_gjk(stats, &simplex, direction, false);
 
-        (false, simplex.distance_to_origin())
+        not_converged(iteration_limit)
     }
 }
 
@@ -109,15 +117,16 @@ where
     B: ExtremePoint2d,
     T: Transformation2d,
 {
-    fn sdfv_minkowski(&self) -> (bool, Vec2) {
+    fn sdfv_minkowski_stats(&self, stats: &mut SolverStats) -> QueryResult2d {
         trace!("");
         trace!("[ GJK (penetration only)]");
-        let iteration_limit = 20;
+        let iteration_limit = self.config.gjk_iteration_limit;
         let mut direction = self.initial_direction();
         trace!("initial direction: {}", direction);
         let mut simplex = Simplex2d::new();
 
         for _i in 0..iteration_limit {
+            stats.gjk_iterations += 1;
             let _human_counter = _i + 1;
             trace!("-- iteration {_i}");
             trace!("direction: {}", direction);
@@ -129,12 +138,13 @@ where
 
             if fitness <= 0.0 {
                 trace!("Collision no longer possible");
-                return (false, Vec2::NAN);
+                finish_gjk(stats, &simplex, direction, true);
+                return QueryResult2d::separated_unmeasured();
             }
 
             let (collides, new_direction) = simplex.add_point(a);
             trace!("new direction: {}", new_direction);
-            let new_direction_too_small = direction_is_too_small(new_direction);
+            let new_direction_too_small = self.direction_is_too_small(new_direction);
             let new_direction = if new_direction_too_small {
                 let perp = direction.perp();
                 warn!("new direction too small: {new_direction:?} OVERRIDING with {perp:?}");
@@ -147,9 +157,9 @@ where
             if collides {
                 trace!("Simplex collides");
                 simplex.enforce_counter_clockwise_winding();
+                stats.simplex_size = simplex.0.points.len();
                 let mut epa = EpaPoly2d(simplex.0);
-                let penetration = epa.calculate_penetration(&self, iteration_limit);
-                return (true, penetration);
+                return epa.calculate_penetration(self, stats);
             }
 
             trace!("new direction: {}", new_direction);
@@ -158,8 +168,9 @@ where
         }
 
         warn_simplex_not_converged!(iteration_limit);
+        finish_gjk(stats, &simplex, direction, false);
 
-        (false, Vec2::NAN)
+        not_converged(iteration_limit)
     }
 }
 
@@ -169,10 +180,10 @@ where
     B: ExtremePoint2d,
     T: Transformation2d,
 {
-    fn sdfv_minkowski(&self) -> (bool, Vec2) {
+    fn sdfv_minkowski_stats(&self, stats: &mut SolverStats) -> QueryResult2d {
         trace!("");
         trace!("[ GJK (full) ]");
-        let iteration_limit = 20;
+        let iteration_limit = self.config.gjk_iteration_limit;
         let mut direction = self.initial_direction();
         trace!("initial direction: {}", direction);
         let mut simplex = Simplex2d::new();
@@ -183,6 +194,7 @@ where
         let mut collision_possible = true;
 
         for _i in 0..iteration_limit {
+            stats.gjk_iterations += 1;
             let _human_counter = _i + 2;
             trace!("-- iteration {_i}");
             trace!("direction: {}", direction);
@@ -197,14 +209,15 @@ where
                 collision_possible = false;
                 let last_fitness = last_a.dot(direction);
 
-                if !is_a_sufficiently_better_than_b(
-                    fitness,
-                    last_fitness,
-                    DISTANCE_ITERATION_FITNESS_RELATIVE_IMPROVEMENT_REQUIRED,
-                ) {
+                if !self
+                    .config
+                    .improvement_tolerance
+                    .is_improvement(fitness, last_fitness)
+                {
                     trace!("fitness: {fitness} is *NOT* sufficiently better than last_fitness: {last_fitness} (break)");
                     trace!("No intersection");
-                    return (false, simplex.distance_to_origin());
+                    finish_gjk(stats, &simplex, direction, true);
+                    return QueryResult2d::separated(simplex.distance_to_origin());
                 }
                 trace!("fitness: {fitness} *is* sufficiently better than last_fitness: {last_fitness} (continue)");
             }
@@ -213,7 +226,10 @@ where
             let (collides, new_direction) = simplex.add_point(a);
             if collides && !collision_possible {
                 error!("Simplex collided after collision was no longer possible");
-                panic!("Simplex collided after collision was no longer possible");
+                finish_gjk(stats, &simplex, direction, false);
+                return QueryResult2d::Unknown {
+                    reason: UnknownReason::Degenerate,
+                };
             }
             trace!("new direction: {}", new_direction);
             // very close to zero. This is a sign that the origin lies on the boundary of the
@@ -221,7 +237,7 @@ where
             // We mainly have two options:
             //   - Consider this a collision
             //   - Start a new iteration with a new direction
-            let new_direction_too_small = direction_is_too_small(new_direction);
+            let new_direction_too_small = self.direction_is_too_small(new_direction);
             let new_direction = if new_direction_too_small {
                 // panic!("new direction too small: {new_direction:?}");
                 direction.perp()
@@ -232,9 +248,9 @@ where
             if collides {
                 trace!("Simplex collides");
                 simplex.enforce_counter_clockwise_winding();
+                stats.simplex_size = simplex.0.points.len();
                 let mut epa = EpaPoly2d(simplex.0);
-                let penetration = epa.calculate_penetration(&self, iteration_limit);
-                return (true, penetration);
+                return epa.calculate_penetration(self, stats);
             }
 
             trace!("new direction: {}", new_direction);
@@ -243,11 +259,20 @@ where
         }
 
         warn_simplex_not_converged!(iteration_limit);
+        finish_gjk(stats, &simplex, direction, false);
 
-        (false, simplex.distance_to_origin())
+        not_converged(iteration_limit)
     }
 }
 
-fn direction_is_too_small(direction: Vec2) -> bool {
-    direction.length_squared() < f32::EPSILON * f32::EPSILON
+fn finish_gjk(stats: &mut SolverStats, simplex: &Simplex2d, direction: Vec2, converged: bool) {
+    stats.simplex_size = simplex.0.points.len();
+    stats.direction = direction;
+    stats.converged = converged;
+}
+
+fn not_converged(iterations: usize) -> QueryResult2d {
+    QueryResult2d::Unknown {
+        reason: UnknownReason::NotConverged { iterations },
+    }
 }
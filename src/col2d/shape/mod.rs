@@ -4,27 +4,156 @@ mod ball2d;
 mod box2d;
 #[cfg(any())]
 mod ellipse2d;
+mod half_space2d;
+#[cfg(feature = "std")]
+mod heightfield2d;
 pub mod local_minkowski_diff;
 // mod minkowski_diff;
+mod pairs;
 mod point2d;
 #[cfg(all(feature = "poly", feature = "std"))]
 mod poly2d;
 mod rounded_box2d;
+mod sat2d;
 #[cfg(feature = "tilemap")]
 mod tilemap;
 
 pub use box2d::*;
+pub use half_space2d::*;
+#[cfg(feature = "std")]
+pub use heightfield2d::*;
+pub use local_minkowski_diff::{
+    GjkCache, GjkEpaConfig, LocalMinkowskiDiff2d, SolverStats, Tolerance,
+};
 #[cfg(all(feature = "poly", feature = "std"))]
 pub use poly2d::*;
-#[cfg(any())]
 pub use rounded_box2d::*;
+pub use sat2d::*;
 #[cfg(feature = "tilemap")]
 pub use tilemap::*;
 
 /// Macro for creating a shape enum.
+///
+/// The enum supports every query against itself and the built-in shapes, in both orders. Variants
+/// listed after a second `;` are user-defined shapes made queryable with
+/// [convex_shape](crate::convex_shape), they are queried against the enum using GJK/EPA.
+///
+/// Unions of built-in shapes only also implement [MassProperties2d], [PointQuery2d],
+/// [RayCast2d] and [BoundingVolume2d].
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// /// Unit-radius half-disk facing `+y`.
+/// #[derive(Debug, Clone)]
+/// pub struct HalfDisk;
+///
+/// impl ExtremePoint2d for HalfDisk {
+///     fn extreme_point(&self, direction: Vec2) -> Vec2 {
+///         if direction.y > 0.0 {
+///             direction.normalize()
+///         } else {
+///             Vec2::new(direction.x.signum(), 0.0)
+///         }
+///     }
+/// }
+///
+/// impl SymmetricBoundingBox2d for HalfDisk {
+///     fn symmetric_bounding_box(&self) -> Box2d {
+///         Box2d::with_halfdims(1.0, 1.0)
+///     }
+/// }
+///
+/// auburn::convex_shape!(HalfDisk);
+/// auburn::shape_union!(GameShape; Ball, Box2d; HalfDisk);
+///
+/// let a = GameShape::HalfDisk(HalfDisk);
+/// let b = GameShape::Ball(Ball::with_radius(0.5));
+/// assert!(!a.collides_rel(&b, &Vec2::new(0.0, -0.6)));
+/// assert!(a.collides_rel(&b, &Vec2::new(0.0, 0.6)));
+/// assert!(Point.collides_rel(&a, &Vec2::new(0.0, -0.5)));
+/// ```
 #[macro_export]
 macro_rules! shape_union {
     ($name: ident; $($variant:ident),+) => {
+        $crate::shape_union!(@union $name; $($variant),+);
+        $crate::shape_union!(@properties $name; $($variant),+);
+    };
+    ($name: ident; $($variant:ident),+; $($custom:ident),+) => {
+        $crate::shape_union!(@union $name; $($variant),+ $(, $custom)+);
+
+        $(
+            impl $crate::col2d::DefaultMinkowski<$custom> for $name {}
+            impl $crate::col2d::DefaultMinkowski<$name> for $custom {}
+        )+
+    };
+    (@properties $name: ident; $($variant:ident),+) => {
+        impl $crate::col2d::MassProperties2d for $name {
+            fn mass_properties(&self, density: f32) -> $crate::col2d::Mass2d {
+                match self {
+                    $(
+                        Self::$variant(shape) => {
+                            $crate::col2d::MassProperties2d::mass_properties(shape, density)
+                        }
+                    )+
+                }
+            }
+        }
+
+        impl $crate::col2d::PointQuery2d for $name {
+            fn project_point(
+                &self,
+                point: $crate::col2d::Vec2,
+            ) -> $crate::col2d::PointProjection2d {
+                match self {
+                    $(
+                        Self::$variant(shape) => {
+                            $crate::col2d::PointQuery2d::project_point(shape, point)
+                        }
+                    )+
+                }
+            }
+        }
+
+        impl $crate::col2d::RayCast2d for $name {
+            fn cast_ray(
+                &self,
+                ray: &$crate::col2d::Ray2d,
+                max_distance: f32,
+            ) -> Option<$crate::col2d::RayHit2d> {
+                match self {
+                    $(
+                        Self::$variant(shape) => {
+                            $crate::col2d::RayCast2d::cast_ray(shape, ray, max_distance)
+                        }
+                    )+
+                }
+            }
+        }
+
+        impl $crate::col2d::BoundingVolume2d for $name {
+            fn oriented_bounding_box(&self) -> $crate::col2d::Obb2d {
+                match self {
+                    $(
+                        Self::$variant(shape) => {
+                            $crate::col2d::BoundingVolume2d::oriented_bounding_box(shape)
+                        }
+                    )+
+                }
+            }
+
+            fn bounding_circle(&self) -> $crate::col2d::BoundingCircle2d {
+                match self {
+                    $(
+                        Self::$variant(shape) => {
+                            $crate::col2d::BoundingVolume2d::bounding_circle(shape)
+                        }
+                    )+
+                }
+            }
+        }
+    };
+    (@union $name: ident; $($variant:ident),+) => {
         #[derive(Debug, Clone)]
         #[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
         pub enum $name {
@@ -33,21 +162,38 @@ macro_rules! shape_union {
             )+
         }
 
-        impl ExtremePoint2d for $name {
-            fn extreme_point(&self, dir: Vec2) -> Vec2 {
+        impl $crate::col2d::ExtremePoint2d for $name {
+            fn extreme_point(&self, dir: $crate::col2d::Vec2) -> $crate::col2d::Vec2 {
                 match self {
                     $(
-                        Self::$variant(shape) => shape.extreme_point(dir),
+                        Self::$variant(shape) => $crate::col2d::ExtremePoint2d::extreme_point(shape, dir),
                     )+
                 }
             }
         }
 
-        impl<T: Transformation2d> ExtremePointT2d<T> for $name {}
+        impl<T: $crate::col2d::Transformation2d> $crate::col2d::ExtremePointT2d<T> for $name {}
 
-        impl DefaultMinkowski<$name> for $name {}
+        impl $crate::col2d::SymmetricBoundingBox2d for $name {
+            fn symmetric_bounding_box(&self) -> $crate::col2d::Box2d {
+                match self {
+                    $(
+                        Self::$variant(shape) => {
+                            $crate::col2d::SymmetricBoundingBox2d::symmetric_bounding_box(shape)
+                        }
+                    )+
+                }
+            }
+        }
 
-    }
+        $crate::impl_union_pairs!($name [$($variant),+];
+            $crate::col2d::Point,
+            $crate::col2d::Ball,
+            $crate::col2d::Box2d,
+            $crate::col2d::Poly2d,
+            $crate::col2d::RoundedBox2d
+        );
+    };
 }
 
 shape_union!(Shape2d; Ball, Box2d, Poly2d);
@@ -0,0 +1,253 @@
+//! Pair matrix of the built-in shapes.
+//!
+//! Every pair of shapes implements every query trait, either through:
+//! * a specialized implementation next to the shape (e.g. `Ball` v `Ball`),
+//! * `impl_reverse_sdfv_common_rel!` swapping the roles of a specialized pair,
+//! * the Separating Axis Theorem for pairs of [Box2d] and [Poly2d] (see [SatRel2d]),
+//! * [DefaultMinkowski] falling back to GJK/EPA,
+//! * `impl_union_pairs!` dispatching on the variants of a shape union.
+//!
+//! User-defined shapes join the matrix with [convex_shape](crate::convex_shape) and
+//! [shape_union](crate::shape_union).
+
+use super::*;
+
+/// Implements `$a` v `$b` queries by swapping the roles of `$b` v `$a`.
+macro_rules! impl_reverse_sdfv_common_rel {
+    ($a:ty, $b:ty) => {
+        impl<const P: bool, const D: bool, T> SdfvCommonRel2d<P, D, $b, T> for $a
+        where
+            $b: SdfvCommonRel2d<P, D, $a, T>,
+            T: Transformation2d + Invertible,
+        {
+            fn sdfv_common_rel(&self, b: &$b, rel: &T) -> QueryResult2d {
+                let inv_rel = rel.inverse();
+                SdfvCommonRel2d::<P, D, $a, T>::sdfv_common_rel(b, self, &inv_rel)
+                    .reversed()
+                    .map_sdfv(|sdfv| rel.apply_normal(sdfv))
+            }
+
+            fn sdfv_common_rel_with(
+                &self,
+                b: &$b,
+                rel: &T,
+                config: &GjkEpaConfig,
+            ) -> QueryResult2d {
+                let inv_rel = rel.inverse();
+                SdfvCommonRel2d::<P, D, $a, T>::sdfv_common_rel_with(b, self, &inv_rel, config)
+                    .reversed()
+                    .map_sdfv(|sdfv| rel.apply_normal(sdfv))
+            }
+        }
+
+        impl SdfRel2d<$b> for $a {
+            fn sdf_rel(&self, b: &$b, rel: &impl Transformation2d) -> f32 {
+                b.sdf_rel(self, &InverseTransform2d(rel))
+            }
+        }
+    };
+}
+
+/// Routes `$a` v each of `$b` to GJK/EPA.
+macro_rules! impl_default_minkowski {
+    ($a:ty; $($b:ty),+) => {
+        $(
+            impl DefaultMinkowski<$b> for $a {}
+        )+
+    };
+}
+
+/// Implements queries between a shape union and each of `$other` (in both orders) and between
+/// the union and itself by dispatching on the variants.
+///
+/// Each variant must support the queries against each of `$other`. Used by [shape_union](crate::shape_union).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_union_pairs {
+    ($union:ident $variants:tt; $($other:ty),+) => {
+        $(
+            $crate::impl_union_pairs!(@union_v $union $variants $other);
+            $crate::impl_union_pairs!(@v_union $union $variants $other);
+        )+
+        $crate::impl_union_pairs!(@union_v $union $variants $union);
+    };
+    (@union_v $union:ident [$($variant:ident),+] $other:ty) => {
+        impl<const P: bool, const D: bool, T>
+            $crate::col2d::SdfvCommonRel2d<P, D, $other, T> for $union
+        where
+            $($variant: $crate::col2d::SdfvCommonRel2d<P, D, $other, T>,)+
+            T: $crate::col2d::Transformation2d,
+        {
+            fn sdfv_common_rel(&self, b: &$other, rel: &T) -> $crate::col2d::QueryResult2d {
+                match self {
+                    $(
+                        Self::$variant(a) => {
+                            $crate::col2d::SdfvCommonRel2d::<P, D, $other, T>::sdfv_common_rel(
+                                a, b, rel,
+                            )
+                        }
+                    )+
+                }
+            }
+
+            fn sdfv_common_rel_with(
+                &self,
+                b: &$other,
+                rel: &T,
+                config: &$crate::col2d::GjkEpaConfig,
+            ) -> $crate::col2d::QueryResult2d {
+                match self {
+                    $(
+                        Self::$variant(a) => {
+                            $crate::col2d::SdfvCommonRel2d::<P, D, $other, T>
+                                ::sdfv_common_rel_with(a, b, rel, config)
+                        }
+                    )+
+                }
+            }
+        }
+
+        impl $crate::col2d::SdfRel2d<$other> for $union {
+            fn sdf_rel(
+                &self,
+                b: &$other,
+                rel: &impl $crate::col2d::Transformation2d,
+            ) -> f32 {
+                match self {
+                    $(
+                        Self::$variant(a) => $crate::col2d::SdfRel2d::sdf_rel(a, b, rel),
+                    )+
+                }
+            }
+        }
+    };
+    (@v_union $union:ident [$($variant:ident),+] $other:ty) => {
+        impl<const P: bool, const D: bool, T>
+            $crate::col2d::SdfvCommonRel2d<P, D, $union, T> for $other
+        where
+            $($other: $crate::col2d::SdfvCommonRel2d<P, D, $variant, T>,)+
+            T: $crate::col2d::Transformation2d,
+        {
+            fn sdfv_common_rel(&self, b: &$union, rel: &T) -> $crate::col2d::QueryResult2d {
+                match b {
+                    $(
+                        $union::$variant(b) => {
+                            $crate::col2d::SdfvCommonRel2d::<P, D, $variant, T>::sdfv_common_rel(
+                                self, b, rel,
+                            )
+                        }
+                    )+
+                }
+            }
+
+            fn sdfv_common_rel_with(
+                &self,
+                b: &$union,
+                rel: &T,
+                config: &$crate::col2d::GjkEpaConfig,
+            ) -> $crate::col2d::QueryResult2d {
+                match b {
+                    $(
+                        $union::$variant(b) => {
+                            $crate::col2d::SdfvCommonRel2d::<P, D, $variant, T>
+                                ::sdfv_common_rel_with(self, b, rel, config)
+                        }
+                    )+
+                }
+            }
+        }
+
+        impl $crate::col2d::SdfRel2d<$union> for $other {
+            fn sdf_rel(
+                &self,
+                b: &$union,
+                rel: &impl $crate::col2d::Transformation2d,
+            ) -> f32 {
+                match b {
+                    $(
+                        $union::$variant(b) => $crate::col2d::SdfRel2d::sdf_rel(self, b, rel),
+                    )+
+                }
+            }
+        }
+    };
+}
+
+/// Makes a user-defined convex shape queryable against every built-in shape (in both orders),
+/// itself and each of the optional `$other` shapes, using GJK/EPA.
+///
+/// The shape must implement [ExtremePoint2d](crate::col2d::ExtremePoint2d).
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// /// Convex hull of a rounded segment.
+/// #[derive(Debug, Clone)]
+/// pub struct Capsule {
+///     half_length: f32,
+///     radius: f32,
+/// }
+///
+/// impl ExtremePoint2d for Capsule {
+///     fn extreme_point(&self, direction: Vec2) -> Vec2 {
+///         let end = Vec2::new(direction.x.signum() * self.half_length, 0.0);
+///         end + self.radius * direction.normalize_or_zero()
+///     }
+/// }
+///
+/// auburn::convex_shape!(Capsule);
+///
+/// let capsule = Capsule { half_length: 1.0, radius: 0.5 };
+/// let ball = Ball::with_radius(0.5);
+/// assert!(capsule.collides_rel(&ball, &Vec2::new(1.8, 0.0)));
+/// assert!(!ball.collides_rel(&capsule, &Vec2::new(0.0, 1.1)));
+/// ```
+#[macro_export]
+macro_rules! convex_shape {
+    ($shape:ty $(; $($other:ty),+)?) => {
+        $crate::convex_shape!(@pairs $shape;
+            $crate::col2d::Point,
+            $crate::col2d::Ball,
+            $crate::col2d::Box2d,
+            $crate::col2d::Poly2d,
+            $crate::col2d::RoundedBox2d
+            $($(, $other)+)?
+        );
+        impl $crate::col2d::DefaultMinkowski<$shape> for $shape {}
+    };
+    (@pairs $shape:ty; $($other:ty),+) => {
+        $(
+            impl $crate::col2d::DefaultMinkowski<$other> for $shape {}
+            impl $crate::col2d::DefaultMinkowski<$shape> for $other {}
+        )+
+    };
+}
+
+impl_reverse_sdfv_common_rel!(Point, Ball);
+impl_reverse_sdfv_common_rel!(Point, Box2d);
+impl_reverse_sdfv_common_rel!(Point, Poly2d);
+impl_reverse_sdfv_common_rel!(Point, RoundedBox2d);
+
+impl_reverse_sdfv_common_rel!(Point, HalfSpace2d);
+impl_reverse_sdfv_common_rel!(Ball, HalfSpace2d);
+impl_reverse_sdfv_common_rel!(Box2d, HalfSpace2d);
+impl_reverse_sdfv_common_rel!(Poly2d, HalfSpace2d);
+impl_reverse_sdfv_common_rel!(RoundedBox2d, HalfSpace2d);
+impl_reverse_sdfv_common_rel!(Shape2d, HalfSpace2d);
+
+#[cfg(feature = "std")]
+mod heightfield_pairs {
+    use super::*;
+
+    impl_reverse_sdfv_common_rel!(Point, Heightfield2d);
+    impl_reverse_sdfv_common_rel!(Ball, Heightfield2d);
+    impl_reverse_sdfv_common_rel!(Box2d, Heightfield2d);
+    impl_reverse_sdfv_common_rel!(Poly2d, Heightfield2d);
+    impl_reverse_sdfv_common_rel!(RoundedBox2d, Heightfield2d);
+    impl_reverse_sdfv_common_rel!(Shape2d, Heightfield2d);
+}
+
+impl_default_minkowski!(Ball; Box2d, Poly2d, RoundedBox2d);
+impl_default_minkowski!(Box2d; RoundedBox2d);
+impl_default_minkowski!(Poly2d; Point, Ball, RoundedBox2d);
+impl_default_minkowski!(RoundedBox2d; Ball, Box2d, Poly2d, RoundedBox2d);
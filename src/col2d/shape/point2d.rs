@@ -6,6 +6,32 @@ impl SymmetricBoundingBox2d for Point {
     }
 }
 
+impl MassProperties2d for Point {
+    fn mass_properties(&self, _density: f32) -> Mass2d {
+        Mass2d::ZERO
+    }
+}
+
+impl BoundingVolume2d for Point {
+    fn oriented_bounding_box(&self) -> Obb2d {
+        Obb2d::default()
+    }
+
+    fn bounding_circle(&self) -> BoundingCircle2d {
+        BoundingCircle2d::default()
+    }
+}
+
+impl PointQuery2d for Point {
+    fn project_point(&self, point: Vec2) -> PointProjection2d {
+        PointProjection2d {
+            point: Vec2::ZERO,
+            is_inside: point == Vec2::ZERO,
+            feature: Feature2d::Vertex(0),
+        }
+    }
+}
+
 impl ExtremePoint2d for Point {
     fn extreme_point(&self, _: Vec2) -> Vec2 {
         Vec2::ZERO
@@ -26,39 +52,18 @@ impl SdfRel2d<Point> for Point {
 }
 
 impl<const P: bool, const D: bool, T: Transformation2d> SdfvCommonRel2d<P, D, Point, T> for Point {
-    fn sdfv_common_rel(&self, _t: &Point, rel: &T) -> (bool, Vec2) {
+    fn sdfv_common_rel(&self, _t: &Point, rel: &T) -> QueryResult2d {
         let a = rel.apply_origin();
         let collides = a.x == 0.0 && a.y == 0.0;
-        (collides, a)
+        QueryResult2d::from_sdfv(collides, a)
     }
 }
 
-macro_rules! impl_reverse_sdfv_common_rel {
-    ($a:ty, $b:ty) => {
-        impl<const P: bool, const D: bool, T> SdfvCommonRel2d<P, D, $b, T> for $a
-        where
-            $b: SdfvCommonRel2d<P, D, $a, T>,
-            T: Transformation2d + Invertible,
-        {
-            fn sdfv_common_rel(&self, b: &$b, rel: &T) -> (bool, Vec2) {
-                let inv_rel = rel.inverse();
-                let (collides, sdfv) =
-                    SdfvCommonRel2d::<P, D, $a, T>::sdfv_common_rel(b, self, &inv_rel);
-                let negated_sdfv = -sdfv;
-                let transformed_sdfv = rel.apply_normal(negated_sdfv);
-                (collides, transformed_sdfv)
-            }
-        }
-    };
-}
-
-impl_reverse_sdfv_common_rel!(Point, Box2d);
-impl_reverse_sdfv_common_rel!(Point, Ball);
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[allow(dead_code)]
     fn default_minkowski_check<A, B>()
     where
         A: DefaultMinkowski<B>,
@@ -24,6 +24,81 @@ impl SymmetricBoundingBox2d for RoundedBox2d {
     }
 }
 
+impl MassProperties2d for RoundedBox2d {
+    /// Sum of the box part, four sides of thickness `radius` and four quarter-circle corners.
+    fn mass_properties(&self, density: f32) -> Mass2d {
+        let Vec2 { x, y } = self.halfsize;
+        let r = self.radius;
+        let sides = [
+            (
+                Box2d::with_halfdims(x, 0.5 * r),
+                Vec2::new(0.0, y + 0.5 * r),
+            ),
+            (
+                Box2d::with_halfdims(0.5 * r, y),
+                Vec2::new(x + 0.5 * r, 0.0),
+            ),
+        ];
+
+        // Inertia of a quarter circle about its own centroid.
+        let quarter = Ball::with_radius(r).mass_properties(0.25 * density);
+        let quarter_offset = 4.0 * r / (3.0 * core::f32::consts::PI);
+        let quarter_center = Vec2::new(x + quarter_offset, y + quarter_offset);
+        let quarter_inertia =
+            quarter.inertia - quarter.mass * quarter_offset * quarter_offset * 2.0;
+        let corners = [
+            Vec2::new(1.0, 1.0),
+            Vec2::new(-1.0, 1.0),
+            Vec2::new(-1.0, -1.0),
+            Vec2::new(1.0, -1.0),
+        ]
+        .map(|sign| Mass2d::new(quarter.mass, sign * quarter_center, quarter_inertia));
+
+        core::iter::once(self.box_part().mass_properties(density))
+            .chain(sides.into_iter().flat_map(|(side, offset)| {
+                let mass = side.mass_properties(density);
+                [mass.transformed(&offset), mass.transformed(&-offset)]
+            }))
+            .chain(corners)
+            .sum()
+    }
+}
+
+impl BoundingVolume2d for RoundedBox2d {
+    fn oriented_bounding_box(&self) -> Obb2d {
+        Obb2d::new(Vec2::ZERO, self.halfsize + self.radius, 0.0)
+    }
+
+    fn bounding_circle(&self) -> BoundingCircle2d {
+        BoundingCircle2d::new(Vec2::ZERO, self.halfsize.length() + self.radius)
+    }
+}
+
+/// Features are those of [RoundedBox2d::box_part], vertices stand for the rounded corners.
+impl PointQuery2d for RoundedBox2d {
+    fn project_point(&self, point: Vec2) -> PointProjection2d {
+        let projection = self.box_part().project_point(point);
+        let (normal, is_inside) = if projection.is_inside {
+            let normal = match projection.feature {
+                Feature2d::Edge(0) => Vec2::NEG_Y,
+                Feature2d::Edge(1) => Vec2::X,
+                Feature2d::Edge(2) => Vec2::Y,
+                _ => Vec2::NEG_X,
+            };
+            (normal, true)
+        } else {
+            let delta = point - projection.point;
+            let distance = delta.length();
+            (delta / distance, distance <= self.radius)
+        };
+        PointProjection2d {
+            point: projection.point + self.radius * normal,
+            is_inside,
+            ..projection
+        }
+    }
+}
+
 impl ExtremePoint2d for RoundedBox2d {
     fn extreme_point(&self, direction: Vec2) -> Vec2 {
         let a = Ball::new(self.radius);
@@ -31,3 +106,5 @@ impl ExtremePoint2d for RoundedBox2d {
         a.extreme_point(direction) + b.extreme_point(direction)
     }
 }
+
+impl<T: Transformation2d> ExtremePointT2d<T> for RoundedBox2d {}
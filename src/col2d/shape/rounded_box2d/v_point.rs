@@ -3,12 +3,12 @@ use super::*;
 impl<const COMPUTE_PENETRATION: bool, const COMPUTE_DISTANCE: bool, T: Transformation2d>
     SdfvCommonRel2d<COMPUTE_PENETRATION, COMPUTE_DISTANCE, Point, T> for RoundedBox2d
 {
-    fn sdfv_common_rel(&self, _: &Point, rel: &T) -> (bool, Vec2) {
+    fn sdfv_common_rel(&self, _: &Point, rel: &T) -> QueryResult2d {
         let delta = rel.apply_origin();
         let delta_x = delta.x.abs() - self.halfsize.x;
         let delta_y = delta.y.abs() - self.halfsize.y;
 
-        if self.box_part().collides_rel(&Point, rel) {
+        let (collides, sdfv) = if self.box_part().collides_rel(&Point, rel) {
             if delta_x > delta_y {
                 (
                     true,
@@ -37,7 +37,9 @@ impl<const COMPUTE_PENETRATION: bool, const COMPUTE_DISTANCE: bool, T: Transform
                 let corner = corner * (corner_length - self.radius) / corner_length;
                 (corner_length < self.radius, corner)
             }
-        }
+        };
+
+        QueryResult2d::from_sdfv(collides, sdfv)
     }
 }
 
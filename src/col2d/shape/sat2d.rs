@@ -0,0 +1,440 @@
+//! Separating Axis Theorem for pairs of convex polygons.
+//!
+//! Faster than GJK/EPA for polygons with few vertices and the penetration normal is exactly the
+//! normal of the reference face.
+
+use crate::warn;
+
+use super::*;
+use local_minkowski_diff::LocalMinkowskiDiff2d;
+
+/// Face of one of two convex polygons along which they are separated the most, i.e. penetrate
+/// the least.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReferenceFace2d {
+    /// Whether the face belongs to `b` rather than `a`.
+    pub on_b: bool,
+    /// Index of the face, numbered like [Feature2d::Edge] of its shape.
+    pub edge: usize,
+    /// Outward unit normal of the face in `a`-centric space.
+    pub normal: Vec2,
+    /// Signed distance of the other polygon from the face, negative when penetrating.
+    pub separation: f32,
+}
+
+/// Trait for finding the reference face of two convex polygons.
+///
+/// [SdfvCommonRel2d] between the polygons uses the same face, the penetration normal of
+/// penetrating polygons is the normal of the reference face (negated if the face belongs to `b`).
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// let a = Box2d::with_halfdims(2.0, 1.0);
+/// let b = Box2d::with_halfdims(1.0, 1.0);
+/// let rel = Transform2d::from_translation(Vec2::new(0.5, 2.0)).with_angle(0.3);
+/// let face = a.reference_face_rel(&b, &rel).unwrap();
+/// assert_eq!((face.on_b, face.edge, face.normal), (false, 2, Vec2::Y));
+/// assert!(face.separation < 0.0);
+/// assert_eq!(a.penetrates_rel(&b, &rel), Some(face.separation * face.normal));
+/// ```
+pub trait SatRel2d<B> {
+    /// Finds the reference face between `self` and `b` transformed by `rel`, `None` if either
+    /// polygon is degenerate.
+    fn reference_face_rel(&self, b: &B, rel: &impl Transformation2d) -> Option<ReferenceFace2d>;
+}
+
+/// Convex polygon with explicit faces.
+pub(crate) trait SatPolygon2d {
+    type Vertices: AsRef<[(usize, Vec2)]>;
+
+    /// Vertices mapped by `map` along with their indices, in counter-clockwise order before the
+    /// mapping. Face `i` starts at vertex `i`.
+    fn sat_vertices(&self, map: impl Fn(Vec2) -> Vec2) -> Self::Vertices;
+}
+
+impl SatPolygon2d for Box2d {
+    type Vertices = [(usize, Vec2); 4];
+
+    fn sat_vertices(&self, map: impl Fn(Vec2) -> Vec2) -> Self::Vertices {
+        let Vec2 { x, y } = self.halfsize;
+        [
+            (0, map(Vec2::new(-x, -y))),
+            (1, map(Vec2::new(x, -y))),
+            (2, map(Vec2::new(x, y))),
+            (3, map(Vec2::new(-x, y))),
+        ]
+    }
+}
+
+/// Vertices of a [Poly2d], small polygons are stored inline.
+#[cfg(all(feature = "poly", feature = "std"))]
+pub(crate) enum PolyVertices {
+    Inline {
+        len: usize,
+        vertices: [(usize, Vec2); PolyVertices::INLINE],
+    },
+    Heap(Vec<(usize, Vec2)>),
+}
+
+#[cfg(all(feature = "poly", feature = "std"))]
+impl PolyVertices {
+    const INLINE: usize = 8;
+}
+
+#[cfg(all(feature = "poly", feature = "std"))]
+impl AsRef<[(usize, Vec2)]> for PolyVertices {
+    fn as_ref(&self) -> &[(usize, Vec2)] {
+        match self {
+            Self::Inline { len, vertices } => &vertices[..*len],
+            Self::Heap(vertices) => vertices,
+        }
+    }
+}
+
+#[cfg(all(feature = "poly", feature = "std"))]
+impl SatPolygon2d for Poly2d {
+    type Vertices = PolyVertices;
+
+    fn sat_vertices(&self, map: impl Fn(Vec2) -> Vec2) -> Self::Vertices {
+        let vertex = |i: usize| (i, map(self.points[i]));
+        // Sorting by angle in `outline` and allocating dominate the query for small polygons,
+        // points given in counter-clockwise order can be used as they are.
+        if !is_convex_ccw(&self.points) {
+            return PolyVertices::Heap(self.outline().into_iter().map(vertex).collect());
+        }
+        let len = self.points.len();
+        if len > PolyVertices::INLINE {
+            return PolyVertices::Heap((0..len).map(vertex).collect());
+        }
+        let mut vertices = [(0, Vec2::ZERO); PolyVertices::INLINE];
+        for (i, v) in vertices[..len].iter_mut().enumerate() {
+            *v = vertex(i);
+        }
+        PolyVertices::Inline { len, vertices }
+    }
+}
+
+/// Whether `points` form a strictly convex counter-clockwise loop.
+#[cfg(all(feature = "poly", feature = "std"))]
+fn is_convex_ccw(points: &[Vec2]) -> bool {
+    let n = points.len();
+    let edge = |i: usize| points[(i + 1) % n] - points[i];
+    // Left turns alone allow loops winding around more than once (e.g. a pentagram), a simple loop
+    // changes horizontal direction at most twice.
+    let mut direction_changes = 0;
+    for i in 0..n {
+        let (e, f) = (edge(i), edge((i + 1) % n));
+        if e.perp_dot(f) <= 0.0 {
+            return false;
+        }
+        if e.x * f.x < 0.0 {
+            direction_changes += 1;
+        }
+    }
+    n >= 3 && direction_changes <= 2
+}
+
+impl ReferenceFace2d {
+    /// Normal pointing from `a` towards `b`.
+    fn normal_towards_b(&self) -> Vec2 {
+        if self.on_b {
+            -self.normal
+        } else {
+            self.normal
+        }
+    }
+
+    /// Whether `self` is a better reference face than `other`.
+    ///
+    /// Ties in separation go to the face facing `towards_b`, then to the more horizontal one.
+    #[inline]
+    fn is_better_than(&self, other: &Self, towards_b: Vec2) -> bool {
+        if self.separation != other.separation {
+            return self.separation > other.separation;
+        }
+        self.wins_tie(other, towards_b)
+    }
+
+    #[cold]
+    fn wins_tie(&self, other: &Self, towards_b: Vec2) -> bool {
+        // Boxes stacked exactly on top of each other tie on every face, the order of the tie-breaks
+        // matches what EPA reported before SAT was added.
+        let key = |face: &Self| {
+            let normal = face.normal_towards_b();
+            [normal.dot(towards_b), normal.x.abs(), normal.x]
+        };
+        key(self)
+            .into_iter()
+            .zip(key(other))
+            .find(|(a, b)| a != b)
+            .is_some_and(|(a, b)| a > b)
+    }
+}
+
+/// Face of `reference` with the largest separation from `incident`, both in `a`-centric space.
+///
+/// `orientation` is `-1.0` if `reference` was reflected and its winding is clockwise.
+#[inline]
+fn max_separation(
+    on_b: bool,
+    reference: &[(usize, Vec2)],
+    orientation: f32,
+    incident: &[(usize, Vec2)],
+    towards_b: Vec2,
+    stop_if_separated: bool,
+) -> Option<ReferenceFace2d> {
+    let mut best: Option<ReferenceFace2d> = None;
+    let mut previous = *reference.last()?;
+    for &current in reference {
+        let ((edge, start), (_, end)) = (previous, current);
+        previous = current;
+        let outward = -orientation * (end - start).perp();
+        let length = outward.length();
+        if length == 0.0 {
+            continue;
+        }
+        let separation = incident
+            .iter()
+            .map(|&(_, p)| outward.dot(p - start))
+            .fold(f32::INFINITY, f32::min)
+            / length;
+        let normal = outward / length;
+        let face = ReferenceFace2d {
+            on_b,
+            edge,
+            normal,
+            separation,
+        };
+        if best.is_none_or(|best| face.is_better_than(&best, towards_b)) {
+            best = Some(face);
+            if stop_if_separated && separation > 0.0 {
+                break;
+            }
+        }
+    }
+    best
+}
+
+/// Reference face of `a` and `b`, stopping at the first separating face if `stop_if_separated`.
+#[inline]
+fn reference_face<A, B>(
+    a: &A,
+    b: &B,
+    rel: &impl Transformation2d,
+    stop_if_separated: bool,
+) -> Option<ReferenceFace2d>
+where
+    A: SatPolygon2d,
+    B: SatPolygon2d,
+{
+    let a_vertices = a.sat_vertices(|p| p);
+    let b_vertices = b.sat_vertices(|p| rel.apply(p));
+    let (a_vertices, b_vertices) = (a_vertices.as_ref(), b_vertices.as_ref());
+
+    let towards_b = rel.apply_origin();
+    let on_a = max_separation(
+        false,
+        a_vertices,
+        1.0,
+        b_vertices,
+        towards_b,
+        stop_if_separated,
+    )?;
+    if stop_if_separated && on_a.separation > 0.0 {
+        return Some(on_a);
+    }
+
+    let orientation = (rel.apply(Vec2::X) - towards_b)
+        .perp_dot(rel.apply(Vec2::Y) - towards_b)
+        .signum();
+    let on_b = max_separation(
+        true,
+        b_vertices,
+        orientation,
+        a_vertices,
+        towards_b,
+        stop_if_separated,
+    )?;
+    // Full ties go to `a` so that resting contacts keep a stable reference face.
+    Some(if on_b.is_better_than(&on_a, towards_b) {
+        on_b
+    } else {
+        on_a
+    })
+}
+
+/// [SdfvCommonRel2d] of two convex polygons using SAT.
+///
+/// `config` is only needed, and [GjkEpaConfig::global] only read, for separated shapes.
+fn sdfv_sat<const P: bool, const D: bool, A, B, T>(
+    a: &A,
+    b: &B,
+    rel: &T,
+    config: Option<&GjkEpaConfig>,
+) -> QueryResult2d
+where
+    A: SatPolygon2d + ExtremePoint2d,
+    B: SatPolygon2d + ExtremePoint2d,
+    T: Transformation2d,
+    for<'x> LocalMinkowskiDiff2d<'x, A, B, T>: SdfvMinkowski2d<P, D>,
+{
+    let Some(face) = reference_face(a, b, rel, !P && !D) else {
+        return QueryResult2d::Unknown {
+            reason: UnknownReason::Degenerate,
+        };
+    };
+    if face.separation >= 0.0 {
+        if D {
+            // The largest separation along the face normals is only a lower bound of the distance
+            // when the closest features are two vertices, GJK finds the exact one.
+            let config = config.copied().unwrap_or_else(GjkEpaConfig::global);
+            let diff = LocalMinkowskiDiff2d::new(a, b, rel, config);
+            return SdfvMinkowski2d::<P, D>::sdfv_minkowski(&diff);
+        }
+        return QueryResult2d::separated_unmeasured();
+    }
+    if !P {
+        return QueryResult2d::penetrating_unmeasured();
+    }
+    QueryResult2d::penetrating(face.separation * face.normal_towards_b())
+}
+
+/// Routes `$a` v `$b` to SAT instead of GJK/EPA.
+macro_rules! impl_sat_pair {
+    ($a:ty, $b:ty) => {
+        impl<const P: bool, const D: bool, T> SdfvCommonRel2d<P, D, $b, T> for $a
+        where
+            T: Transformation2d,
+            for<'x> LocalMinkowskiDiff2d<'x, $a, $b, T>: SdfvMinkowski2d<P, D>,
+        {
+            fn sdfv_common_rel(&self, b: &$b, rel: &T) -> QueryResult2d {
+                sdfv_sat::<P, D, _, _, _>(self, b, rel, None)
+            }
+
+            fn sdfv_common_rel_with(
+                &self,
+                b: &$b,
+                rel: &T,
+                config: &GjkEpaConfig,
+            ) -> QueryResult2d {
+                sdfv_sat::<P, D, _, _, _>(self, b, rel, Some(config))
+            }
+        }
+
+        impl SdfRel2d<$b> for $a {
+            fn sdf_rel(&self, b: &$b, rel: &impl Transformation2d) -> f32 {
+                let result = sdfv_sat::<true, true, _, _, _>(self, b, rel, None);
+                result.sdf().unwrap_or_else(|| {
+                    warn!("query result is unknown: {:?}", result);
+                    f32::NAN
+                })
+            }
+        }
+
+        impl SatRel2d<$b> for $a {
+            fn reference_face_rel(
+                &self,
+                b: &$b,
+                rel: &impl Transformation2d,
+            ) -> Option<ReferenceFace2d> {
+                reference_face(self, b, rel, false)
+            }
+        }
+    };
+}
+
+impl_sat_pair!(Box2d, Box2d);
+
+#[cfg(all(feature = "poly", feature = "std"))]
+mod poly_pairs {
+    use super::*;
+
+    impl_sat_pair!(Box2d, Poly2d);
+    impl_sat_pair!(Poly2d, Box2d);
+    impl_sat_pair!(Poly2d, Poly2d);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks SAT against GJK/EPA.
+    fn assert_matches_gjk<A, B>(a: &A, b: &B, rel: &Transform2d)
+    where
+        A: SdfvCommonRel2d<true, true, B, Transform2d> + ExtremePoint2d,
+        B: ExtremePoint2d,
+    {
+        let sat = SdfvCommonRel2d::<true, true, _, _>::sdfv_common_rel(a, b, rel);
+        let diff = LocalMinkowskiDiff2d::raw(a, b, rel);
+        let gjk = SdfvMinkowski2d::<true, true>::sdfv_minkowski(&diff);
+        assert_eq!(sat.collides(), gjk.collides(), "{sat:?} v {gjk:?}");
+        let (sat, gjk) = (sat.sdfv().unwrap(), gjk.sdfv().unwrap());
+        assert!(sat.abs_diff_eq(gjk, 1e-3), "{sat} v {gjk}");
+    }
+
+    #[test_log::test]
+    fn box_v_box_matches_gjk() {
+        let a = Box2d::with_halfdims(2.0, 1.0);
+        let b = Box2d::with_halfdims(0.5, 1.5);
+        for i in 0..64 {
+            let angle = i as f32 * 0.37;
+            let offset = Vec2::new((i as f32 * 0.9).sin() * 3.0, (i as f32 * 1.3).cos() * 2.5);
+            let rel = Transform2d::from_translation(offset).with_angle(angle);
+            assert_matches_gjk(&a, &b, &rel);
+        }
+    }
+
+    #[test_log::test]
+    fn poly_v_box_matches_gjk() {
+        let a = Poly2d::regular(5, 1.5);
+        let b = Box2d::with_halfdims(1.0, 0.25);
+        for i in 0..64 {
+            let angle = i as f32 * 0.61;
+            let offset = Vec2::new((i as f32 * 0.7).cos() * 2.5, (i as f32 * 1.1).sin() * 2.5);
+            let rel = Transform2d::from_translation(offset).with_angle(angle);
+            assert_matches_gjk(&a, &b, &rel);
+            assert_matches_gjk(&b, &a, &rel);
+        }
+    }
+
+    #[test_log::test]
+    fn reference_face_on_b() {
+        let a = Box2d::with_halfdims(1.0, 1.0);
+        let b = Box2d::with_halfdims(4.0, 0.5);
+        // In `b`'s space, `a` is rotated by 45 degrees and its corner sinks 0.2 into the top face.
+        let depth = 0.2;
+        let height = 0.5 + 2f32.sqrt() - depth;
+        let rel = Transform2d::from_translation(height * Vec2::new(1.0, -1.0).normalize())
+            .with_angle(core::f32::consts::FRAC_PI_4);
+        let face = a.reference_face_rel(&b, &rel).unwrap();
+        assert!(face.on_b);
+        assert_eq!(face.edge, 2);
+        let up = Vec2::new(-1.0, 1.0).normalize();
+        assert!(face.normal.abs_diff_eq(up, 1e-5));
+        assert!((face.separation + depth).abs() < 1e-5);
+        let penetration = a.penetrates_rel(&b, &rel).unwrap();
+        assert!(penetration.abs_diff_eq(depth * up, 1e-5));
+    }
+
+    #[test_log::test]
+    fn mirrored() {
+        let a = Box2d::with_halfdims(1.0, 1.0);
+        let b = Poly2d::new(&[
+            Vec2::new(-1.0, -1.0),
+            Vec2::new(2.0, -1.0),
+            Vec2::new(-1.0, 1.0),
+        ]);
+        let rel =
+            Transform2d::from_translation(Vec2::new(1.5, 0.0)).with_scale(Vec2::new(-1.0, 1.0));
+        // Mirrored triangle is (2.5, -1), (-0.5, -1), (2.5, 1), the hypotenuse cuts off the
+        // bottom-right corner of `a`.
+        let face = a.reference_face_rel(&b, &rel).unwrap();
+        let normal = Vec2::new(-2.0, 3.0) / 13f32.sqrt();
+        assert_eq!((face.on_b, face.edge), (true, 1));
+        assert!(face.normal.abs_diff_eq(normal, 1e-5));
+        assert!((face.separation + 3.0 / 13f32.sqrt()).abs() < 1e-5);
+        let penetration = a.penetrates_rel(&b, &rel).unwrap();
+        assert!(penetration.abs_diff_eq(-face.separation * normal, 1e-5));
+    }
+}
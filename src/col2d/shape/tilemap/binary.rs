@@ -0,0 +1,496 @@
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use lk_math::vector::V2i32;
+
+use super::{Chunk, Tilemap, TilemapEvent, Vec2};
+use crate::utils::publisher::Publisher;
+
+const TILEMAP_MAGIC: [u8; 4] = *b"AUTM";
+const CHUNK_MAGIC: [u8; 4] = *b"AUCH";
+/// Version of the binary format, bumped on incompatible changes.
+const VERSION: u16 = 1;
+/// Largest chunk width and height accepted when reading, so that a corrupted size can't make a
+/// chunk allocate gigabytes.
+const MAX_CHUNK_SIDE: i32 = 1024;
+
+/// How the tiles of a chunk are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+enum ChunkEncoding {
+    /// One byte per tile.
+    Raw = 0,
+    /// Pairs of run length and tile.
+    RunLength = 1,
+    /// Up to 16 distinct tiles followed by tightly packed indices into them.
+    Palette = 2,
+}
+
+/// Compact binary format, versioned and little-endian.
+///
+/// A tilemap is a header with [Tilemap::tile_size], [Tilemap::origin] and
+/// [Tilemap::chunk_size] followed by its chunks. Each chunk is stored on its own, so chunks can
+/// also be streamed in and out of separate files with [Tilemap::write_chunk] and
+/// [Tilemap::read_chunk]. Chunk tiles are stored run-length or palette encoded, whichever is
+/// smaller.
+///
+/// Tile shapes and events are not stored. Reading fails with [io::ErrorKind::InvalidData] on
+/// corrupted data, including chunks larger than 1024 tiles along either axis.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// # use lk_math::vector::V2i32;
+/// let mut tilemap = Tilemap::default();
+/// for x in 0..100 {
+///     tilemap.set_tile(V2i32::from_xy(x, 0), 1);
+/// }
+/// let mut bytes = Vec::new();
+/// tilemap.write_binary(&mut bytes).unwrap();
+/// assert!(bytes.len() < 300);
+///
+/// let loaded = Tilemap::read_binary(bytes.as_slice()).unwrap();
+/// assert_eq!(loaded.get_tile(V2i32::from_xy(42, 0)), 1);
+/// ```
+impl Tilemap {
+    pub fn write_binary<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&TILEMAP_MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        write_vec2(&mut writer, self.tile_size)?;
+        write_vec2(&mut writer, self.origin)?;
+        write_v2i32(&mut writer, self.chunk_size)?;
+        writer.write_all(&(self.chunks.len() as u32).to_le_bytes())?;
+        for (chunk_pos, chunk) in &self.chunks {
+            write_chunk_record(&mut writer, *chunk_pos, chunk)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a tilemap written by [Tilemap::write_binary], with the default tile shapes.
+    pub fn read_binary<R: Read>(mut reader: R) -> io::Result<Self> {
+        read_header(&mut reader, TILEMAP_MAGIC)?;
+        let tile_size = read_vec2(&mut reader)?;
+        let origin = read_vec2(&mut reader)?;
+        let chunk_size = read_v2i32(&mut reader)?;
+        let valid_tile_size = tile_size.is_finite() && tile_size.x > 0.0 && tile_size.y > 0.0;
+        if !valid_tile_size || !origin.is_finite() {
+            return Err(invalid_data("invalid tilemap dimensions"));
+        }
+        tile_count(chunk_size)?;
+        let mut tilemap = Tilemap::default()
+            .with_tile_size(tile_size)
+            .with_origin(origin)
+            .with_chunk_size(chunk_size);
+        let count = read_u32(&mut reader)?;
+        for _ in 0..count {
+            let (chunk_pos, chunk) = read_chunk_record(&mut reader, chunk_size)?;
+            tilemap.chunks.insert(chunk_pos, chunk);
+        }
+        Ok(tilemap)
+    }
+
+    /// Writes a single chunk, see [Tilemap::read_chunk].
+    ///
+    /// Fails with [io::ErrorKind::NotFound] if the chunk is not allocated.
+    pub fn write_chunk<W: Write>(&self, chunk_pos: &V2i32, mut writer: W) -> io::Result<()> {
+        let chunk = self
+            .chunks
+            .get(chunk_pos)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "chunk is not allocated"))?;
+        write_chunk_record(&mut writer, *chunk_pos, chunk)
+    }
+
+    /// Reads a chunk written by [Tilemap::write_chunk] into the tilemap, replacing the chunk at
+    /// the same position. Returns the position of the chunk.
+    ///
+    /// Fails with [io::ErrorKind::InvalidData] if the chunk size differs from
+    /// [Tilemap::chunk_size].
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// # use lk_math::vector::V2i32;
+    /// let mut tilemap = Tilemap::default();
+    /// tilemap.set_tile(V2i32::from_xy(40, 3), 7);
+    /// let chunk_pos = tilemap.tile_to_chunk_pos(&V2i32::from_xy(40, 3));
+    ///
+    /// let mut file = Vec::new();
+    /// tilemap.write_chunk(&chunk_pos, &mut file).unwrap();
+    /// tilemap.unload_chunk(&chunk_pos);
+    /// assert_eq!(tilemap.get_tile(V2i32::from_xy(40, 3)), 0);
+    ///
+    /// assert_eq!(tilemap.read_chunk(file.as_slice()).unwrap(), chunk_pos);
+    /// assert_eq!(tilemap.get_tile(V2i32::from_xy(40, 3)), 7);
+    /// ```
+    pub fn read_chunk<R: Read>(&mut self, mut reader: R) -> io::Result<V2i32> {
+        let (chunk_pos, chunk) = read_chunk_record(&mut reader, self.chunk_size)?;
+        let event = match self.chunks.insert(chunk_pos, chunk) {
+            Some(_) => TilemapEvent::ChunkChanged(chunk_pos),
+            None => TilemapEvent::ChunkCreated(chunk_pos),
+        };
+        self.events.notify(event);
+        Ok(chunk_pos)
+    }
+
+    /// Removes a chunk from memory, e.g. after writing it with [Tilemap::write_chunk].
+    pub fn unload_chunk(&mut self, chunk_pos: &V2i32) -> Option<Chunk> {
+        let chunk = self.chunks.remove(chunk_pos)?;
+        self.events.notify(TilemapEvent::ChunkRemoved(*chunk_pos));
+        Some(chunk)
+    }
+
+    /// Positions of the allocated chunks.
+    pub fn chunk_positions(&self) -> impl Iterator<Item = V2i32> + '_ {
+        self.chunks.keys().copied()
+    }
+
+    pub fn save_binary(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(std::fs::File::create(path)?);
+        self.write_binary(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn load_binary(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read_binary(BufReader::new(std::fs::File::open(path)?))
+    }
+}
+
+fn write_chunk_record(writer: &mut impl Write, chunk_pos: V2i32, chunk: &Chunk) -> io::Result<()> {
+    writer.write_all(&CHUNK_MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    write_v2i32(writer, chunk_pos)?;
+    let (width, height) = (chunk.array2d.width(), chunk.array2d.height());
+    write_v2i32(writer, V2i32::from_xy(width as i32, height as i32))?;
+
+    let tiles = &chunk.array2d.data;
+    let encodings = [
+        Some((ChunkEncoding::Raw, tiles.clone())),
+        Some((ChunkEncoding::RunLength, encode_run_length(tiles))),
+        encode_palette(tiles).map(|palette| (ChunkEncoding::Palette, palette)),
+    ];
+    let (encoding, payload) = encodings
+        .into_iter()
+        .flatten()
+        .min_by_key(|(_, payload)| payload.len())
+        .unwrap();
+    writer.write_all(&[encoding as u8])?;
+    writer.write_all(&(payload.len() as u32).to_le_bytes())?;
+    writer.write_all(&payload)
+}
+
+fn read_chunk_record(reader: &mut impl Read, chunk_size: V2i32) -> io::Result<(V2i32, Chunk)> {
+    read_header(reader, CHUNK_MAGIC)?;
+    let chunk_pos = read_v2i32(reader)?;
+    if read_v2i32(reader)? != chunk_size {
+        return Err(invalid_data("chunk size does not match the tilemap"));
+    }
+    let count = tile_count(chunk_size)?;
+    let encoding = read_u8(reader)?;
+    let length = read_u32(reader)? as usize;
+    // The payload is never larger than raw tiles, this guards the allocation against corrupted
+    // lengths.
+    if length > count {
+        return Err(invalid_data("chunk payload is too large"));
+    }
+    let mut payload = vec![0; length];
+    reader.read_exact(&mut payload)?;
+    let tiles = match encoding {
+        e if e == ChunkEncoding::Raw as u8 => Some(payload),
+        e if e == ChunkEncoding::RunLength as u8 => decode_run_length(&payload, count),
+        e if e == ChunkEncoding::Palette as u8 => decode_palette(&payload, count),
+        _ => return Err(invalid_data("unknown chunk encoding")),
+    };
+    let tiles = tiles
+        .filter(|tiles| tiles.len() == count)
+        .ok_or_else(|| invalid_data("corrupted chunk"))?;
+    let mut chunk = Chunk::with_size(chunk_size);
+    chunk.array2d.data = tiles;
+    Ok((chunk_pos, chunk))
+}
+
+/// Runs of at most 255 equal tiles, each stored as its length and the tile.
+fn encode_run_length(tiles: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    for run in tiles.chunk_by(|a, b| a == b) {
+        for part in run.chunks(u8::MAX as usize) {
+            encoded.extend([part.len() as u8, part[0]]);
+        }
+    }
+    encoded
+}
+
+fn decode_run_length(encoded: &[u8], count: usize) -> Option<Vec<u8>> {
+    if !encoded.len().is_multiple_of(2) {
+        return None;
+    }
+    let mut tiles = Vec::with_capacity(count);
+    for pair in encoded.chunks(2) {
+        if tiles.len() + pair[0] as usize > count {
+            return None;
+        }
+        tiles.extend(core::iter::repeat_n(pair[1], pair[0] as usize));
+    }
+    Some(tiles)
+}
+
+/// Number of tiles in a chunk of `chunk_size` read from a file, see [MAX_CHUNK_SIDE].
+fn tile_count(chunk_size: V2i32) -> io::Result<usize> {
+    let valid = |side: i32| (1..=MAX_CHUNK_SIDE).contains(&side);
+    if !valid(chunk_size.x()) || !valid(chunk_size.y()) {
+        return Err(invalid_data("invalid chunk size"));
+    }
+    chunk_size
+        .x()
+        .checked_mul(chunk_size.y())
+        .map(|count| count as usize)
+        .ok_or_else(|| invalid_data("invalid chunk size"))
+}
+
+/// Number of distinct tiles followed by the tiles and their indices packed into 1, 2 or 4 bits,
+/// `None` if there are more than 16 distinct tiles.
+fn encode_palette(tiles: &[u8]) -> Option<Vec<u8>> {
+    let mut palette: Vec<u8> = Vec::new();
+    for &tile in tiles {
+        if !palette.contains(&tile) {
+            if palette.len() == 16 {
+                return None;
+            }
+            palette.push(tile);
+        }
+    }
+    let bits = palette_bits(palette.len());
+    let mut encoded = vec![palette.len() as u8];
+    encoded.extend(&palette);
+    if bits == 0 {
+        return Some(encoded);
+    }
+    let per_byte = 8 / bits;
+    for group in tiles.chunks(per_byte) {
+        let byte = group.iter().enumerate().fold(0, |byte, (i, tile)| {
+            let index = palette.iter().position(|p| p == tile).unwrap() as u8;
+            byte | index << (i * bits)
+        });
+        encoded.push(byte);
+    }
+    Some(encoded)
+}
+
+fn decode_palette(encoded: &[u8], count: usize) -> Option<Vec<u8>> {
+    let (&length, rest) = encoded.split_first()?;
+    let palette = rest.get(..length as usize)?;
+    let packed = &rest[length as usize..];
+    let bits = palette_bits(palette.len());
+    if bits == 0 {
+        return Some(vec![*palette.first()?; count]);
+    }
+    let mask = (1 << bits) - 1;
+    let per_byte = 8 / bits;
+    (0..count)
+        .map(|i| {
+            let byte = packed.get(i / per_byte)?;
+            palette
+                .get(((byte >> (i % per_byte * bits)) & mask) as usize)
+                .copied()
+        })
+        .collect()
+}
+
+fn palette_bits(length: usize) -> usize {
+    match length {
+        0 | 1 => 0,
+        2 => 1,
+        3 | 4 => 2,
+        _ => 4,
+    }
+}
+
+fn read_header(reader: &mut impl Read, magic: [u8; 4]) -> io::Result<()> {
+    let mut found = [0; 4];
+    reader.read_exact(&mut found)?;
+    if found != magic {
+        return Err(invalid_data("not an auburn tilemap"));
+    }
+    let mut version = [0; 2];
+    reader.read_exact(&mut version)?;
+    if u16::from_le_bytes(version) != VERSION {
+        return Err(invalid_data("unsupported tilemap version"));
+    }
+    Ok(())
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn write_vec2(writer: &mut impl Write, v: Vec2) -> io::Result<()> {
+    writer.write_all(&v.x.to_le_bytes())?;
+    writer.write_all(&v.y.to_le_bytes())
+}
+
+fn write_v2i32(writer: &mut impl Write, v: V2i32) -> io::Result<()> {
+    writer.write_all(&v.x().to_le_bytes())?;
+    writer.write_all(&v.y().to_le_bytes())
+}
+
+fn read_u8(reader: &mut impl Read) -> io::Result<u8> {
+    let mut bytes = [0; 1];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes[0])
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_i32(reader: &mut impl Read) -> io::Result<i32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(i32::from_le_bytes(bytes))
+}
+
+fn read_f32(reader: &mut impl Read) -> io::Result<f32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(f32::from_le_bytes(bytes))
+}
+
+fn read_vec2(reader: &mut impl Read) -> io::Result<Vec2> {
+    Ok(Vec2::new(read_f32(reader)?, read_f32(reader)?))
+}
+
+fn read_v2i32(reader: &mut impl Read) -> io::Result<V2i32> {
+    Ok(V2i32::from_xy(read_i32(reader)?, read_i32(reader)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: i32, y: i32) -> V2i32 {
+        V2i32::from_xy(x, y)
+    }
+
+    #[test_log::test]
+    fn encodings_round_trip() {
+        let count = 256;
+        let cases: [Vec<u8>; 4] = [
+            vec![0; count],
+            (0..count).map(|i| (i % 3) as u8).collect(),
+            (0..count).map(|i| (i / 20) as u8).collect(),
+            (0..count).map(|i| (i * 7) as u8).collect(),
+        ];
+        for tiles in cases {
+            let run_length = encode_run_length(&tiles);
+            assert_eq!(decode_run_length(&run_length, count), Some(tiles.clone()));
+            if let Some(palette) = encode_palette(&tiles) {
+                assert_eq!(decode_palette(&palette, count), Some(tiles.clone()));
+            }
+        }
+        assert_eq!(encode_run_length(&[0; 256]), [255, 0, 1, 0]);
+        assert_eq!(encode_palette(&[3; 256]), Some(vec![1, 3]));
+        assert_eq!(encode_palette(&(0..17).collect::<Vec<u8>>()), None);
+    }
+
+    #[test_log::test]
+    fn tilemap_round_trip() {
+        let mut tilemap = Tilemap::default()
+            .with_tile_size(Vec2::splat(8.0))
+            .with_origin(Vec2::new(-3.0, 5.0))
+            .with_chunk_size(v(8, 4));
+        for i in 0..200 {
+            tilemap.set_tile(v(i * 7 % 53 - 20, i * 3 % 31 - 10), (i % 5) as u8 + 1);
+        }
+        tilemap.set_tile(v(100, 100), 200);
+
+        let mut bytes = Vec::new();
+        tilemap.write_binary(&mut bytes).unwrap();
+        let loaded = Tilemap::read_binary(bytes.as_slice()).unwrap();
+        assert_eq!(loaded.tile_size(), tilemap.tile_size());
+        assert_eq!(loaded.origin(), tilemap.origin());
+        assert_eq!(loaded.chunk_size(), tilemap.chunk_size());
+        assert_eq!(loaded.chunks.len(), tilemap.chunks.len());
+        for (chunk_pos, chunk) in &tilemap.chunks {
+            assert_eq!(loaded.chunks[chunk_pos].array2d.data, chunk.array2d.data);
+        }
+
+        let json = serde_json::to_vec(&tilemap).unwrap();
+        assert!(
+            bytes.len() * 3 < json.len(),
+            "{} {}",
+            bytes.len(),
+            json.len()
+        );
+    }
+
+    #[test_log::test]
+    fn corrupted_data() {
+        let mut tilemap = Tilemap::default();
+        tilemap.set_tile(v(1, 1), 1);
+        let mut bytes = Vec::new();
+        tilemap.write_binary(&mut bytes).unwrap();
+
+        let error = |bytes: &[u8]| Tilemap::read_binary(bytes).err().unwrap().kind();
+        assert_eq!(
+            error(&bytes[..bytes.len() - 1]),
+            io::ErrorKind::UnexpectedEof
+        );
+        let mut wrong_version = bytes.clone();
+        wrong_version[4] = 9;
+        assert_eq!(error(&wrong_version), io::ErrorKind::InvalidData);
+        assert_eq!(error(br#"{"chunks":[]}"#), io::ErrorKind::InvalidData);
+
+        let mut chunk = Vec::new();
+        tilemap.write_chunk(&v(0, 0), &mut chunk).unwrap();
+        let mut other = Tilemap::default().with_chunk_size(v(8, 8));
+        let error = other.read_chunk(chunk.as_slice()).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let error = tilemap.write_chunk(&v(5, 5), Vec::new()).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test_log::test]
+    fn malicious_headers() {
+        let header = |tile_size: Vec2, chunk_size: V2i32, chunks: u32| {
+            let mut bytes = TILEMAP_MAGIC.to_vec();
+            bytes.extend(VERSION.to_le_bytes());
+            write_vec2(&mut bytes, tile_size).unwrap();
+            write_vec2(&mut bytes, Vec2::ZERO).unwrap();
+            write_v2i32(&mut bytes, chunk_size).unwrap();
+            bytes.extend(chunks.to_le_bytes());
+            bytes
+        };
+        let error = |bytes: Vec<u8>| Tilemap::read_binary(bytes.as_slice()).err().unwrap().kind();
+
+        for chunk_size in [
+            v(0, 16),
+            v(-16, 16),
+            v(65536, 65536),
+            v(i32::MAX, 2),
+            v(16, 2048),
+        ] {
+            let bytes = header(Vec2::ONE, chunk_size, 1);
+            assert_eq!(error(bytes), io::ErrorKind::InvalidData, "{chunk_size:?}");
+        }
+        for tile_size in [Vec2::ZERO, Vec2::new(1.0, -1.0), Vec2::NAN, Vec2::INFINITY] {
+            let bytes = header(tile_size, v(16, 16), 0);
+            assert_eq!(error(bytes), io::ErrorKind::InvalidData, "{tile_size}");
+        }
+        let bytes = header(Vec2::ONE, v(16, 16), u32::MAX);
+        assert_eq!(error(bytes.clone()), io::ErrorKind::UnexpectedEof);
+        assert_eq!(error(bytes[..10].to_vec()), io::ErrorKind::UnexpectedEof);
+
+        // A run-length chunk whose runs add up to more tiles than the chunk holds.
+        let mut chunk = CHUNK_MAGIC.to_vec();
+        chunk.extend(VERSION.to_le_bytes());
+        write_v2i32(&mut chunk, v(0, 0)).unwrap();
+        write_v2i32(&mut chunk, v(16, 16)).unwrap();
+        chunk.push(ChunkEncoding::RunLength as u8);
+        chunk.extend(4u32.to_le_bytes());
+        chunk.extend([255, 1, 255, 1]);
+        let mut bytes = header(Vec2::ONE, v(16, 16), 1);
+        bytes.extend(chunk);
+        assert_eq!(error(bytes), io::ErrorKind::InvalidData);
+    }
+}
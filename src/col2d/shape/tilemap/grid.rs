@@ -0,0 +1,673 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+use lk_math::arraynd::Array2d;
+use lk_math::vector::V2i32;
+
+use super::Tilemap;
+use crate::utils::rect2::Rect2i32;
+
+/// Which neighbouring tiles are connected in grid queries on a [Tilemap].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity2d {
+    /// Tiles sharing an edge.
+    Four,
+    /// Tiles sharing an edge or a corner.
+    Eight(CornerCutting),
+}
+
+/// When a diagonal step between two tiles is allowed, depending on the two tiles next to both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CornerCutting {
+    /// Both tiles next to the step must be free.
+    Never,
+    /// At least one tile next to the step must be free.
+    IfOneSideFree,
+    /// Diagonal steps are always allowed, even squeezing between two blocked tiles.
+    Always,
+}
+
+const DIAGONAL_COST: f32 = core::f32::consts::SQRT_2;
+
+const ORTHOGONAL: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+const DIAGONAL: [(i32, i32); 4] = [(1, 1), (-1, 1), (-1, -1), (1, -1)];
+
+/// Grid queries, tiles with value `0` are free and others are blocked.
+///
+/// Queries are limited to `bounds`, because the tilemap is unbounded, missing chunks are read as
+/// free without being allocated.
+impl Tilemap {
+    fn is_free(&self, bounds: &Rect2i32, p: V2i32) -> bool {
+        bounds.contains(p) && self.get_tile(p) == 0
+    }
+
+    /// Neighbours of `p` reachable in one step, with the cost of the step.
+    fn grid_neighbours<'a>(
+        &'a self,
+        bounds: &'a Rect2i32,
+        connectivity: Connectivity2d,
+        p: V2i32,
+    ) -> impl Iterator<Item = (V2i32, f32)> + 'a {
+        let orthogonal = ORTHOGONAL
+            .into_iter()
+            .map(move |(x, y)| (p + V2i32::from_xy(x, y), 1.0));
+        let diagonal = match connectivity {
+            Connectivity2d::Four => None,
+            Connectivity2d::Eight(corner_cutting) => {
+                Some(DIAGONAL.into_iter().filter_map(move |(x, y)| {
+                    let side_x = self.is_free(bounds, p + V2i32::from_xy(x, 0));
+                    let side_y = self.is_free(bounds, p + V2i32::from_xy(0, y));
+                    let allowed = match corner_cutting {
+                        CornerCutting::Never => side_x && side_y,
+                        CornerCutting::IfOneSideFree => side_x || side_y,
+                        CornerCutting::Always => true,
+                    };
+                    allowed.then(|| (p + V2i32::from_xy(x, y), DIAGONAL_COST))
+                }))
+            }
+        };
+        orthogonal
+            .chain(diagonal.into_iter().flatten())
+            .filter(move |&(q, _)| self.is_free(bounds, q))
+    }
+
+    /// Shortest path of free tiles from `start` to `goal` with A*, both ends included.
+    ///
+    /// Orthogonal steps cost `1` and diagonal steps `sqrt(2)`.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// # use auburn::utils::rect2::Rect2i32;
+    /// # use lk_math::vector::V2i32;
+    /// let mut tilemap = Tilemap::default();
+    /// for y in -2..=2 {
+    ///     tilemap.set_tile(V2i32::from_xy(0, y), 1);
+    /// }
+    /// let bounds = Rect2i32::min_max(V2i32::from_xy(-5, -5), V2i32::from_xy(5, 5));
+    /// let path = tilemap
+    ///     .find_path(
+    ///         V2i32::from_xy(-2, 0),
+    ///         V2i32::from_xy(2, 0),
+    ///         Connectivity2d::Four,
+    ///         &bounds,
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(path.len(), 11);
+    /// ```
+    pub fn find_path(
+        &self,
+        start: V2i32,
+        goal: V2i32,
+        connectivity: Connectivity2d,
+        bounds: &Rect2i32,
+    ) -> Option<Vec<V2i32>> {
+        if !self.is_free(bounds, start) || !self.is_free(bounds, goal) {
+            return None;
+        }
+        let heuristic = |p: V2i32| grid_distance(connectivity, p, goal);
+        let mut cost = HashMap::from([(start, 0.0)]);
+        let mut came_from = HashMap::new();
+        let mut open = BinaryHeap::from([Open {
+            estimate: heuristic(start),
+            tile: start,
+        }]);
+
+        while let Some(Open { estimate, tile }) = open.pop() {
+            if tile == goal {
+                return Some(unwind(&came_from, goal));
+            }
+            let tile_cost = cost[&tile];
+            if estimate > tile_cost + heuristic(tile) {
+                continue;
+            }
+            for (next, step) in self.grid_neighbours(bounds, connectivity, tile) {
+                let next_cost = tile_cost + step;
+                if cost.get(&next).is_none_or(|&c| next_cost < c) {
+                    cost.insert(next, next_cost);
+                    came_from.insert(next, tile);
+                    open.push(Open {
+                        estimate: next_cost + heuristic(next),
+                        tile: next,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Same as [Tilemap::find_path] with [Connectivity2d::Eight] and [CornerCutting::Never], but
+    /// with jump point search, which skips over open areas.
+    ///
+    /// The path is equally short, though not necessarily the same one.
+    pub fn find_path_jps(
+        &self,
+        start: V2i32,
+        goal: V2i32,
+        bounds: &Rect2i32,
+    ) -> Option<Vec<V2i32>> {
+        if !self.is_free(bounds, start) || !self.is_free(bounds, goal) {
+            return None;
+        }
+        let connectivity = Connectivity2d::Eight(CornerCutting::Never);
+        let heuristic = |p: V2i32| grid_distance(connectivity, p, goal);
+        let mut cost = HashMap::from([(start, 0.0)]);
+        let mut came_from = HashMap::new();
+        let mut open = BinaryHeap::from([Open {
+            estimate: heuristic(start),
+            tile: start,
+        }]);
+
+        while let Some(Open { estimate, tile }) = open.pop() {
+            if tile == goal {
+                let jump_points = unwind(&came_from, goal);
+                return Some(fill_jumps(&jump_points));
+            }
+            let tile_cost = cost[&tile];
+            if estimate > tile_cost + heuristic(tile) {
+                continue;
+            }
+            let parent = came_from.get(&tile).copied();
+            for direction in self.jps_directions(bounds, tile, parent) {
+                let Some(jump_point) = self.jump(bounds, tile, direction, goal) else {
+                    continue;
+                };
+                let next_cost = tile_cost + grid_distance(connectivity, tile, jump_point);
+                if cost.get(&jump_point).is_none_or(|&c| next_cost < c) {
+                    cost.insert(jump_point, next_cost);
+                    came_from.insert(jump_point, tile);
+                    open.push(Open {
+                        estimate: next_cost + heuristic(jump_point),
+                        tile: jump_point,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Directions worth jumping in from `p` when arriving from `parent`, without cutting corners.
+    fn jps_directions(&self, bounds: &Rect2i32, p: V2i32, parent: Option<V2i32>) -> Vec<V2i32> {
+        let free = |x: i32, y: i32| self.is_free(bounds, p + V2i32::from_xy(x, y));
+        let Some(parent) = parent else {
+            return self
+                .grid_neighbours(bounds, Connectivity2d::Eight(CornerCutting::Never), p)
+                .map(|(q, _)| q - p)
+                .collect();
+        };
+        let (dx, dy) = ((p.x() - parent.x()).signum(), (p.y() - parent.y()).signum());
+        let mut directions = Vec::with_capacity(4);
+        let mut push = |x: i32, y: i32| directions.push(V2i32::from_xy(x, y));
+        if dx != 0 && dy != 0 {
+            let (side_x, side_y) = (free(dx, 0), free(0, dy));
+            if side_x {
+                push(dx, 0);
+            }
+            if side_y {
+                push(0, dy);
+            }
+            if side_x && side_y {
+                push(dx, dy);
+            }
+        } else if dx != 0 {
+            let (next, up, down) = (free(dx, 0), free(0, 1), free(0, -1));
+            if next {
+                push(dx, 0);
+                if up {
+                    push(dx, 1);
+                }
+                if down {
+                    push(dx, -1);
+                }
+            }
+            if up {
+                push(0, 1);
+            }
+            if down {
+                push(0, -1);
+            }
+        } else {
+            let (next, right, left) = (free(0, dy), free(1, 0), free(-1, 0));
+            if next {
+                push(0, dy);
+                if right {
+                    push(1, dy);
+                }
+                if left {
+                    push(-1, dy);
+                }
+            }
+            if right {
+                push(1, 0);
+            }
+            if left {
+                push(-1, 0);
+            }
+        }
+        directions
+            .into_iter()
+            .filter(|&d| {
+                // Diagonal steps must not cut corners.
+                d.x() == 0 || d.y() == 0 || (free(d.x(), 0) && free(0, d.y()) && free(d.x(), d.y()))
+            })
+            .collect()
+    }
+
+    /// Walks from `from` along `direction` until reaching the goal or a tile with a neighbour
+    /// that can only be reached optimally through it.
+    fn jump(&self, bounds: &Rect2i32, from: V2i32, direction: V2i32, goal: V2i32) -> Option<V2i32> {
+        let (dx, dy) = (direction.x(), direction.y());
+        let mut p = from;
+        loop {
+            p += direction;
+            let free = |x: i32, y: i32| self.is_free(bounds, p + V2i32::from_xy(x, y));
+            if !free(0, 0) {
+                return None;
+            }
+            if p == goal {
+                return Some(p);
+            }
+            if dx != 0 && dy != 0 {
+                let straight_x = self.jump(bounds, p, V2i32::from_xy(dx, 0), goal);
+                let straight_y = self.jump(bounds, p, V2i32::from_xy(0, dy), goal);
+                if straight_x.is_some() || straight_y.is_some() {
+                    return Some(p);
+                }
+                if !(free(dx, 0) && free(0, dy)) {
+                    return None;
+                }
+            } else if dx != 0 {
+                if (free(0, 1) && !free(-dx, 1)) || (free(0, -1) && !free(-dx, -1)) {
+                    return Some(p);
+                }
+            } else if (free(1, 0) && !free(1, -dy)) || (free(-1, 0) && !free(-1, -dy)) {
+                return Some(p);
+            }
+        }
+    }
+
+    /// Tiles connected to `start` having the same value, `start` included.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// # use auburn::utils::rect2::Rect2i32;
+    /// # use lk_math::vector::V2i32;
+    /// let mut tilemap = Tilemap::default();
+    /// tilemap.set_tile(V2i32::from_xy(0, 0), 3);
+    /// tilemap.set_tile(V2i32::from_xy(1, 1), 3);
+    /// let bounds = Rect2i32::min_max(V2i32::from_xy(-5, -5), V2i32::from_xy(5, 5));
+    ///
+    /// let start = V2i32::from_xy(0, 0);
+    /// assert_eq!(tilemap.flood_fill(start, Connectivity2d::Four, &bounds).len(), 1);
+    /// let eight = Connectivity2d::Eight(CornerCutting::Always);
+    /// assert_eq!(tilemap.flood_fill(start, eight, &bounds).len(), 2);
+    /// ```
+    pub fn flood_fill(
+        &self,
+        start: V2i32,
+        connectivity: Connectivity2d,
+        bounds: &Rect2i32,
+    ) -> Vec<V2i32> {
+        if !bounds.contains(start) {
+            return Vec::new();
+        }
+        let value = self.get_tile(start);
+        let steps: &[(i32, i32)] = match connectivity {
+            Connectivity2d::Four => &ORTHOGONAL,
+            Connectivity2d::Eight(_) => &[
+                (1, 0),
+                (0, 1),
+                (-1, 0),
+                (0, -1),
+                (1, 1),
+                (-1, 1),
+                (-1, -1),
+                (1, -1),
+            ],
+        };
+        let same = |p: V2i32| bounds.contains(p) && self.get_tile(p) == value;
+
+        let mut region = vec![start];
+        let mut seen = HashSet::from([start]);
+        let mut i = 0;
+        while i < region.len() {
+            let p = region[i];
+            i += 1;
+            for &(x, y) in steps {
+                let q = p + V2i32::from_xy(x, y);
+                if !same(q) || seen.contains(&q) {
+                    continue;
+                }
+                // Region tiles act as free tiles for the corner-cutting rule.
+                let allowed = match connectivity {
+                    Connectivity2d::Eight(CornerCutting::Never) if x != 0 && y != 0 => {
+                        same(p + V2i32::from_xy(x, 0)) && same(p + V2i32::from_xy(0, y))
+                    }
+                    Connectivity2d::Eight(CornerCutting::IfOneSideFree) if x != 0 && y != 0 => {
+                        same(p + V2i32::from_xy(x, 0)) || same(p + V2i32::from_xy(0, y))
+                    }
+                    _ => true,
+                };
+                if allowed {
+                    seen.insert(q);
+                    region.push(q);
+                }
+            }
+        }
+        region
+    }
+
+    /// Distance from every tile in `bounds` to the nearest blocked tile in `bounds`.
+    ///
+    /// Distances are measured along paths of steps allowed by `connectivity`, so orthogonal
+    /// steps cost `1` and diagonal steps `sqrt(2)`. Element `(x, y)` of the result belongs to
+    /// tile `bounds.min + (x, y)`, blocked tiles are `0.0` and tiles with no blocked tile in
+    /// reach are [f32::INFINITY].
+    pub fn distance_transform(
+        &self,
+        connectivity: Connectivity2d,
+        bounds: &Rect2i32,
+    ) -> Array2d<f32> {
+        let width = (bounds.max.x() - bounds.min.x() + 1).max(0) as usize;
+        let height = (bounds.max.y() - bounds.min.y() + 1).max(0) as usize;
+        let mut distances = Array2d::with_dimensions(width, height, f32::INFINITY);
+        let index = |p: V2i32| p - bounds.min;
+
+        // Multi-source Dijkstra from all blocked tiles, without diagonal steps every step costs the
+        // same and a queue is enough.
+        let mut queue = VecDeque::new();
+        let mut open = BinaryHeap::new();
+        for p in bounds.iterate() {
+            if self.get_tile(p) != 0 {
+                distances.set(index(p), 0.0);
+                match connectivity {
+                    Connectivity2d::Four => queue.push_back(p),
+                    Connectivity2d::Eight(_) => open.push(Open {
+                        estimate: 0.0,
+                        tile: p,
+                    }),
+                }
+            }
+        }
+
+        let relax = |distances: &mut Array2d<f32>, p: V2i32, q: V2i32, step: f32| {
+            let next = distances.get(index(p)).copied().unwrap_or(f32::INFINITY) + step;
+            match distances.get(index(q)) {
+                Some(&current) if next < current => {
+                    distances.set(index(q), next);
+                    true
+                }
+                _ => false,
+            }
+        };
+
+        while let Some(p) = queue.pop_front() {
+            for (x, y) in ORTHOGONAL {
+                let q = p + V2i32::from_xy(x, y);
+                if bounds.contains(q) && relax(&mut distances, p, q, 1.0) {
+                    queue.push_back(q);
+                }
+            }
+        }
+
+        while let Some(Open { estimate, tile: p }) = open.pop() {
+            if distances.get(index(p)).is_some_and(|&d| estimate > d) {
+                continue;
+            }
+            let Connectivity2d::Eight(corner_cutting) = connectivity else {
+                break;
+            };
+            for (x, y) in ORTHOGONAL.into_iter().chain(DIAGONAL) {
+                let q = p + V2i32::from_xy(x, y);
+                if !bounds.contains(q) {
+                    continue;
+                }
+                let step = if x != 0 && y != 0 {
+                    // Steps leaving a blocked tile are always allowed, the corner-cutting rule
+                    // only applies between free tiles.
+                    let blocked = |p: V2i32| !bounds.contains(p) || self.get_tile(p) != 0;
+                    let (side_x, side_y) = (
+                        !blocked(p + V2i32::from_xy(x, 0)),
+                        !blocked(p + V2i32::from_xy(0, y)),
+                    );
+                    let source = self.get_tile(p) != 0;
+                    let allowed = source
+                        || match corner_cutting {
+                            CornerCutting::Never => side_x && side_y,
+                            CornerCutting::IfOneSideFree => side_x || side_y,
+                            CornerCutting::Always => true,
+                        };
+                    if !allowed {
+                        continue;
+                    }
+                    DIAGONAL_COST
+                } else {
+                    1.0
+                };
+                if relax(&mut distances, p, q, step) {
+                    open.push(Open {
+                        estimate: distances.get(index(q)).copied().unwrap_or(f32::INFINITY),
+                        tile: q,
+                    });
+                }
+            }
+        }
+        distances
+    }
+}
+
+/// Length of the shortest path between `a` and `b` on an empty grid.
+fn grid_distance(connectivity: Connectivity2d, a: V2i32, b: V2i32) -> f32 {
+    let (dx, dy) = ((a.x() - b.x()).abs(), (a.y() - b.y()).abs());
+    match connectivity {
+        Connectivity2d::Four => (dx + dy) as f32,
+        Connectivity2d::Eight(_) => {
+            let (short, long) = (dx.min(dy), dx.max(dy));
+            (long - short) as f32 + DIAGONAL_COST * short as f32
+        }
+    }
+}
+
+fn unwind(came_from: &HashMap<V2i32, V2i32>, goal: V2i32) -> Vec<V2i32> {
+    let mut path = vec![goal];
+    let mut current = goal;
+    while let Some(&previous) = came_from.get(&current) {
+        path.push(previous);
+        current = previous;
+    }
+    path.reverse();
+    path
+}
+
+/// Tiles between consecutive jump points, which always lie on a straight or diagonal line.
+fn fill_jumps(jump_points: &[V2i32]) -> Vec<V2i32> {
+    let mut path = vec![jump_points[0]];
+    for pair in jump_points.windows(2) {
+        let (mut p, to) = (pair[0], pair[1]);
+        let step = V2i32::from_xy((to.x() - p.x()).signum(), (to.y() - p.y()).signum());
+        while p != to {
+            p += step;
+            path.push(p);
+        }
+    }
+    path
+}
+
+/// Entry of an open set, ordered so that [BinaryHeap] pops the lowest estimate first.
+struct Open {
+    estimate: f32,
+    tile: V2i32,
+}
+
+impl PartialEq for Open {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Open {}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Open {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: i32, y: i32) -> V2i32 {
+        V2i32::from_xy(x, y)
+    }
+
+    fn bounds(min: i32, max: i32) -> Rect2i32 {
+        Rect2i32::min_max(v(min, min), v(max, max))
+    }
+
+    fn path_cost(path: &[V2i32]) -> f32 {
+        path.windows(2)
+            .map(|w| grid_distance(Connectivity2d::Eight(CornerCutting::Always), w[0], w[1]))
+            .sum()
+    }
+
+    /// Pseudo-random obstacles, roughly a third of the tiles are blocked.
+    fn noise(seed: u32, bounds: &Rect2i32) -> Tilemap {
+        let mut tilemap = Tilemap::default();
+        let mut state = seed;
+        for p in bounds.iterate() {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            if state.is_multiple_of(3) {
+                tilemap.set_tile(p, 1);
+            }
+        }
+        tilemap
+    }
+
+    #[test_log::test]
+    fn path_through_gap() {
+        let mut tilemap = Tilemap::default();
+        for y in -4..=4 {
+            if y != 3 {
+                tilemap.set_tile(v(0, y), 1);
+            }
+        }
+        let bounds = bounds(-4, 4);
+        let path = tilemap
+            .find_path(v(-2, 0), v(2, 0), Connectivity2d::Four, &bounds)
+            .unwrap();
+        assert!(path.contains(&v(0, 3)));
+        assert_eq!(path.len(), 11);
+
+        let eight = Connectivity2d::Eight(CornerCutting::Never);
+        let path = tilemap
+            .find_path(v(-2, 0), v(2, 0), eight, &bounds)
+            .unwrap();
+        assert!(path.contains(&v(0, 3)));
+        let jps = tilemap.find_path_jps(v(-2, 0), v(2, 0), &bounds).unwrap();
+        assert!((path_cost(&path) - path_cost(&jps)).abs() < 1e-5);
+
+        tilemap.set_tile(v(0, 3), 1);
+        assert_eq!(
+            tilemap.find_path(v(-2, 0), v(2, 0), Connectivity2d::Four, &bounds),
+            None
+        );
+        assert_eq!(tilemap.find_path_jps(v(-2, 0), v(2, 0), &bounds), None);
+    }
+
+    #[test_log::test]
+    fn corner_cutting() {
+        //  .#
+        //  #.
+        let mut tilemap = Tilemap::default();
+        tilemap.set_tile(v(1, 1), 1);
+        tilemap.set_tile(v(0, 0), 1);
+        let bounds = bounds(-3, 3);
+        let (start, goal) = (v(0, 1), v(1, 0));
+        let length = |tilemap: &Tilemap, corner_cutting| {
+            tilemap
+                .find_path(start, goal, Connectivity2d::Eight(corner_cutting), &bounds)
+                .map(|path| path.len())
+        };
+        assert_eq!(length(&tilemap, CornerCutting::Always), Some(2));
+        assert_eq!(length(&tilemap, CornerCutting::IfOneSideFree), Some(4));
+        assert_eq!(length(&tilemap, CornerCutting::Never), Some(7));
+
+        tilemap.remove_tile(v(0, 0));
+        assert_eq!(length(&tilemap, CornerCutting::IfOneSideFree), Some(2));
+        assert_eq!(length(&tilemap, CornerCutting::Never), Some(3));
+    }
+
+    #[test_log::test]
+    fn jps_matches_astar() {
+        let eight = Connectivity2d::Eight(CornerCutting::Never);
+        let bounds = bounds(-12, 12);
+        for seed in 1..20 {
+            let tilemap = noise(seed * 7919, &bounds);
+            for (start, goal) in [(v(-12, -12), v(12, 12)), (v(-10, 5), v(11, -3))] {
+                if tilemap.get_tile(start) != 0 || tilemap.get_tile(goal) != 0 {
+                    continue;
+                }
+                let astar = tilemap.find_path(start, goal, eight, &bounds);
+                let jps = tilemap.find_path_jps(start, goal, &bounds);
+                assert_eq!(astar.is_some(), jps.is_some(), "{seed}");
+                let (Some(astar), Some(jps)) = (astar, jps) else {
+                    continue;
+                };
+                assert!((path_cost(&astar) - path_cost(&jps)).abs() < 1e-3, "{seed}");
+                for w in jps.windows(2) {
+                    let step = w[1] - w[0];
+                    assert!(step.x().abs() <= 1 && step.y().abs() <= 1);
+                    assert_eq!(tilemap.get_tile(w[1]), 0);
+                    if step.x() != 0 && step.y() != 0 {
+                        assert_eq!(tilemap.get_tile(w[0] + v(step.x(), 0)), 0);
+                        assert_eq!(tilemap.get_tile(w[0] + v(0, step.y())), 0);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test_log::test]
+    fn queries_do_not_allocate_chunks() {
+        let mut tilemap = Tilemap::default();
+        tilemap.set_tile(v(0, 0), 1);
+        let bounds = bounds(-40, 40);
+        let region = tilemap.flood_fill(v(5, 5), Connectivity2d::Four, &bounds);
+        assert_eq!(region.len(), 81 * 81 - 1);
+        tilemap.find_path(v(-40, -40), v(40, 40), Connectivity2d::Four, &bounds);
+        tilemap.distance_transform(Connectivity2d::Four, &bounds);
+        assert_eq!(tilemap.chunks.len(), 1);
+    }
+
+    #[test_log::test]
+    fn distance_transform() {
+        let mut tilemap = Tilemap::default();
+        tilemap.set_tile(v(2, 2), 1);
+        let bounds = bounds(0, 4);
+        let at = |distances: &Array2d<f32>, x: i32, y: i32| *distances.get(v(x, y)).unwrap();
+
+        let four = tilemap.distance_transform(Connectivity2d::Four, &bounds);
+        assert_eq!(at(&four, 2, 2), 0.0);
+        assert_eq!(at(&four, 3, 2), 1.0);
+        assert_eq!(at(&four, 4, 4), 4.0);
+
+        let eight =
+            tilemap.distance_transform(Connectivity2d::Eight(CornerCutting::Never), &bounds);
+        assert_eq!(at(&eight, 3, 3), DIAGONAL_COST);
+        assert_eq!(at(&eight, 4, 3), 1.0 + DIAGONAL_COST);
+        assert_eq!(at(&eight, 0, 0), 2.0 * DIAGONAL_COST);
+
+        let empty = Tilemap::default().distance_transform(Connectivity2d::Four, &bounds);
+        assert_eq!(at(&empty, 1, 1), f32::INFINITY);
+    }
+}
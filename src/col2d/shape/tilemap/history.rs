@@ -0,0 +1,344 @@
+use std::collections::{HashMap, VecDeque};
+
+use lk_math::vector::V2i32;
+
+use super::{Tilemap, TilemapEvent};
+use crate::utils::publisher::Publisher;
+use crate::utils::rect2::Rect2i32;
+
+/// A single tile change.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct TileEdit {
+    pub pos: V2i32,
+    pub old: u8,
+    pub new: u8,
+}
+
+#[derive(Debug)]
+struct DirtyChunk {
+    chunk: V2i32,
+    /// Whether the chunk was allocated before the batch.
+    existed: bool,
+    dirty: Rect2i32,
+}
+
+/// Edits between [Tilemap::begin_batch] and the matching [Tilemap::end_batch].
+#[derive(Debug, Default)]
+pub(super) struct Batch {
+    depth: usize,
+    edits: Vec<TileEdit>,
+    /// Chunks in the order they were first changed.
+    chunks: Vec<DirtyChunk>,
+    index: HashMap<V2i32, usize>,
+}
+
+impl Batch {
+    pub(super) fn touch(&mut self, chunk: V2i32, pos: V2i32, existed: bool) {
+        if let Some(&i) = self.index.get(&chunk) {
+            self.chunks[i].dirty.cover(pos);
+        } else {
+            self.index.insert(chunk, self.chunks.len());
+            self.chunks.push(DirtyChunk {
+                chunk,
+                existed,
+                dirty: Rect2i32::min_max(pos, pos),
+            });
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct TilemapHistory {
+    limit: usize,
+    undo: VecDeque<Vec<TileEdit>>,
+    redo: Vec<Vec<TileEdit>>,
+    pub batch: Option<Batch>,
+    replaying: bool,
+}
+
+impl TilemapHistory {
+    pub(super) fn record(&mut self, edit: TileEdit) {
+        if self.limit == 0 || self.replaying {
+            return;
+        }
+        match &mut self.batch {
+            Some(batch) => batch.edits.push(edit),
+            None => self.push(vec![edit]),
+        }
+    }
+
+    fn push(&mut self, edits: Vec<TileEdit>) {
+        if edits.is_empty() {
+            return;
+        }
+        self.redo.clear();
+        self.undo.push_back(edits);
+        self.truncate();
+    }
+
+    fn truncate(&mut self) {
+        while self.undo.len() > self.limit {
+            self.undo.pop_front();
+        }
+        if self.limit == 0 {
+            self.redo.clear();
+        }
+    }
+}
+
+impl Tilemap {
+    /// Keeps the last `limit` edits for [Tilemap::undo], a whole batch counts as one edit.
+    ///
+    /// The history is disabled by default. Only [Tilemap::set_tile] and [Tilemap::remove_tile]
+    /// are recorded, loading or unloading chunks is not.
+    pub fn with_history_limit(mut self, limit: usize) -> Self {
+        self.set_history_limit(limit);
+        self
+    }
+
+    /// See [Tilemap::with_history_limit], `0` disables the history and drops it.
+    pub fn set_history_limit(&mut self, limit: usize) {
+        self.history.limit = limit;
+        self.history.truncate();
+    }
+
+    pub fn history_limit(&self) -> usize {
+        self.history.limit
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.history.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.history.redo.is_empty()
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.undo.clear();
+        self.history.redo.clear();
+    }
+
+    /// Starts grouping edits until the matching [Tilemap::end_batch], batches can be nested.
+    ///
+    /// Instead of an event per edit, ending the batch notifies one event per changed chunk:
+    /// [TilemapEvent::ChunkCreated], [TilemapEvent::ChunkRemoved] or
+    /// [TilemapEvent::TilesChanged] with the changed tiles. The batch is undone as a whole.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// # use lk_math::vector::V2i32;
+    /// let mut tilemap = Tilemap::default().with_history_limit(16);
+    /// tilemap.set_tile(V2i32::from_xy(0, 0), 1);
+    /// tilemap.events.events.clear();
+    ///
+    /// tilemap.begin_batch();
+    /// for x in 2..6 {
+    ///     tilemap.set_tile(V2i32::from_xy(x, 3), 1);
+    /// }
+    /// tilemap.end_batch();
+    /// assert_eq!(tilemap.events.events.len(), 1);
+    ///
+    /// tilemap.undo();
+    /// assert_eq!(tilemap.get_tile(V2i32::from_xy(2, 3)), 0);
+    /// assert_eq!(tilemap.get_tile(V2i32::from_xy(0, 0)), 1);
+    /// ```
+    pub fn begin_batch(&mut self) {
+        self.history
+            .batch
+            .get_or_insert_with(Default::default)
+            .depth += 1;
+    }
+
+    /// # Panics
+    /// If there is no batch in progress.
+    pub fn end_batch(&mut self) {
+        let batch = self
+            .history
+            .batch
+            .as_mut()
+            .expect("end_batch without begin_batch");
+        batch.depth -= 1;
+        if batch.depth > 0 {
+            return;
+        }
+        let Some(batch) = self.history.batch.take() else {
+            return;
+        };
+        for DirtyChunk {
+            chunk,
+            existed,
+            dirty,
+        } in batch.chunks
+        {
+            match (existed, self.chunks.contains_key(&chunk)) {
+                (false, true) => self.events.notify(TilemapEvent::ChunkCreated(chunk)),
+                (true, false) => self.events.notify(TilemapEvent::ChunkRemoved(chunk)),
+                (true, true) => self
+                    .events
+                    .notify(TilemapEvent::TilesChanged { chunk, dirty }),
+                (false, false) => {}
+            }
+        }
+        self.history.push(batch.edits);
+    }
+
+    /// Runs `f` in a batch, see [Tilemap::begin_batch].
+    pub fn batch<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.begin_batch();
+        let result = f(self);
+        self.end_batch();
+        result
+    }
+
+    /// Reverts the last edit or batch, `false` if there is nothing to undo.
+    ///
+    /// Events are notified like for a batch.
+    ///
+    /// # Panics
+    /// If a batch is in progress.
+    pub fn undo(&mut self) -> bool {
+        assert!(self.history.batch.is_none(), "undo during a batch");
+        let Some(edits) = self.history.undo.pop_back() else {
+            return false;
+        };
+        self.replay(edits.iter().rev().map(|edit| (edit.pos, edit.old)));
+        self.history.redo.push(edits);
+        true
+    }
+
+    /// Reapplies the last undone edit or batch, `false` if there is nothing to redo.
+    ///
+    /// # Panics
+    /// If a batch is in progress.
+    pub fn redo(&mut self) -> bool {
+        assert!(self.history.batch.is_none(), "redo during a batch");
+        let Some(edits) = self.history.redo.pop() else {
+            return false;
+        };
+        self.replay(edits.iter().map(|edit| (edit.pos, edit.new)));
+        self.history.undo.push_back(edits);
+        true
+    }
+
+    fn replay(&mut self, tiles: impl Iterator<Item = (V2i32, u8)>) {
+        self.history.replaying = true;
+        self.begin_batch();
+        for (pos, tile) in tiles {
+            // Removing empty chunks so that undoing the first tile of a chunk frees it again.
+            self.write_tile(pos, tile, true);
+        }
+        self.end_batch();
+        self.history.replaying = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: i32, y: i32) -> V2i32 {
+        V2i32::from_xy(x, y)
+    }
+
+    #[test_log::test]
+    fn remove_tile_events() {
+        let mut tilemap = Tilemap::default();
+        tilemap.remove_tile(v(3, 3));
+        assert_eq!(tilemap.chunk_positions().count(), 0);
+        assert!(tilemap.events.events.is_empty());
+
+        tilemap.set_tile(v(3, 3), 1);
+        tilemap.set_tile(v(4, 3), 1);
+        tilemap.remove_tile(v(3, 3));
+        tilemap.remove_tile(v(3, 3));
+        tilemap.remove_tile(v(4, 3));
+        assert!(matches!(
+            tilemap.events.events[..],
+            [
+                TilemapEvent::ChunkCreated(_),
+                TilemapEvent::ChunkChanged(_),
+                TilemapEvent::ChunkChanged(_),
+                TilemapEvent::ChunkRemoved(_),
+            ]
+        ));
+        assert_eq!(tilemap.chunk_positions().count(), 0);
+    }
+
+    #[test_log::test]
+    fn batch_events() {
+        let mut tilemap = Tilemap::default();
+        tilemap.set_tile(v(0, 0), 1);
+        tilemap.set_tile(v(20, 0), 1);
+        tilemap.events.events.clear();
+
+        tilemap.batch(|tilemap| {
+            tilemap.set_tile(v(5, 2), 2);
+            tilemap.batch(|tilemap| {
+                tilemap.set_tile(v(3, 7), 2);
+                tilemap.set_tile(v(-1, 0), 2);
+            });
+            tilemap.remove_tile(v(20, 0));
+            tilemap.set_tile(v(40, 0), 1);
+            tilemap.remove_tile(v(40, 0));
+            assert!(tilemap.events.events.is_empty());
+        });
+
+        let events = &tilemap.events.events;
+        assert_eq!(events.len(), 3, "{events:?}");
+        assert!(matches!(
+            events[0],
+            TilemapEvent::TilesChanged { chunk, dirty }
+                if chunk == v(0, 0) && dirty == Rect2i32::min_max(v(3, 2), v(5, 7))
+        ));
+        assert!(matches!(events[1], TilemapEvent::ChunkCreated(c) if c == v(-1, 0)));
+        assert!(matches!(events[2], TilemapEvent::ChunkRemoved(c) if c == v(1, 0)));
+    }
+
+    #[test_log::test]
+    fn undo_redo() {
+        let mut tilemap = Tilemap::default().with_history_limit(2);
+        assert!(!tilemap.undo());
+
+        tilemap.set_tile(v(0, 0), 1);
+        tilemap.batch(|tilemap| {
+            tilemap.set_tile(v(0, 0), 2);
+            tilemap.set_tile(v(0, 0), 3);
+            tilemap.set_tile(v(17, 0), 4);
+        });
+        tilemap.remove_tile(v(0, 0));
+        tilemap.events.events.clear();
+
+        assert!(tilemap.undo());
+        assert_eq!(tilemap.get_tile(v(0, 0)), 3);
+        assert!(tilemap.undo());
+        assert_eq!(tilemap.get_tile(v(0, 0)), 1);
+        assert_eq!(tilemap.get_tile(v(17, 0)), 0);
+        assert!(matches!(
+            tilemap.events.events[..],
+            [
+                TilemapEvent::ChunkCreated(_),
+                TilemapEvent::ChunkRemoved(c),
+                TilemapEvent::TilesChanged { .. },
+            ] if c == v(1, 0)
+        ));
+        // The first edit fell out of the history.
+        assert!(!tilemap.undo());
+        assert!(tilemap.can_redo());
+
+        assert!(tilemap.redo());
+        assert_eq!(tilemap.get_tile(v(0, 0)), 3);
+        assert_eq!(tilemap.get_tile(v(17, 0)), 4);
+
+        tilemap.set_tile(v(1, 1), 5);
+        assert!(!tilemap.can_redo());
+        assert!(tilemap.undo());
+        assert!(tilemap.undo());
+        assert_eq!(tilemap.get_tile(v(0, 0)), 1);
+        assert_eq!(tilemap.chunk_positions().count(), 1);
+
+        tilemap.set_history_limit(0);
+        assert!(!tilemap.can_undo() && !tilemap.can_redo());
+    }
+}
@@ -0,0 +1,183 @@
+use std::io;
+
+use serde::Deserialize;
+
+use super::{
+    check_tile_size, pixel_tilemap, set_map_tile, MapObject2d, MapTile2d, ObjectKind, RawObject,
+};
+use crate::col2d::{Tilemap, Vec2};
+
+/// A project made in [LDtk](https://ldtk.io), read from its JSON format.
+///
+/// Levels and layers are looked up by their identifiers. Coordinates are local to the level.
+/// Levels saved in separate files are not loaded.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// # use lk_math::vector::V2i32;
+/// let project = LdtkProject::from_json(
+///     r#"{ "levels": [ { "identifier": "Level_0", "layerInstances": [
+///         { "__identifier": "Collisions", "__type": "IntGrid", "__cWid": 2, "__gridSize": 8,
+///           "intGridCsv": [0, 1, 1, 0] }
+///     ] } ] }"#,
+/// )
+/// .unwrap();
+/// let tilemap = project
+///     .tilemap("Level_0", "Collisions", |tile| tile.id as u8)
+///     .unwrap();
+/// assert_eq!(tilemap.get_tile(V2i32::from_xy(0, -1)), 1);
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct LdtkProject {
+    levels: Vec<Level>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Level {
+    identifier: String,
+    #[serde(rename = "layerInstances", default)]
+    layers: Option<Vec<Layer>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Layer {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__type")]
+    kind: String,
+    #[serde(rename = "__cWid")]
+    width: u32,
+    #[serde(rename = "__gridSize")]
+    grid_size: f32,
+    #[serde(rename = "pxTotalOffsetX", default)]
+    offset_x: f32,
+    #[serde(rename = "pxTotalOffsetY", default)]
+    offset_y: f32,
+    #[serde(rename = "intGridCsv", default)]
+    int_grid: Vec<u32>,
+    #[serde(rename = "gridTiles", default)]
+    grid_tiles: Vec<Tile>,
+    #[serde(rename = "autoLayerTiles", default)]
+    auto_layer_tiles: Vec<Tile>,
+    #[serde(rename = "entityInstances", default)]
+    entities: Vec<Entity>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Tile {
+    px: [f32; 2],
+    t: u32,
+    /// Bit 0 flips along `x`, bit 1 along `y`.
+    #[serde(default)]
+    f: u8,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Entity {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__pivot", default)]
+    pivot: [f32; 2],
+    px: [f32; 2],
+    width: f32,
+    height: f32,
+}
+
+impl LdtkProject {
+    pub fn from_json(json: &str) -> io::Result<Self> {
+        let project: Self = serde_json::from_str(json)?;
+        let layers = project
+            .levels
+            .iter()
+            .flat_map(|level| level.layers.iter().flatten());
+        for layer in layers {
+            check_tile_size(Vec2::splat(layer.grid_size))?;
+        }
+        Ok(project)
+    }
+
+    pub fn level_names(&self) -> impl Iterator<Item = &str> {
+        self.levels.iter().map(|level| level.identifier.as_str())
+    }
+
+    fn layer(&self, level: &str, layer: &str) -> Option<&Layer> {
+        self.levels
+            .iter()
+            .find(|l| l.identifier == level)?
+            .layers
+            .as_ref()?
+            .iter()
+            .find(|l| l.identifier == layer)
+    }
+
+    /// Reads an IntGrid, Tiles or AutoLayer layer, `None` if there is no such layer.
+    ///
+    /// `map_tile` decides the id of each placed tile, [MapTile2d::id] is the IntGrid value or the
+    /// tile id in the tileset. Tiles mapped to `0` are left empty.
+    pub fn tilemap(
+        &self,
+        level: &str,
+        layer: &str,
+        mut map_tile: impl FnMut(MapTile2d) -> u8,
+    ) -> Option<Tilemap> {
+        let layer = self.layer(level, layer)?;
+        let tile_size = Vec2::splat(layer.grid_size);
+        let mut tilemap = pixel_tilemap(tile_size, Vec2::new(layer.offset_x, layer.offset_y));
+        match layer.kind.as_str() {
+            "IntGrid" => {
+                let width = layer.width.max(1);
+                for (i, &value) in layer.int_grid.iter().enumerate() {
+                    if value == 0 {
+                        continue;
+                    }
+                    let col = (i as u32 % width) as i32;
+                    let row = (i as u32 / width) as i32;
+                    set_map_tile(&mut tilemap, col, row, MapTile2d::new(value), &mut map_tile);
+                }
+            }
+            "Tiles" | "AutoLayer" => {
+                for tile in layer.grid_tiles.iter().chain(&layer.auto_layer_tiles) {
+                    let placed = MapTile2d {
+                        flip_x: tile.f & 1 != 0,
+                        flip_y: tile.f & 2 != 0,
+                        ..MapTile2d::new(tile.t)
+                    };
+                    let col = (tile.px[0] / layer.grid_size).floor() as i32;
+                    let row = (tile.px[1] / layer.grid_size).floor() as i32;
+                    set_map_tile(&mut tilemap, col, row, placed, &mut map_tile);
+                }
+            }
+            _ => return None,
+        }
+        Some(tilemap)
+    }
+
+    /// Reads an Entities layer as rectangles, `None` if there is no such layer.
+    pub fn objects(&self, level: &str, layer: &str) -> Option<Vec<MapObject2d>> {
+        let layer = self.layer(level, layer)?;
+        if layer.kind != "Entities" {
+            return None;
+        }
+        let offset = Vec2::new(layer.offset_x, layer.offset_y);
+        Some(
+            layer
+                .entities
+                .iter()
+                .map(|entity| {
+                    let size = Vec2::new(entity.width, entity.height);
+                    let pivot = Vec2::from(entity.pivot);
+                    RawObject {
+                        name: entity.identifier.clone(),
+                        class: entity.identifier.clone(),
+                        pos: Vec2::from(entity.px) - pivot * size,
+                        size,
+                        rotation: 0.0,
+                        kind: ObjectKind::Rect,
+                    }
+                    .into_map_object(offset)
+                })
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,182 @@
+//! Importers for maps made in level editors.
+//!
+//! Editors use pixels with `y` pointing down, imported maps keep pixel units but flip `y`, so
+//! pixel `(x, y)` is world `(x, -y)` and map row `r` is tile row `-r`.
+
+use std::io;
+
+use lk_math::vector::V2i32;
+
+use super::{TileShape2d, Tilemap};
+use crate::col2d::{convex_colliders, signed_area, Poly2d, Rotor2d, Shape2d, Transform2d, Vec2};
+
+mod ldtk;
+mod tiled;
+
+pub use ldtk::*;
+pub use tiled::*;
+
+/// Number of sides of polygons approximating ellipses.
+const ELLIPSE_SEGMENTS: usize = 16;
+
+/// A tile placed in an editor, passed to the callback deciding its tile id in the [Tilemap].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MapTile2d {
+    /// Tile id as stored by the editor, without the flip flags.
+    pub id: u32,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    /// Mirrored across the diagonal from the top-left to the bottom-right corner, combined with
+    /// the other flips this rotates the tile by 90°.
+    pub flip_diagonal: bool,
+}
+
+impl MapTile2d {
+    pub fn new(id: u32) -> Self {
+        Self {
+            id,
+            flip_x: false,
+            flip_y: false,
+            flip_diagonal: false,
+        }
+    }
+
+    /// `base` transformed like this tile, see [TileShape2d::flipped].
+    pub fn shape(&self, base: &TileShape2d) -> TileShape2d {
+        base.flipped(self.flip_x, self.flip_y, self.flip_diagonal)
+    }
+}
+
+/// An object placed in an editor with its colliders in world space.
+///
+/// Polygons are split into convex pieces and polylines into one thin box per segment, so a single
+/// object can have several colliders. Points have none.
+#[derive(Debug, Clone)]
+pub struct MapObject2d {
+    pub name: String,
+    /// Class or type of the object, the identifier of the entity in LDtk.
+    pub class: String,
+    pub shapes: Vec<(Shape2d, Transform2d)>,
+}
+
+/// Geometry of an object in editor pixels, relative to its position.
+#[derive(Debug, Clone)]
+enum ObjectKind {
+    Rect,
+    Ellipse,
+    Point,
+    Polygon(Vec<Vec2>),
+    Polyline(Vec<Vec2>),
+    /// Tile objects are anchored at their bottom-left corner.
+    Tile,
+}
+
+#[derive(Debug, Clone)]
+struct RawObject {
+    name: String,
+    class: String,
+    pos: Vec2,
+    size: Vec2,
+    /// Clockwise, in degrees.
+    rotation: f32,
+    kind: ObjectKind,
+}
+
+impl RawObject {
+    fn into_map_object(self, offset: Vec2) -> MapObject2d {
+        let pos = to_world(self.pos + offset);
+        let angle = -self.rotation.to_radians();
+        let rot = Rotor2d::from_angle(angle);
+        let at = |local: Vec2| Transform2d::from_translation(pos + rot * local).with_angle(angle);
+        let halfsize = 0.5 * self.size;
+
+        let shapes = match self.kind {
+            ObjectKind::Rect => vec![(
+                Shape2d::rect(halfsize.x, halfsize.y),
+                at(Vec2::new(halfsize.x, -halfsize.y)),
+            )],
+            ObjectKind::Tile => vec![(
+                Shape2d::rect(halfsize.x, halfsize.y),
+                at(Vec2::new(halfsize.x, halfsize.y)),
+            )],
+            ObjectKind::Ellipse => {
+                let shape = if halfsize.x == halfsize.y {
+                    Shape2d::ball(halfsize.x)
+                } else {
+                    let points: Vec<Vec2> = Poly2d::regular(ELLIPSE_SEGMENTS, 1.0)
+                        .points
+                        .into_iter()
+                        .map(|p| p * halfsize)
+                        .collect();
+                    Shape2d::poly(&points)
+                };
+                vec![(shape, at(Vec2::new(halfsize.x, -halfsize.y)))]
+            }
+            ObjectKind::Point => Vec::new(),
+            ObjectKind::Polygon(points) => {
+                let mut points: Vec<Vec2> = points.into_iter().map(to_world).collect();
+                if signed_area(&points) < 0.0 {
+                    points.reverse();
+                }
+                convex_colliders(&[points])
+                    .into_iter()
+                    .map(|(poly, offset)| (Shape2d::Poly2d(poly), at(offset)))
+                    .collect()
+            }
+            ObjectKind::Polyline(points) => points
+                .windows(2)
+                .map(|segment| {
+                    let (a, b) = (to_world(segment[0]), to_world(segment[1]));
+                    let delta = b - a;
+                    let transform = at(0.5 * (a + b));
+                    let transform = transform.with_angle(angle + delta.y.atan2(delta.x));
+                    (Shape2d::rect(0.5 * delta.length(), 0.0), transform)
+                })
+                .collect(),
+        };
+
+        MapObject2d {
+            name: self.name,
+            class: self.class,
+            shapes,
+        }
+    }
+}
+
+fn to_world(pixel: Vec2) -> Vec2 {
+    Vec2::new(pixel.x, -pixel.y)
+}
+
+/// `tile_size` read from a map, fails unless both dimensions are positive, see
+/// [Tilemap::with_tile_size].
+fn check_tile_size(tile_size: Vec2) -> io::Result<Vec2> {
+    if tile_size.is_finite() && tile_size.x > 0.0 && tile_size.y > 0.0 {
+        Ok(tile_size)
+    } else {
+        Err(invalid_data("tile size must be positive"))
+    }
+}
+
+/// Empty tilemap whose tile `(col, -row)` covers the map cell `(col, row)`.
+fn pixel_tilemap(tile_size: Vec2, offset: Vec2) -> Tilemap {
+    Tilemap::default()
+        .with_tile_size(tile_size)
+        .with_origin(to_world(offset + 0.5 * tile_size))
+}
+
+fn set_map_tile(
+    tilemap: &mut Tilemap,
+    col: i32,
+    row: i32,
+    tile: MapTile2d,
+    map_tile: &mut impl FnMut(MapTile2d) -> u8,
+) {
+    let id = map_tile(tile);
+    if id != 0 {
+        tilemap.set_tile(V2i32::from_xy(col, -row), id);
+    }
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
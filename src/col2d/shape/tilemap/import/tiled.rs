@@ -0,0 +1,535 @@
+use std::io;
+
+use serde::Deserialize;
+
+use super::{
+    check_tile_size, invalid_data, pixel_tilemap, set_map_tile, MapObject2d, MapTile2d, ObjectKind,
+    RawObject,
+};
+use crate::col2d::{Tilemap, Vec2};
+
+const FLIP_X: u32 = 0x8000_0000;
+const FLIP_Y: u32 = 0x4000_0000;
+const FLIP_DIAGONAL: u32 = 0x2000_0000;
+/// Only meaningful for hexagonal maps, ignored.
+const ROTATE_120: u32 = 0x1000_0000;
+
+/// A map made in [Tiled](https://www.mapeditor.org), read from the TMX or JSON format.
+///
+/// Layers are looked up by name, layers in groups are included with the group offsets applied.
+/// Tile data must be CSV, a JSON array or uncompressed base64. Finite and infinite maps are
+/// supported.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// # use lk_math::vector::V2i32;
+/// let map = TiledMap::from_json(
+///     r#"{
+///         "tilewidth": 16, "tileheight": 16,
+///         "layers": [
+///             { "type": "tilelayer", "name": "ground", "width": 2, "data": [1, 0, 0, 1] },
+///             { "type": "objectgroup", "name": "walls", "objects": [
+///                 { "name": "wall", "x": 0, "y": 0, "width": 32, "height": 8 }
+///             ] }
+///         ]
+///     }"#,
+/// )
+/// .unwrap();
+/// let tilemap = map.tilemap("ground", |tile| tile.id as u8).unwrap();
+/// assert_eq!(tilemap.get_tile(V2i32::from_xy(1, -1)), 1);
+/// let walls = map.objects("walls").unwrap();
+/// assert_eq!(walls[0].shapes[0].1.pos, Vec2::new(16.0, -4.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TiledMap {
+    tile_size: Vec2,
+    layers: Vec<Layer>,
+}
+
+#[derive(Debug, Clone)]
+struct Layer {
+    name: String,
+    offset: Vec2,
+    data: LayerData,
+}
+
+#[derive(Debug, Clone)]
+enum LayerData {
+    Tiles(Vec<TileChunk>),
+    Objects(Vec<RawObject>),
+}
+
+/// Rectangle of tiles, the whole layer for finite maps.
+#[derive(Debug, Clone)]
+struct TileChunk {
+    x: i32,
+    y: i32,
+    width: u32,
+    gids: Vec<u32>,
+}
+
+impl TileChunk {
+    fn new(x: i32, y: i32, width: u32, gids: Vec<u32>) -> io::Result<Self> {
+        if width == 0 {
+            return Err(invalid_data("tile layer width must be positive"));
+        }
+        Ok(Self { x, y, width, gids })
+    }
+}
+
+impl TiledMap {
+    pub fn from_json(json: &str) -> io::Result<Self> {
+        let map: JsonMap = serde_json::from_str(json)?;
+        let mut layers = Vec::new();
+        for layer in map.layers {
+            layer.collect(Vec2::ZERO, &mut layers)?;
+        }
+        Ok(Self {
+            tile_size: check_tile_size(Vec2::new(map.tilewidth, map.tileheight))?,
+            layers,
+        })
+    }
+
+    pub fn from_tmx(tmx: &str) -> io::Result<Self> {
+        let document = roxmltree::Document::parse(tmx).map_err(|e| invalid_data(e.to_string()))?;
+        let map = document.root_element();
+        if !map.has_tag_name("map") {
+            return Err(invalid_data("expected a map element"));
+        }
+        let mut layers = Vec::new();
+        collect_tmx_layers(map, Vec2::ZERO, &mut layers)?;
+        Ok(Self {
+            tile_size: check_tile_size(Vec2::new(
+                parse_attribute(map, "tilewidth")?,
+                parse_attribute(map, "tileheight")?,
+            ))?,
+            layers,
+        })
+    }
+
+    /// Size of a tile in pixels.
+    pub fn tile_size(&self) -> Vec2 {
+        self.tile_size
+    }
+
+    pub fn layer_names(&self) -> impl Iterator<Item = &str> {
+        self.layers.iter().map(|layer| layer.name.as_str())
+    }
+
+    /// Reads the tile layer `layer`, `None` if there is no such tile layer.
+    ///
+    /// `map_tile` decides the id of each placed tile, [MapTile2d::id] is the global tile id, i.e.
+    /// including the `firstgid` of its tileset. Tiles mapped to `0` are left empty.
+    pub fn tilemap(
+        &self,
+        layer: &str,
+        mut map_tile: impl FnMut(MapTile2d) -> u8,
+    ) -> Option<Tilemap> {
+        let (chunks, offset) = self.layers.iter().find_map(|l| match &l.data {
+            LayerData::Tiles(chunks) if l.name == layer => Some((chunks, l.offset)),
+            _ => None,
+        })?;
+        let mut tilemap = pixel_tilemap(self.tile_size, offset);
+        for chunk in chunks {
+            for (i, &gid) in chunk.gids.iter().enumerate() {
+                if gid == 0 {
+                    continue;
+                }
+                let tile = MapTile2d {
+                    id: gid & !(FLIP_X | FLIP_Y | FLIP_DIAGONAL | ROTATE_120),
+                    flip_x: gid & FLIP_X != 0,
+                    flip_y: gid & FLIP_Y != 0,
+                    flip_diagonal: gid & FLIP_DIAGONAL != 0,
+                };
+                let col = chunk.x + (i as u32 % chunk.width) as i32;
+                let row = chunk.y + (i as u32 / chunk.width) as i32;
+                set_map_tile(&mut tilemap, col, row, tile, &mut map_tile);
+            }
+        }
+        Some(tilemap)
+    }
+
+    /// Reads the object layer `layer`, `None` if there is no such object layer.
+    pub fn objects(&self, layer: &str) -> Option<Vec<MapObject2d>> {
+        self.layers.iter().find_map(|l| match &l.data {
+            LayerData::Objects(objects) if l.name == layer => Some(
+                objects
+                    .iter()
+                    .map(|object| object.clone().into_map_object(l.offset))
+                    .collect(),
+            ),
+            _ => None,
+        })
+    }
+}
+
+/// Decodes the tile ids of a layer or a chunk.
+fn decode_gids(
+    encoding: Option<&str>,
+    compression: Option<&str>,
+    text: &str,
+) -> io::Result<Vec<u32>> {
+    if compression.is_some_and(|c| !c.is_empty()) {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "compressed tile data is not supported",
+        ));
+    }
+    match encoding {
+        Some("csv") => text
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse().map_err(|_| invalid_data("invalid tile id")))
+            .collect(),
+        Some("base64") => {
+            let bytes = decode_base64(text)?;
+            if bytes.len() % 4 != 0 {
+                return Err(invalid_data("tile data is not a multiple of 4 bytes"));
+            }
+            Ok(bytes
+                .chunks_exact(4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect())
+        }
+        _ => Err(invalid_data("unknown tile data encoding")),
+    }
+}
+
+fn decode_base64(text: &str) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in text
+        .bytes()
+        .filter(|c| !c.is_ascii_whitespace() && *c != b'=')
+    {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return Err(invalid_data("invalid base64")),
+        };
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+#[derive(Deserialize)]
+struct JsonMap {
+    tilewidth: f32,
+    tileheight: f32,
+    layers: Vec<JsonLayer>,
+}
+
+#[derive(Deserialize)]
+struct JsonLayer {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    offsetx: f32,
+    #[serde(default)]
+    offsety: f32,
+    #[serde(default)]
+    width: u32,
+    data: Option<JsonTileData>,
+    encoding: Option<String>,
+    compression: Option<String>,
+    chunks: Option<Vec<JsonChunk>>,
+    objects: Option<Vec<JsonObject>>,
+    layers: Option<Vec<JsonLayer>>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonTileData {
+    Array(Vec<u32>),
+    Encoded(String),
+}
+
+#[derive(Deserialize)]
+struct JsonChunk {
+    x: i32,
+    y: i32,
+    width: u32,
+    data: JsonTileData,
+}
+
+#[derive(Deserialize)]
+struct JsonObject {
+    #[serde(default)]
+    name: String,
+    #[serde(default, rename = "type", alias = "class")]
+    class: String,
+    x: f32,
+    y: f32,
+    #[serde(default)]
+    width: f32,
+    #[serde(default)]
+    height: f32,
+    #[serde(default)]
+    rotation: f32,
+    #[serde(default)]
+    ellipse: bool,
+    #[serde(default)]
+    point: bool,
+    polygon: Option<Vec<JsonPoint>>,
+    polyline: Option<Vec<JsonPoint>>,
+    gid: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct JsonPoint {
+    x: f32,
+    y: f32,
+}
+
+impl JsonLayer {
+    fn collect(self, offset: Vec2, layers: &mut Vec<Layer>) -> io::Result<()> {
+        let offset = offset + Vec2::new(self.offsetx, self.offsety);
+        let data = match self.kind.as_str() {
+            "tilelayer" => {
+                let gids = |data: JsonTileData| match data {
+                    JsonTileData::Array(gids) => Ok(gids),
+                    JsonTileData::Encoded(text) => {
+                        decode_gids(self.encoding.as_deref(), self.compression.as_deref(), &text)
+                    }
+                };
+                let mut chunks = Vec::new();
+                if let Some(data) = self.data {
+                    chunks.push(TileChunk::new(0, 0, self.width, gids(data)?)?);
+                }
+                for chunk in self.chunks.into_iter().flatten() {
+                    chunks.push(TileChunk::new(
+                        chunk.x,
+                        chunk.y,
+                        chunk.width,
+                        gids(chunk.data)?,
+                    )?);
+                }
+                LayerData::Tiles(chunks)
+            }
+            "objectgroup" => LayerData::Objects(
+                self.objects
+                    .into_iter()
+                    .flatten()
+                    .map(JsonObject::into_raw)
+                    .collect(),
+            ),
+            "group" => {
+                for layer in self.layers.into_iter().flatten() {
+                    layer.collect(offset, layers)?;
+                }
+                return Ok(());
+            }
+            _ => return Ok(()),
+        };
+        layers.push(Layer {
+            name: self.name,
+            offset,
+            data,
+        });
+        Ok(())
+    }
+}
+
+impl JsonObject {
+    fn into_raw(self) -> RawObject {
+        let points =
+            |points: Vec<JsonPoint>| points.into_iter().map(|p| Vec2::new(p.x, p.y)).collect();
+        let kind = if let Some(polygon) = self.polygon {
+            ObjectKind::Polygon(points(polygon))
+        } else if let Some(polyline) = self.polyline {
+            ObjectKind::Polyline(points(polyline))
+        } else if self.ellipse {
+            ObjectKind::Ellipse
+        } else if self.point {
+            ObjectKind::Point
+        } else if self.gid.is_some() {
+            ObjectKind::Tile
+        } else {
+            ObjectKind::Rect
+        };
+        RawObject {
+            name: self.name,
+            class: self.class,
+            pos: Vec2::new(self.x, self.y),
+            size: Vec2::new(self.width, self.height),
+            rotation: self.rotation,
+            kind,
+        }
+    }
+}
+
+fn collect_tmx_layers(
+    parent: roxmltree::Node,
+    offset: Vec2,
+    layers: &mut Vec<Layer>,
+) -> io::Result<()> {
+    for node in parent.children().filter(|n| n.is_element()) {
+        let name = node.attribute("name").unwrap_or_default().to_string();
+        let offset = offset
+            + Vec2::new(
+                parse_attribute_or(node, "offsetx", 0.0)?,
+                parse_attribute_or(node, "offsety", 0.0)?,
+            );
+        let data = match node.tag_name().name() {
+            "layer" => {
+                let width = parse_attribute_or(node, "width", 1)?;
+                let Some(data) = node.children().find(|n| n.has_tag_name("data")) else {
+                    continue;
+                };
+                let mut chunks = Vec::new();
+                let gids = |node: roxmltree::Node| -> io::Result<Vec<u32>> {
+                    let tiles: Vec<roxmltree::Node> =
+                        node.children().filter(|n| n.has_tag_name("tile")).collect();
+                    if data.attribute("encoding").is_none() {
+                        tiles
+                            .iter()
+                            .map(|tile| parse_attribute_or(*tile, "gid", 0))
+                            .collect()
+                    } else {
+                        decode_gids(
+                            data.attribute("encoding"),
+                            data.attribute("compression"),
+                            node.text().unwrap_or_default(),
+                        )
+                    }
+                };
+                let tmx_chunks: Vec<roxmltree::Node> = data
+                    .children()
+                    .filter(|n| n.has_tag_name("chunk"))
+                    .collect();
+                if tmx_chunks.is_empty() {
+                    chunks.push(TileChunk::new(0, 0, width, gids(data)?)?);
+                }
+                for chunk in tmx_chunks {
+                    chunks.push(TileChunk::new(
+                        parse_attribute(chunk, "x")?,
+                        parse_attribute(chunk, "y")?,
+                        parse_attribute(chunk, "width")?,
+                        gids(chunk)?,
+                    )?);
+                }
+                LayerData::Tiles(chunks)
+            }
+            "objectgroup" => LayerData::Objects(
+                node.children()
+                    .filter(|n| n.has_tag_name("object"))
+                    .map(tmx_object)
+                    .collect::<io::Result<_>>()?,
+            ),
+            "group" => {
+                collect_tmx_layers(node, offset, layers)?;
+                continue;
+            }
+            _ => continue,
+        };
+        layers.push(Layer { name, offset, data });
+    }
+    Ok(())
+}
+
+fn tmx_object(node: roxmltree::Node) -> io::Result<RawObject> {
+    let child = |name: &str| node.children().find(|n| n.has_tag_name(name));
+    let points = |node: roxmltree::Node| -> io::Result<Vec<Vec2>> {
+        node.attribute("points")
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(|point| {
+                let (x, y) = point
+                    .split_once(',')
+                    .ok_or_else(|| invalid_data("invalid point"))?;
+                let parse = |s: &str| s.parse().map_err(|_| invalid_data("invalid point"));
+                Ok(Vec2::new(parse(x)?, parse(y)?))
+            })
+            .collect()
+    };
+    let kind = if let Some(polygon) = child("polygon") {
+        ObjectKind::Polygon(points(polygon)?)
+    } else if let Some(polyline) = child("polyline") {
+        ObjectKind::Polyline(points(polyline)?)
+    } else if child("ellipse").is_some() {
+        ObjectKind::Ellipse
+    } else if child("point").is_some() {
+        ObjectKind::Point
+    } else if node.has_attribute("gid") {
+        ObjectKind::Tile
+    } else {
+        ObjectKind::Rect
+    };
+    Ok(RawObject {
+        name: node.attribute("name").unwrap_or_default().to_string(),
+        class: node
+            .attribute("class")
+            .or(node.attribute("type"))
+            .unwrap_or_default()
+            .to_string(),
+        pos: Vec2::new(parse_attribute(node, "x")?, parse_attribute(node, "y")?),
+        size: Vec2::new(
+            parse_attribute_or(node, "width", 0.0)?,
+            parse_attribute_or(node, "height", 0.0)?,
+        ),
+        rotation: parse_attribute_or(node, "rotation", 0.0)?,
+        kind,
+    })
+}
+
+fn parse_attribute<T: std::str::FromStr>(node: roxmltree::Node, name: &str) -> io::Result<T> {
+    let value = node
+        .attribute(name)
+        .ok_or_else(|| invalid_data(format!("missing attribute {name}")))?;
+    value
+        .parse()
+        .map_err(|_| invalid_data(format!("invalid attribute {name}")))
+}
+
+fn parse_attribute_or<T: std::str::FromStr>(
+    node: roxmltree::Node,
+    name: &str,
+    default: T,
+) -> io::Result<T> {
+    if node.has_attribute(name) {
+        parse_attribute(node, name)
+    } else {
+        Ok(default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn gid_encodings() {
+        assert_eq!(
+            decode_gids(Some("csv"), None, "\n1,2,\n0,3\n").unwrap(),
+            vec![1, 2, 0, 3]
+        );
+        // 1, 0x80000002 as little-endian bytes.
+        assert_eq!(
+            decode_gids(Some("base64"), Some(""), " AQAAAAIAAIA= ").unwrap(),
+            vec![1, 0x8000_0002]
+        );
+        assert_eq!(
+            decode_gids(Some("base64"), Some("zlib"), "")
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::Unsupported
+        );
+        assert_eq!(
+            decode_gids(Some("base64"), None, "AQA").unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+}
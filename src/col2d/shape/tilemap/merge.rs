@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use lk_math::vector::V2i32;
+
+use super::{TileShape2d, Tilemap, TilemapEvent};
+use crate::col2d::{Box2d, Shape2d, Vec2};
+use crate::utils::rect2::Rect2i32;
+
+/// Static collider covering one or more tiles of a chunk, see [Tilemap::merged_colliders].
+#[derive(Debug, Clone)]
+pub struct TileCollider2d {
+    pub shape: Shape2d,
+    /// World position of the shape's center.
+    pub pos: Vec2,
+    /// Tiles covered by the collider.
+    pub tiles: Rect2i32,
+    pub one_way: bool,
+    pub sensor: bool,
+}
+
+impl Tilemap {
+    /// Colliders of the tiles of a chunk with [TileShape2d::Full] tiles merged into as few
+    /// rectangles as possible.
+    ///
+    /// Colliding with each tile separately catches bodies sliding along a floor on the seams
+    /// between tiles. Merged rectangles leave far fewer seams, a straight floor is split only at
+    /// chunk borders and where its [TileProperties2d](super::TileProperties2d) change. Other
+    /// shapes get a collider per tile.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// # use lk_math::vector::V2i32;
+    /// let mut tilemap = Tilemap::default();
+    /// for x in 0..10 {
+    ///     tilemap.set_tile(V2i32::from_xy(x, 0), 1);
+    ///     tilemap.set_tile(V2i32::from_xy(x, 1), 1);
+    /// }
+    /// let colliders = tilemap.merged_colliders(&V2i32::from_xy(0, 0));
+    /// assert_eq!(colliders.len(), 1);
+    /// assert_eq!(colliders[0].pos, Vec2::new(4.5, 0.5));
+    /// ```
+    pub fn merged_colliders(&self, chunk_pos: &V2i32) -> Vec<TileCollider2d> {
+        let mut colliders = Vec::new();
+        if self.get_chunk(chunk_pos).is_none() {
+            return colliders;
+        }
+        let first = self.chunk_to_tile_pos(chunk_pos);
+        let (width, height) = (self.chunk_size.x(), self.chunk_size.y());
+        let mut merged = vec![false; (width * height) as usize];
+        let tile = |x: i32, y: i32| self.get_tile(first + V2i32::from_xy(x, y));
+        let key = |x: i32, y: i32| {
+            let properties = self.tile_shapes.get(tile(x, y));
+            match properties.shape {
+                TileShape2d::Full => Some((properties.one_way, properties.sensor)),
+                _ => None,
+            }
+        };
+
+        // Greedy meshing, each rectangle grows along x as far as possible, then along y while the
+        // whole row below matches.
+        for y in 0..height {
+            for x in 0..width {
+                if merged[(y * width + x) as usize] {
+                    continue;
+                }
+                let Some(k) = key(x, y) else {
+                    let pos = first + V2i32::from_xy(x, y);
+                    if let Some((shape, center, properties)) = self.tile_collider(pos) {
+                        colliders.push(TileCollider2d {
+                            shape: shape.clone(),
+                            pos: center,
+                            tiles: Rect2i32::min_max(pos, pos),
+                            one_way: properties.one_way,
+                            sensor: properties.sensor,
+                        });
+                    }
+                    continue;
+                };
+                let free = |x: i32, y: i32, merged: &[bool]| {
+                    !merged[(y * width + x) as usize] && key(x, y) == Some(k)
+                };
+                let mut w = 1;
+                while x + w < width && free(x + w, y, &merged) {
+                    w += 1;
+                }
+                let mut h = 1;
+                while y + h < height && (x..x + w).all(|x| free(x, y + h, &merged)) {
+                    h += 1;
+                }
+                for yy in y..y + h {
+                    for xx in x..x + w {
+                        merged[(yy * width + xx) as usize] = true;
+                    }
+                }
+
+                let min = first + V2i32::from_xy(x, y);
+                let max = first + V2i32::from_xy(x + w - 1, y + h - 1);
+                let size = Vec2::new(w as f32, h as f32) * self.tile_size;
+                colliders.push(TileCollider2d {
+                    shape: Shape2d::Box2d(Box2d::new(0.5 * size)),
+                    pos: 0.5 * (self.tile_to_world_pos(&min) + self.tile_to_world_pos(&max)),
+                    tiles: Rect2i32::min_max(min, max),
+                    one_way: k.0,
+                    sensor: k.1,
+                });
+            }
+        }
+        colliders
+    }
+}
+
+/// [Tilemap::merged_colliders] of every chunk, kept up to date from [TilemapEvent]s.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// # use lk_math::vector::V2i32;
+/// let mut tilemap = Tilemap::default();
+/// let mut colliders = MergedTileColliders2d::new(&tilemap);
+///
+/// tilemap.set_tile(V2i32::from_xy(0, 0), 1);
+/// tilemap.set_tile(V2i32::from_xy(1, 0), 1);
+/// let events = std::mem::take(&mut tilemap.events.events);
+/// colliders.update(&tilemap, &events);
+/// assert_eq!(colliders.iter().count(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MergedTileColliders2d {
+    chunks: HashMap<V2i32, Vec<TileCollider2d>>,
+}
+
+impl MergedTileColliders2d {
+    pub fn new(tilemap: &Tilemap) -> Self {
+        let mut colliders = Self::default();
+        for chunk_pos in tilemap.chunk_positions() {
+            colliders.rebuild_chunk(tilemap, &chunk_pos);
+        }
+        colliders
+    }
+
+    /// Regenerates the chunks the events refer to.
+    ///
+    /// Changing the [TileShapeTable2d](super::TileShapeTable2d) notifies no events, use
+    /// [MergedTileColliders2d::new] afterwards.
+    pub fn update<E: std::borrow::Borrow<TilemapEvent>>(
+        &mut self,
+        tilemap: &Tilemap,
+        events: impl IntoIterator<Item = E>,
+    ) {
+        for event in events {
+            match *event.borrow() {
+                TilemapEvent::ChunkCreated(chunk_pos)
+                | TilemapEvent::ChunkChanged(chunk_pos)
+                | TilemapEvent::TilesChanged {
+                    chunk: chunk_pos, ..
+                } => self.rebuild_chunk(tilemap, &chunk_pos),
+                TilemapEvent::ChunkRemoved(chunk_pos) => {
+                    self.chunks.remove(&chunk_pos);
+                }
+            }
+        }
+    }
+
+    pub fn rebuild_chunk(&mut self, tilemap: &Tilemap, chunk_pos: &V2i32) {
+        let colliders = tilemap.merged_colliders(chunk_pos);
+        if colliders.is_empty() {
+            self.chunks.remove(chunk_pos);
+        } else {
+            self.chunks.insert(*chunk_pos, colliders);
+        }
+    }
+
+    pub fn chunk(&self, chunk_pos: &V2i32) -> &[TileCollider2d] {
+        self.chunks.get(chunk_pos).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &TileCollider2d> {
+        self.chunks.values().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::col2d::{PointQuery2d, TileProperties2d, TileShapeTable2d};
+
+    fn v(x: i32, y: i32) -> V2i32 {
+        V2i32::from_xy(x, y)
+    }
+
+    #[test_log::test]
+    fn merged_colliders_cover_the_tiles() {
+        let mut tilemap = Tilemap::default()
+            .with_tile_size(Vec2::new(2.0, 1.0))
+            .with_chunk_size(v(8, 8))
+            .with_tile_shapes(
+                TileShapeTable2d::default()
+                    .with(
+                        2,
+                        TileShape2d::Slope45 {
+                            flip_x: false,
+                            flip_y: false,
+                        },
+                    )
+                    .with(
+                        3,
+                        TileProperties2d::new(TileShape2d::Full).with_one_way(true),
+                    ),
+            );
+        // An L of solid tiles, a slope and a one-way platform on top of it.
+        for x in 0..6 {
+            tilemap.set_tile(v(x, 0), 1);
+        }
+        for y in 1..4 {
+            tilemap.set_tile(v(0, y), 1);
+        }
+        tilemap.set_tile(v(1, 1), 2);
+        tilemap.set_tile(v(4, 1), 3);
+        tilemap.set_tile(v(5, 1), 3);
+        // Crosses into the next chunk.
+        for x in 6..10 {
+            tilemap.set_tile(v(x, 0), 1);
+        }
+
+        let colliders = tilemap.merged_colliders(&v(0, 0));
+        assert_eq!(colliders.len(), 4, "{colliders:?}");
+        let one_way: Vec<_> = colliders.iter().filter(|c| c.one_way).collect();
+        assert_eq!(one_way.len(), 1);
+        assert_eq!(one_way[0].tiles, Rect2i32::min_max(v(4, 1), v(5, 1)));
+        assert!(colliders
+            .iter()
+            .any(|c| c.tiles == Rect2i32::min_max(v(0, 0), v(7, 0))));
+
+        for y in -1..5 {
+            for x in -1..12 {
+                let point = Vec2::new(2.0 * x as f32 + 0.3, y as f32 - 0.2);
+                let merged = [v(0, 0), v(1, 0)].iter().any(|chunk| {
+                    tilemap
+                        .merged_colliders(chunk)
+                        .iter()
+                        .any(|c| c.shape.contains_point_t(&c.pos, point))
+                });
+                assert_eq!(merged, tilemap.contains_point(point), "{point}");
+            }
+        }
+    }
+
+    #[test_log::test]
+    fn incremental_updates() {
+        let mut tilemap = Tilemap::default();
+        for x in 0..20 {
+            tilemap.set_tile(v(x, 0), 1);
+        }
+        let mut colliders = MergedTileColliders2d::new(&tilemap);
+        tilemap.events.events.clear();
+        assert_eq!(colliders.iter().count(), 2);
+
+        tilemap.set_tile(v(5, 0), 0);
+        colliders.update(&tilemap, &tilemap.events.events);
+        tilemap.events.events.clear();
+        assert_eq!(colliders.chunk(&v(0, 0)).len(), 2);
+
+        tilemap.batch(|tilemap| {
+            for x in 16..20 {
+                tilemap.remove_tile(v(x, 0));
+            }
+            tilemap.set_tile(v(0, -1), 1);
+        });
+        let events = std::mem::take(&mut tilemap.events.events);
+        colliders.update(&tilemap, &events);
+        assert!(colliders.chunk(&v(1, 0)).is_empty());
+        assert_eq!(colliders.chunk(&v(0, -1)).len(), 1);
+        assert_eq!(colliders.iter().count(), 3);
+    }
+}
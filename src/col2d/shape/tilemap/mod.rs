@@ -0,0 +1,938 @@
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use lk_math::arraynd::Array2d;
+use lk_math::modular::ModularDecompose;
+use lk_math::vector::V2i32;
+use round_to::*;
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+use super::{
+    Aabb2d, Ball, BoundingBox2d, Box2d, Feature2d, PenetratesRel2d, Point, PointProjection2d,
+    PointQuery2d, Poly2d, QueryResult2d, SdfvCommonRel2d, Shape2d, Transform2d, Transformation2d,
+    UnknownReason, Vec2,
+};
+use crate::col::DeltaTransform;
+use crate::col2d::visibility2d::{angles_around, cast_rays, circle_directions};
+
+use crate::error;
+use crate::utils::publisher::{Ledger, Publisher};
+use crate::utils::rect2::Rect2i32;
+
+mod binary;
+mod grid;
+mod history;
+mod import;
+mod merge;
+mod raycast;
+mod tile_shape;
+
+use history::{TileEdit, TilemapHistory};
+
+pub use grid::*;
+pub use import::*;
+pub use merge::*;
+pub use raycast::*;
+pub use tile_shape::*;
+
+const DEFAULT_CHUNK_SIZE: V2i32 = V2i32::from_xy(16, 16);
+
+#[derive(Debug)]
+pub enum TilemapEvent {
+    ChunkCreated(V2i32),
+    ChunkRemoved(V2i32),
+    ChunkChanged(V2i32),
+    /// Tiles of an existing chunk changed in a [batch](Tilemap::begin_batch), `dirty` covers
+    /// all of them in tile coordinates.
+    TilesChanged {
+        chunk: V2i32,
+        dirty: Rect2i32,
+    },
+}
+
+/// Grid of tiles stored in chunks, tiles with value `0` are empty and others are solid.
+///
+/// Tile `(x, y)` is the axis-aligned rectangle of [Tilemap::tile_size] centered at
+/// `origin + (x, y) * tile_size`. By default tiles are unit squares centered at integer positions.
+/// Any other placement, e.g. rotation, is applied through the `_t` queries' transformation on
+/// top of that.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// # use lk_math::vector::V2i32;
+/// let tilemap = Tilemap::default()
+///     .with_tile_size(Vec2::splat(8.0))
+///     .with_origin(Vec2::new(4.0, 4.0));
+/// assert_eq!(tilemap.world_to_tile_pos(&Vec2::new(15.0, 1.0)), V2i32::from_xy(1, 0));
+/// assert_eq!(tilemap.tile_to_world_pos(&V2i32::from_xy(1, 0)), Vec2::new(12.0, 4.0));
+/// ```
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+pub struct Tilemap {
+    // NOTE(lubo): Have to use `std::collections::HashMap` because `serde_as` is not implemented for
+    #[serde_as(as = "Vec<(_, _)>")]
+    chunks: std::collections::HashMap<V2i32, Chunk>,
+    #[serde(default = "default_tile_size")]
+    tile_size: Vec2,
+    #[serde(default)]
+    origin: Vec2,
+    #[serde(default = "default_chunk_size")]
+    chunk_size: V2i32,
+    #[serde(default)]
+    tile_shapes: TileShapeTable2d,
+    /// Colliders of every tile id at [Tilemap::tile_size], built on first use, see
+    /// [Tilemap::tile_collider].
+    #[serde(skip)]
+    tile_colliders: OnceLock<Vec<Option<(Shape2d, Vec2)>>>,
+    #[serde(skip)]
+    history: TilemapHistory,
+    #[serde(skip)]
+    // pub events: impl Publisher<TilemapEvent>,
+    pub events: Ledger<TilemapEvent>,
+}
+
+fn default_tile_size() -> Vec2 {
+    Vec2::ONE
+}
+
+fn default_chunk_size() -> V2i32 {
+    DEFAULT_CHUNK_SIZE
+}
+
+impl Default for Tilemap {
+    fn default() -> Self {
+        Self {
+            chunks: Default::default(),
+            tile_size: default_tile_size(),
+            origin: Vec2::ZERO,
+            chunk_size: default_chunk_size(),
+            tile_shapes: Default::default(),
+            tile_colliders: Default::default(),
+            history: Default::default(),
+            events: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Chunk {
+    pub array2d: Array2d<u8>,
+}
+
+impl Default for Chunk {
+    fn default() -> Self {
+        Self::with_size(DEFAULT_CHUNK_SIZE)
+    }
+}
+
+impl Chunk {
+    pub fn with_size(size: V2i32) -> Self {
+        Self {
+            array2d: Array2d::with_dimensions(size.x() as usize, size.y() as usize, 0),
+        }
+    }
+}
+
+impl Tilemap {
+    /// Size of a tile in world units.
+    ///
+    /// # Panics
+    /// If either dimension is not positive.
+    pub fn with_tile_size(mut self, tile_size: Vec2) -> Self {
+        assert!(tile_size.x > 0.0 && tile_size.y > 0.0);
+        self.tile_size = tile_size;
+        self.tile_colliders = OnceLock::new();
+        self
+    }
+
+    /// World position of the center of tile `(0, 0)`.
+    pub fn with_origin(mut self, origin: Vec2) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Number of tiles in a chunk along each axis.
+    ///
+    /// # Panics
+    /// If either dimension is not positive or if the tilemap already has tiles.
+    pub fn with_chunk_size(mut self, chunk_size: V2i32) -> Self {
+        assert!(chunk_size.x() > 0 && chunk_size.y() > 0);
+        assert!(
+            self.chunks.is_empty(),
+            "chunk size must be set before adding tiles"
+        );
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    pub fn tile_size(&self) -> Vec2 {
+        self.tile_size
+    }
+
+    pub fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    pub fn chunk_size(&self) -> V2i32 {
+        self.chunk_size
+    }
+
+    /// Converts a world position to tilemap space, where tiles are unit squares centered at
+    /// integer positions.
+    fn world_to_grid(&self, world_pos: Vec2) -> Vec2 {
+        (world_pos - self.origin) / self.tile_size
+    }
+
+    fn grid_to_world(&self, grid_pos: Vec2) -> Vec2 {
+        self.origin + grid_pos * self.tile_size
+    }
+
+    pub fn world_to_tile_pos(&self, world_pos: &Vec2) -> V2i32 {
+        let grid_pos = self.world_to_grid(*world_pos);
+        V2i32::from_xy(grid_pos.x.round_to_i32(), grid_pos.y.round_to_i32())
+    }
+
+    /// World position of the center of a tile.
+    pub fn tile_to_world_pos(&self, tile_pos: &V2i32) -> Vec2 {
+        self.grid_to_world(Vec2::new(tile_pos.x() as f32, tile_pos.y() as f32))
+    }
+    /// Tiles overlapping `shape` transformed by `t`, see [BoundingBox2d].
+    pub fn quantize<S, T>(&self, shape: &S, t: &T) -> Rect2i32
+    where
+        S: BoundingBox2d<T>,
+        T: Transformation2d,
+    {
+        self.quantize_aabb(&shape.bounding_box_t(t))
+    }
+
+    /// Tiles overlapping a world-space bounding box, see [BoundingBox2d].
+    pub fn quantize_aabb(&self, aabb: &Aabb2d) -> Rect2i32 {
+        Rect2i32 {
+            min: self.world_to_tile_pos(&aabb.min),
+            max: self.world_to_tile_pos(&aabb.max),
+        }
+    }
+
+    pub fn tile_to_chunk_and_local_pos(&self, tile_pos: &V2i32) -> (V2i32, V2i32) {
+        tile_pos.modular_decompose(self.chunk_size)
+    }
+
+    pub fn tile_to_chunk_pos(&self, tile_pos: &V2i32) -> V2i32 {
+        let (chunk_pos, _) = tile_pos.modular_decompose(self.chunk_size);
+        chunk_pos
+    }
+
+    /// Position of the first tile of a chunk.
+    pub fn chunk_to_tile_pos(&self, chunk_pos: &V2i32) -> V2i32 {
+        V2i32::from_xy(
+            chunk_pos.x() * self.chunk_size.x(),
+            chunk_pos.y() * self.chunk_size.y(),
+        )
+    }
+
+    pub fn get_chunk(&self, chunk_pos: &V2i32) -> Option<&'_ Chunk> {
+        self.chunks.get(chunk_pos)
+    }
+
+    pub fn get_tile(&self, pos: V2i32) -> u8 {
+        let (chunk_pos, local) = pos.modular_decompose(self.chunk_size);
+        if let Some(chunk) = self.chunks.get(&chunk_pos) {
+            *chunk.array2d.get(local).unwrap_or(&0)
+        } else {
+            0
+        }
+    }
+
+    pub fn set_tile(&mut self, pos: V2i32, tile: u8) {
+        self.write_tile(pos, tile, false);
+    }
+
+    /// Clears a tile and frees its chunk once the chunk is empty.
+    pub fn remove_tile(&mut self, pos: V2i32) {
+        self.write_tile(pos, 0, true);
+    }
+
+    /// Changes a tile, records it in the [history](Tilemap::undo) and notifies [Tilemap::events]
+    /// or the current [batch](Tilemap::begin_batch).
+    fn write_tile(&mut self, pos: V2i32, tile: u8, remove_empty: bool) {
+        let (chunk_pos, local) = pos.modular_decompose(self.chunk_size);
+        let old = self.get_tile(pos);
+        if old == tile {
+            return;
+        }
+        let created = !self.chunks.contains_key(&chunk_pos);
+        let chunk = self
+            .chunks
+            .entry(chunk_pos)
+            .or_insert_with(|| Chunk::with_size(self.chunk_size));
+        chunk.array2d.set(local, tile);
+        let removed = remove_empty && chunk.array2d.data.iter().all(|x| x == &0);
+        if removed {
+            self.chunks.remove(&chunk_pos);
+        }
+
+        self.history.record(TileEdit {
+            pos,
+            old,
+            new: tile,
+        });
+        if let Some(batch) = &mut self.history.batch {
+            batch.touch(chunk_pos, pos, !created);
+        } else if created {
+            self.events.notify(TilemapEvent::ChunkCreated(chunk_pos));
+        } else if removed {
+            self.events.notify(TilemapEvent::ChunkRemoved(chunk_pos));
+        } else {
+            self.events.notify(TilemapEvent::ChunkChanged(chunk_pos));
+        }
+    }
+
+    fn for_tiles_in_rect<F: FnMut(V2i32, u8)>(
+        &self,
+        rect: Rect2i32,
+        dir_x: f32,
+        dir_y: f32,
+        mut f: F,
+    ) {
+        let flip_x = dir_x < 0.0;
+        let flip_y = dir_y < 0.0;
+        if flip_y {
+            if flip_x {
+                for tile_pos in rect.iterate_south_west() {
+                    f(tile_pos, self.get_tile(tile_pos));
+                }
+            } else {
+                for tile_pos in rect.iterate_south_east() {
+                    f(tile_pos, self.get_tile(tile_pos));
+                }
+            }
+        } else if flip_x {
+            for tile_pos in rect.iterate_north_west() {
+                f(tile_pos, self.get_tile(tile_pos));
+            }
+        } else {
+            for tile_pos in rect.iterate_north_east() {
+                f(tile_pos, self.get_tile(tile_pos));
+            }
+        }
+    }
+
+    /// Pushes `col` at `pos` moving along `dir` out of the tiles, see [TileShapeTable2d].
+    ///
+    /// Sensor tiles are ignored and one-way tiles only push up when `col` is not moving up.
+    pub fn simple_resolve_ball(&self, col: &Ball, pos: Vec2, dir: &Vec2) -> Vec2 {
+        self.simple_resolve_t(col, &Transform2d::from_translation(pos), dir)
+    }
+
+    /// See [Tilemap::simple_resolve_ball].
+    pub fn simple_resolve_box(&self, col: &Box2d, pos: Vec2, dir: &Vec2) -> Vec2 {
+        self.simple_resolve_t(col, &Transform2d::from_translation(pos), dir)
+    }
+
+    /// Pushes `col` transformed by `t` moving along `dir` out of the tiles, returns the resolved
+    /// position, see [Tilemap::simple_resolve_ball].
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// # use lk_math::vector::V2i32;
+    /// let mut tilemap = Tilemap::default();
+    /// tilemap.set_tile(V2i32::from_xy(3, 2), 1);
+    /// let plank = Box2d::with_halfdims(0.5, 0.1);
+    /// let t = Transform2d::from_translation(Vec2::new(3.0, 2.9))
+    ///     .with_angle(core::f32::consts::FRAC_PI_2);
+    /// let pos = tilemap.simple_resolve_t(&plank, &t, &Vec2::NEG_Y);
+    /// assert!(pos.abs_diff_eq(Vec2::new(3.0, 3.0), 1e-4));
+    /// ```
+    pub fn simple_resolve_t<S>(&self, col: &S, t: &Transform2d, dir: &Vec2) -> Vec2
+    where
+        S: BoundingBox2d<Transform2d> + PenetratesRel2d<Shape2d, Transform2d>,
+    {
+        let rect = self.quantize(col, t);
+        let mut t = t.clone();
+        self.for_tiles_in_rect(rect, dir.x, dir.y, |p, tile| {
+            if tile == 0 {
+                return;
+            }
+            let Some((b, center, properties)) = self.tile_collider(p) else {
+                return;
+            };
+            if properties.sensor {
+                return;
+            }
+            let rel = t.delta_transform(&Transform2d::from_translation(center));
+            if let Some(error) = col.penetrates_rel(b, &rel) {
+                let error = t.apply(error) - t.apply_origin();
+                // Only landing on a one-way tile counts, pushing sideways or down would snag bodies
+                // passing through.
+                if properties.one_way && (dir.y > 0.0 || error.y <= error.x.abs()) {
+                    return;
+                }
+                t.pos += error;
+            }
+        });
+        t.pos
+    }
+
+    /// Region lit from `viewpoint`, solid tiles are the obstacles, see
+    /// [visibility_polygon](crate::col2d::visibility_polygon).
+    ///
+    /// Rays are cast with [Tilemap::cast_ray_tile] towards the corners of tile colliders that
+    /// are not surrounded by full tiles, see [TileShapeTable2d]. Sensor tiles let light through,
+    /// one-way tiles only block it from above and the tile containing the viewpoint is ignored.
+    pub fn visibility_polygon(&self, viewpoint: Vec2, radius: f32, segments: usize) -> Vec<Vec2> {
+        let rect = self.quantize_aabb(&Aabb2d::from_center_halfsize(
+            viewpoint,
+            Vec2::splat(radius),
+        ));
+        let neighbours = [(1, 0), (-1, 0), (0, 1), (0, -1)].map(|(x, y)| V2i32::from_xy(x, y));
+        let blocks_view = |p: V2i32| {
+            let properties = self.tile_shapes.get(self.get_tile(p));
+            matches!(properties.shape, TileShape2d::Full)
+                && !properties.sensor
+                && !properties.one_way
+        };
+
+        let mut angles: Vec<f32> = circle_directions(segments)
+            .iter()
+            .map(|d| d.to_angle())
+            .collect();
+        for p in rect.iterate() {
+            let Some((shape, center, properties)) = self.tile_collider(p) else {
+                continue;
+            };
+            if properties.sensor || neighbours.iter().all(|&n| blocks_view(p + n)) {
+                continue;
+            }
+            let corners = match shape {
+                Shape2d::Box2d(b) => Poly2d::from(*b).points,
+                Shape2d::Poly2d(poly) => poly.points.clone(),
+                _ => Vec::new(),
+            };
+            for corner in corners {
+                angles.extend(angles_around((center + corner - viewpoint).to_angle()));
+            }
+        }
+
+        cast_rays(viewpoint, radius, angles, |ray, radius| {
+            self.cast_ray_tile_from(ray, radius, true)
+                .map_or(radius, |hit| hit.hit.distance)
+        })
+    }
+
+    // pub fn resolve(&self, character: &mut crate::character::Character) {
+    //     let rect = self.quantize(&character.col, &character.pos);
+    //     self.for_tiles_in_rect(rect, character.vel.x, character.vel.y, |p, t| {
+    //         if t > 0 {
+    //             let b = Box2d::with_halfdims(0.5, 0.5);
+    //             let delta = self.tile_to_world_pos(&p) - character.pos;
+    //             if let Some(error) = character.col.penetrates(&b, &delta) {
+    //                 character.pos -= error;
+    //             }
+    //         }
+    //     });
+    // }
+}
+
+/// Covers all allocated chunks, which may be larger than the occupied tiles.
+impl<T: Transformation2d> BoundingBox2d<T> for Tilemap {
+    fn bounding_box_t(&self, t: &T) -> Aabb2d {
+        let corners = self.chunks.keys().flat_map(|chunk_pos| {
+            let first = self.chunk_to_tile_pos(chunk_pos);
+            let last = first + self.chunk_size - V2i32::from_xy(1, 1);
+            let min = self.tile_to_world_pos(&first) - 0.5 * self.tile_size;
+            let max = self.tile_to_world_pos(&last) + 0.5 * self.tile_size;
+            [min, max, Vec2::new(min.x, max.y), Vec2::new(max.x, min.y)]
+        });
+        Aabb2d::from_points(corners.map(|corner| t.apply(corner))).unwrap_or_else(|| {
+            let origin = t.apply_origin();
+            Aabb2d::new(origin, origin)
+        })
+    }
+}
+
+/// Points are inside the [TileShape2d] of their tile. Projections treat every non-empty tile as
+/// [TileShape2d::Full], the feature is the [Feature2d::Tile] of the solid tile closest to the
+/// projection.
+impl PointQuery2d for Tilemap {
+    fn contains_point(&self, point: Vec2) -> bool {
+        self.tile_collider(self.world_to_tile_pos(&point))
+            .is_some_and(|(shape, center, _)| shape.contains_point_t(&center, point))
+    }
+
+    fn project_point(&self, point: Vec2) -> PointProjection2d {
+        if self.chunks.is_empty() {
+            return PointProjection2d {
+                point: Vec2::NAN,
+                is_inside: false,
+                feature: Feature2d::Unknown,
+            };
+        }
+
+        let center = self.world_to_tile_pos(&point);
+        let is_inside = self.get_tile(center) != 0;
+        let best = if is_inside {
+            self.closest_empty_tile(point, center)
+        } else {
+            self.closest_solid_tile(point)
+        };
+        let halfsize = 0.5 * self.tile_size;
+
+        let Some((_, closest, tile_pos)) = best else {
+            return PointProjection2d {
+                point: Vec2::NAN,
+                is_inside,
+                feature: Feature2d::Unknown,
+            };
+        };
+        let solid_tile = if is_inside {
+            // The closest tile is empty, step back towards the point to find the solid one sharing
+            // the boundary.
+            let nudge = 0.01 * halfsize.min_element() * (point - closest).normalize_or_zero();
+            self.world_to_tile_pos(&(closest + nudge))
+        } else {
+            tile_pos
+        };
+        PointProjection2d {
+            point: closest,
+            is_inside,
+            feature: Feature2d::Tile {
+                x: solid_tile.x(),
+                y: solid_tile.y(),
+            },
+        }
+    }
+}
+
+impl Tilemap {
+    /// Closest point of the closest solid tile, visiting the chunks nearest first.
+    fn closest_solid_tile(&self, point: Vec2) -> Option<(f32, Vec2, V2i32)> {
+        let halfsize = 0.5 * self.tile_size;
+        let mut chunks: Vec<(f32, V2i32, &Chunk)> = self
+            .chunks
+            .iter()
+            .map(|(chunk_pos, chunk)| {
+                let first = self.chunk_to_tile_pos(chunk_pos);
+                let last = V2i32::from_xy(
+                    first.x() + self.chunk_size.x() - 1,
+                    first.y() + self.chunk_size.y() - 1,
+                );
+                let min = self.tile_to_world_pos(&first) - halfsize;
+                let max = self.tile_to_world_pos(&last) + halfsize;
+                (point.clamp(min, max).distance_squared(point), first, chunk)
+            })
+            .collect();
+        chunks.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut best: Option<(f32, Vec2, V2i32)> = None;
+        for (reach, first, chunk) in chunks {
+            if best.is_some_and(|(distance_squared, ..)| reach >= distance_squared) {
+                break;
+            }
+            for y in 0..self.chunk_size.y() {
+                for x in 0..self.chunk_size.x() {
+                    let local = V2i32::from_xy(x, y);
+                    if chunk.array2d.get(local).is_none_or(|&tile| tile == 0) {
+                        continue;
+                    }
+                    let tile_pos = first + local;
+                    let tile_center = self.tile_to_world_pos(&tile_pos);
+                    let closest = point.clamp(tile_center - halfsize, tile_center + halfsize);
+                    let distance_squared = closest.distance_squared(point);
+                    if best.is_none_or(|(best_distance_squared, ..)| {
+                        distance_squared < best_distance_squared
+                    }) {
+                        best = Some((distance_squared, closest, tile_pos));
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// Closest point of the closest empty tile to `point` inside of the solid tile `center`,
+    /// searching ring by ring around it.
+    fn closest_empty_tile(&self, point: Vec2, center: V2i32) -> Option<(f32, Vec2, V2i32)> {
+        // Every tile outside of the allocated chunks is empty, the boundary can't be any farther.
+        let bounds = self.bounding_box_t(&Vec2::ZERO);
+        let grid_point = self.world_to_grid(point);
+        let max_ring = (grid_point - self.world_to_grid(bounds.min))
+            .abs()
+            .max((grid_point - self.world_to_grid(bounds.max)).abs())
+            .max_element()
+            .ceil() as i32
+            + 1;
+        let halfsize = 0.5 * self.tile_size;
+
+        let mut best: Option<(f32, Vec2, V2i32)> = None;
+        for ring in 0..=max_ring {
+            // Tiles in the ring are at least `ring - 1` tiles away from any point of the center
+            // tile.
+            let reach = (ring - 1) as f32 * self.tile_size.min_element();
+            if best.is_some_and(|(distance_squared, ..)| {
+                reach > 0.0 && reach * reach > distance_squared
+            }) {
+                break;
+            }
+
+            for offset in ring_offsets(ring) {
+                let tile_pos = center + offset;
+                if self.get_tile(tile_pos) != 0 {
+                    continue;
+                }
+                let tile_center = self.tile_to_world_pos(&tile_pos);
+                let closest = point.clamp(tile_center - halfsize, tile_center + halfsize);
+                let distance_squared = closest.distance_squared(point);
+                if best.is_none_or(|(best_distance_squared, ..)| {
+                    distance_squared < best_distance_squared
+                }) {
+                    best = Some((distance_squared, closest, tile_pos));
+                }
+            }
+        }
+        best
+    }
+}
+
+/// Offsets of tiles at Chebyshev distance `ring`.
+///
+/// Ring `0` yields the center twice, which does not matter for searching.
+fn ring_offsets(ring: i32) -> impl Iterator<Item = V2i32> {
+    let horizontal =
+        (-ring..=ring).flat_map(move |x| [V2i32::from_xy(x, -ring), V2i32::from_xy(x, ring)]);
+    let vertical =
+        (1 - ring..ring).flat_map(move |y| [V2i32::from_xy(-ring, y), V2i32::from_xy(ring, y)]);
+    horizontal.chain(vertical)
+}
+
+impl<T: Transformation2d> SdfvCommonRel2d<false, false, Point, T> for Tilemap {
+    fn sdfv_common_rel(&self, _: &Point, rel: &T) -> QueryResult2d {
+        if self.contains_point(rel.apply_origin()) {
+            QueryResult2d::penetrating_unmeasured()
+        } else {
+            QueryResult2d::separated_unmeasured()
+        }
+    }
+}
+
+impl<T: Transformation2d> SdfvCommonRel2d<true, false, Point, T> for Tilemap {
+    fn sdfv_common_rel(&self, _: &Point, rel: &T) -> QueryResult2d {
+        if self.contains_point(rel.apply_origin()) {
+            error!("tilemap penetration is not implemented yet");
+            QueryResult2d::Unknown {
+                reason: UnknownReason::Unsupported,
+            }
+        } else {
+            QueryResult2d::separated_unmeasured()
+        }
+    }
+}
+
+impl Tilemap {
+    /// Saves as JSON, see [Tilemap::save_binary] for a compact format.
+    pub fn save(&self, filename: impl AsRef<Path>) -> std::io::Result<()> {
+        let file = std::fs::File::create(filename)?;
+        let mut writer = BufWriter::new(file);
+        self.write_json(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn load(filename: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = std::fs::File::open(filename)?;
+        Self::read_json(BufReader::new(file))
+    }
+
+    pub fn write_json<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        Ok(serde_json::to_writer(writer, self)?)
+    }
+
+    pub fn read_json<R: Read>(reader: R) -> std::io::Result<Self> {
+        Ok(serde_json::from_reader(reader)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::col2d::polygon_contains_point;
+    use lk_math::vector::V2;
+
+    #[test_log::test]
+    fn serialize_v2() {
+        let v = V2::from_xy(3, 2);
+        let serialized = serde_json::to_string(&v).unwrap();
+        println!("{serialized}");
+        let deserialized: V2<i32> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(v, deserialized);
+    }
+
+    #[test_log::test]
+    fn serialize_chunk() {
+        let chunk = Chunk::default();
+        let serialized = serde_json::to_string(&chunk).unwrap();
+        println!("{serialized}");
+        let deserialized: Chunk = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(chunk.array2d.dims, deserialized.array2d.dims);
+        assert_eq!(chunk.array2d.dim_strides, deserialized.array2d.dim_strides);
+        assert_eq!(chunk.array2d.data, deserialized.array2d.data);
+    }
+
+    #[test_log::test]
+    fn serialize_tilemap() {
+        let mut tilemap = Tilemap::default();
+        tilemap.set_tile(lk_math::vector::V2::from_xy(3, 2), 1);
+        let serialized = serde_json::to_string(&tilemap).unwrap();
+        println!("{serialized}");
+        let deserialized: Tilemap = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(tilemap.chunks.len(), deserialized.chunks.len());
+    }
+
+    #[test_log::test]
+    #[allow(unused_variables)]
+    fn test_collision() {
+        let mut tilemap = Tilemap::default();
+        tilemap.set_tile(lk_math::vector::V2::from_xy(3, 2), 1);
+        let ball = Ball::with_radius(0.5);
+        let pos = Vec2::new(3.5, 2.5);
+    }
+
+    #[test_log::test]
+    fn bounding_box_covers_chunks() {
+        let mut tilemap = Tilemap::default();
+        tilemap.set_tile(lk_math::vector::V2::from_xy(3, 2), 1);
+        tilemap.set_tile(lk_math::vector::V2::from_xy(-1, 20), 1);
+        let aabb = tilemap.bounding_box_t(&Vec2::new(10.0, 0.0));
+        assert_eq!(
+            aabb,
+            Aabb2d::new(Vec2::new(-6.5, -0.5), Vec2::new(25.5, 31.5))
+        );
+
+        let ball = Ball::with_radius(0.5);
+        let t = Vec2::new(3.9, 2.0);
+        let rect = tilemap.quantize_aabb(&ball.bounding_box_t(&t));
+        assert_eq!(rect.min, lk_math::vector::V2::from_xy(3, 2));
+        assert_eq!(rect.max, lk_math::vector::V2::from_xy(4, 2));
+    }
+    #[test_log::test]
+    fn point_query() {
+        let mut tilemap = Tilemap::default();
+        assert_eq!(
+            tilemap.project_point(Vec2::ZERO).feature,
+            Feature2d::Unknown
+        );
+        for x in 0..4 {
+            tilemap.set_tile(lk_math::vector::V2::from_xy(x, 0), 1);
+        }
+
+        assert!(tilemap.contains_point(Vec2::new(2.2, 0.3)));
+        assert!(!tilemap.contains_point(Vec2::new(2.2, 0.7)));
+
+        let projection = tilemap.project_point(Vec2::new(2.2, 3.0));
+        assert!(!projection.is_inside);
+        assert_eq!(projection.point, Vec2::new(2.2, 0.5));
+        assert_eq!(projection.feature, Feature2d::Tile { x: 2, y: 0 });
+
+        let projection = tilemap.project_point(Vec2::new(1.1, -0.2));
+        assert!(projection.is_inside);
+        assert_eq!(projection.point, Vec2::new(1.1, -0.5));
+        assert_eq!(projection.feature, Feature2d::Tile { x: 1, y: 0 });
+
+        let projection = tilemap.project_point(Vec2::new(-3.0, 0.0));
+        assert_eq!(projection.point, Vec2::new(-0.5, 0.0));
+        assert_eq!(projection.feature, Feature2d::Tile { x: 0, y: 0 });
+
+        let t = Vec2::new(0.0, 10.0);
+        assert!(tilemap.contains_point_t(&t, Vec2::new(3.0, 10.0)));
+    }
+
+    #[test_log::test]
+    fn project_point_far_from_sparse_chunks() {
+        let mut tilemap = Tilemap::default();
+        let tiles = [(0, 0), (1000, 0), (990, 40), (-500, -500)].map(|(x, y)| V2::from_xy(x, y));
+        for tile in tiles {
+            tilemap.set_tile(tile, 1);
+        }
+        for point in [
+            Vec2::new(900.0, 50.0),
+            Vec2::new(2000.0, -3.0),
+            Vec2::new(-100.0, -300.0),
+            Vec2::new(17.0, 4.0),
+        ] {
+            let expected = tiles
+                .iter()
+                .map(|tile| {
+                    let center = tilemap.tile_to_world_pos(tile);
+                    point.clamp(center - 0.5, center + 0.5)
+                })
+                .min_by(|a, b| a.distance(point).total_cmp(&b.distance(point)))
+                .unwrap();
+            let projection = tilemap.project_point(point);
+            assert!(!projection.is_inside);
+            assert_eq!(projection.point, expected, "{point}");
+        }
+    }
+
+    #[test_log::test]
+    fn tile_size_origin_and_chunk_size() {
+        let mut tilemap = Tilemap::default()
+            .with_tile_size(Vec2::new(8.0, 4.0))
+            .with_origin(Vec2::new(100.0, 2.0))
+            .with_chunk_size(V2::from_xy(4, 2));
+        tilemap.set_tile(V2::from_xy(5, -1), 1);
+        assert_eq!(tilemap.chunks.len(), 1);
+        assert_eq!(
+            tilemap.tile_to_chunk_pos(&V2::from_xy(5, -1)),
+            V2::from_xy(1, -1)
+        );
+
+        // Tile (5, -1) covers x in [136, 144] and y in [-4, 0].
+        assert!(tilemap.contains_point(Vec2::new(137.0, -0.5)));
+        assert!(!tilemap.contains_point(Vec2::new(135.0, -0.5)));
+        assert_eq!(
+            tilemap.bounding_box_t(&Vec2::ZERO),
+            Aabb2d::new(Vec2::new(128.0, -8.0), Vec2::new(160.0, 0.0))
+        );
+
+        let projection = tilemap.project_point(Vec2::new(140.0, 3.0));
+        assert_eq!(projection.point, Vec2::new(140.0, 0.0));
+        assert_eq!(projection.feature, Feature2d::Tile { x: 5, y: -1 });
+
+        let ball = Ball::with_radius(1.0);
+        let resolved = tilemap.simple_resolve_ball(&ball, Vec2::new(140.0, 0.5), &-Vec2::Y);
+        assert!(
+            (resolved - Vec2::new(140.0, 1.0)).length() < 1e-3,
+            "{resolved}"
+        );
+
+        let ray = crate::col2d::Ray2d::new(Vec2::new(100.0, -2.0), Vec2::X);
+        let hit = tilemap.cast_ray_tile(&ray, 100.0).unwrap();
+        assert_eq!(hit.tile, V2::from_xy(5, -1));
+        assert_eq!(hit.hit.distance, 36.0);
+
+        let serialized = serde_json::to_string(&tilemap).unwrap();
+        let deserialized: Tilemap = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.tile_size(), tilemap.tile_size());
+        assert_eq!(deserialized.origin(), tilemap.origin());
+        assert_eq!(deserialized.chunk_size(), tilemap.chunk_size());
+        assert_eq!(deserialized.get_tile(V2::from_xy(5, -1)), 1);
+    }
+
+    #[test_log::test]
+    fn deserialize_without_configuration() {
+        let tilemap: Tilemap = serde_json::from_str(r#"{"chunks":[]}"#).unwrap();
+        assert_eq!(tilemap.tile_size(), Vec2::ONE);
+        assert_eq!(tilemap.origin(), Vec2::ZERO);
+        assert_eq!(tilemap.chunk_size(), V2::from_xy(16, 16));
+    }
+
+    #[test_log::test]
+    fn tile_shapes() {
+        let shapes = TileShapeTable2d::default()
+            .with(
+                2,
+                TileShape2d::Slope45 {
+                    flip_x: false,
+                    flip_y: false,
+                },
+            )
+            .with(
+                3,
+                TileProperties2d::new(TileShape2d::Half(TileSide2d::Top)).with_one_way(true),
+            )
+            .with(
+                4,
+                TileProperties2d::new(TileShape2d::Full).with_sensor(true),
+            );
+        let mut tilemap = Tilemap::default().with_tile_shapes(shapes);
+        tilemap.set_tile(V2::from_xy(0, 0), 2);
+        tilemap.set_tile(V2::from_xy(5, 0), 3);
+        tilemap.set_tile(V2::from_xy(8, 0), 4);
+
+        // The ball rests on the slope, its center moves along the slope normal.
+        let ball = Ball::with_radius(0.25);
+        let resolved = tilemap.simple_resolve_ball(&ball, Vec2::new(0.0, 0.1), &-Vec2::Y);
+        let normal = Vec2::new(-1.0, 1.0).normalize();
+        assert!((resolved.dot(normal) - 0.25).abs() < 1e-3, "{resolved}");
+        assert!(!tilemap.contains_point(Vec2::new(-0.3, 0.3)));
+        assert!(tilemap.contains_point(Vec2::new(0.3, -0.3)));
+
+        // Landing on the platform, jumping through it and walking through the sensor.
+        let b = Box2d::with_halfdims(0.5, 0.5);
+        let landed = tilemap.simple_resolve_box(&b, Vec2::new(5.0, 0.6), &-Vec2::Y);
+        assert!((landed - Vec2::new(5.0, 1.0)).length() < 1e-3, "{landed}");
+        let jumping = tilemap.simple_resolve_box(&b, Vec2::new(5.0, 0.2), &Vec2::Y);
+        assert_eq!(jumping, Vec2::new(5.0, 0.2));
+        let walking = tilemap.simple_resolve_box(&b, Vec2::new(8.2, 0.0), &Vec2::X);
+        assert_eq!(walking, Vec2::new(8.2, 0.0));
+
+        let down = crate::col2d::Ray2d::new(Vec2::new(0.25, 2.0), -Vec2::Y);
+        let hit = tilemap.cast_ray_tile(&down, 10.0).unwrap();
+        assert!((hit.hit.distance - 1.75).abs() < 1e-4, "{hit:?}");
+        assert!(hit.hit.normal.abs_diff_eq(normal, 1e-4));
+
+        let up = crate::col2d::Ray2d::new(Vec2::new(5.0, -2.0), Vec2::Y);
+        assert_eq!(tilemap.cast_ray_tile(&up, 10.0), None);
+        let down = crate::col2d::Ray2d::new(Vec2::new(5.0, 2.0), -Vec2::Y);
+        assert_eq!(
+            tilemap.cast_ray_tile(&down, 10.0).unwrap().hit.distance,
+            1.5
+        );
+        assert!(tilemap.has_line_of_sight(Vec2::new(7.0, 0.0), Vec2::new(9.0, 0.0)));
+    }
+
+    #[test_log::test]
+    fn visibility_polygon_uses_tile_shapes() {
+        let shapes = TileShapeTable2d::default()
+            .with(2, TileShape2d::Half(TileSide2d::Bottom))
+            .with(
+                3,
+                TileProperties2d::new(TileShape2d::Full).with_sensor(true),
+            );
+        let mut tilemap = Tilemap::default().with_tile_shapes(shapes);
+        for y in -3..=3 {
+            tilemap.set_tile(V2::from_xy(3, y), 2);
+            tilemap.set_tile(V2::from_xy(-3, y), 3);
+        }
+        let lit = tilemap.visibility_polygon(Vec2::ZERO, 8.0, 32);
+        // Light passes above each half tile, but not through its lower half.
+        assert!(polygon_contains_point(&lit, Vec2::new(3.0, 0.2)));
+        assert!(!polygon_contains_point(&lit, Vec2::new(3.0, -0.2)));
+        assert!(lit.iter().any(|p| p.distance(Vec2::new(2.5, 0.0)) < 1e-3));
+        // Sensors don't cast shadows.
+        assert!(polygon_contains_point(&lit, Vec2::new(-6.0, 0.0)));
+    }
+
+    #[test_log::test]
+    fn visibility_polygon_behind_wall() {
+        let mut tilemap = Tilemap::default();
+        for y in -3..=3 {
+            tilemap.set_tile(V2::from_xy(3, y), 1);
+            tilemap.set_tile(V2::from_xy(4, y), 1);
+        }
+        let lit = tilemap.visibility_polygon(Vec2::ZERO, 8.0, 32);
+        assert!(polygon_contains_point(&lit, Vec2::new(2.0, 0.0)));
+        assert!(!polygon_contains_point(&lit, Vec2::new(6.0, 0.0)));
+        assert!(!polygon_contains_point(&lit, Vec2::new(6.0, 3.0)));
+        assert!(polygon_contains_point(&lit, Vec2::new(0.0, 6.0)));
+        // Wall corner nearest to the viewpoint, the ray through the corner itself passes it.
+        assert!(lit.iter().any(|p| p.distance(Vec2::new(2.5, 3.5)) < 1e-3));
+    }
+
+    #[test_log::test]
+    fn visibility_polygon_from_inside_a_tile() {
+        let mut tilemap = Tilemap::default();
+        tilemap.set_tile(V2::from_xy(0, 0), 1);
+        for y in -3..=3 {
+            tilemap.set_tile(V2::from_xy(3, y), 1);
+        }
+        let lit = tilemap.visibility_polygon(Vec2::new(0.2, 0.1), 8.0, 32);
+        assert!(polygon_contains_point(&lit, Vec2::new(2.0, 0.0)));
+        assert!(!polygon_contains_point(&lit, Vec2::new(5.0, 0.0)));
+        assert!(polygon_contains_point(&lit, Vec2::new(-5.0, 0.0)));
+    }
+}
@@ -0,0 +1,377 @@
+use lk_math::vector::V2i32;
+
+use super::{TileShape2d, Tilemap};
+use crate::col2d::{Ray2d, RayCast2d, RayHit2d, Vec2};
+
+/// First solid tile hit by a ray, see [Tilemap::cast_ray_tile].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileRayHit2d {
+    pub tile: V2i32,
+    /// The normal is the face the ray entered the tile through.
+    pub hit: RayHit2d,
+}
+
+/// Amanatides–Woo traversal of the tiles crossed by a ray.
+///
+/// Works in tilemap space where tiles are unit squares, distances along the ray are the same as
+/// in world space.
+#[derive(Debug, Clone)]
+struct GridTraversal2d {
+    tile: V2i32,
+    step: (i32, i32),
+    /// Distance along the ray to the next vertical and horizontal tile boundary.
+    next: Vec2,
+    /// Distance along the ray between two vertical and two horizontal tile boundaries.
+    delta: Vec2,
+    /// Distance along the ray where it entered the current tile.
+    distance: f32,
+    /// Outward normal of the face of the current tile the ray entered through.
+    normal: Vec2,
+}
+
+impl GridTraversal2d {
+    fn new(tilemap: &Tilemap, ray: &Ray2d) -> Self {
+        let tile = tilemap.world_to_tile_pos(&ray.origin);
+        let center = Vec2::new(tile.x() as f32, tile.y() as f32);
+        let ray = Ray2d::new(
+            tilemap.world_to_grid(ray.origin),
+            ray.direction / tilemap.tile_size(),
+        );
+        let axis = |origin: f32, direction: f32, center: f32| {
+            if direction > 0.0 {
+                (1, (center + 0.5 - origin) / direction, 1.0 / direction)
+            } else if direction < 0.0 {
+                (-1, (center - 0.5 - origin) / direction, -1.0 / direction)
+            } else {
+                (0, f32::INFINITY, f32::INFINITY)
+            }
+        };
+        let (step_x, next_x, delta_x) = axis(ray.origin.x, ray.direction.x, center.x);
+        let (step_y, next_y, delta_y) = axis(ray.origin.y, ray.direction.y, center.y);
+        Self {
+            tile,
+            step: (step_x, step_y),
+            next: Vec2::new(next_x, next_y),
+            delta: Vec2::new(delta_x, delta_y),
+            distance: 0.0,
+            normal: Vec2::ZERO,
+        }
+    }
+
+    /// Moves to the next tile, returns `false` if the ray doesn't move.
+    fn advance(&mut self) -> bool {
+        if self.next.x < self.next.y {
+            self.advance_x(1);
+        } else if self.next.y.is_finite() {
+            self.advance_y(1);
+        } else {
+            return false;
+        }
+        true
+    }
+
+    fn advance_x(&mut self, tiles: i32) {
+        self.distance = self.next.x + (tiles - 1) as f32 * self.delta.x;
+        self.next.x += tiles as f32 * self.delta.x;
+        self.tile += V2i32::from_xy(self.step.0 * tiles, 0);
+        self.normal = Vec2::new(-self.step.0 as f32, 0.0);
+    }
+
+    fn advance_y(&mut self, tiles: i32) {
+        self.distance = self.next.y + (tiles - 1) as f32 * self.delta.y;
+        self.next.y += tiles as f32 * self.delta.y;
+        self.tile += V2i32::from_xy(0, self.step.1 * tiles);
+        self.normal = Vec2::new(0.0, -self.step.1 as f32);
+    }
+
+    /// Moves to the first tile past the chunk containing the current tile, visiting the same
+    /// tiles as [GridTraversal2d::advance] would, without looking at them.
+    fn skip_chunk(&mut self, tilemap: &Tilemap) {
+        let min = tilemap.chunk_to_tile_pos(&tilemap.tile_to_chunk_pos(&self.tile));
+        let max = min + tilemap.chunk_size() - V2i32::from_xy(1, 1);
+        let tiles_left = |step: i32, tile: i32, min: i32, max: i32| match step {
+            1 => max - tile + 1,
+            -1 => tile - min + 1,
+            _ => 0,
+        };
+        let tiles_x = tiles_left(self.step.0, self.tile.x(), min.x(), max.x());
+        let tiles_y = tiles_left(self.step.1, self.tile.y(), min.y(), max.y());
+        let exit = |tiles: i32, next: f32, delta: f32| match tiles {
+            0 => f32::INFINITY,
+            _ => next + (tiles - 1) as f32 * delta,
+        };
+        let exit_x = exit(tiles_x, self.next.x, self.delta.x);
+        let exit_y = exit(tiles_y, self.next.y, self.delta.y);
+        // Ties step along y like in `advance`, so the steps along the other axis are the ones
+        // `advance` would take before leaving the chunk.
+        if exit_x < exit_y {
+            while self.next.y <= exit_x {
+                self.advance_y(1);
+            }
+            self.advance_x(tiles_x);
+        } else if exit_y.is_finite() {
+            while self.next.x < exit_y {
+                self.advance_x(1);
+            }
+            self.advance_y(tiles_y);
+        }
+    }
+}
+
+/// Ray casts against the shapes of non-empty tiles, see [TileShapeTable2d](super::TileShapeTable2d).
+///
+/// Sensor tiles are ignored and one-way tiles are only hit from above.
+/// Tiles are visited in the order the ray crosses them, absent chunks are skipped whole.
+impl Tilemap {
+    /// Tiles crossed by `ray` up to `max_distance`, with the distance where the ray enters each
+    /// of them, starting with the tile containing the origin at distance `0.0`.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// # use lk_math::vector::V2i32;
+    /// let tilemap = Tilemap::default();
+    /// let ray = Ray2d::new(Vec2::ZERO, Vec2::new(1.0, 0.5));
+    /// let tiles: Vec<V2i32> = tilemap
+    ///     .tiles_along_ray(&ray, 2.0)
+    ///     .map(|(tile, _)| tile)
+    ///     .collect();
+    /// assert_eq!(
+    ///     tiles,
+    ///     [(0, 0), (1, 0), (1, 1), (2, 1)].map(|(x, y)| V2i32::from_xy(x, y))
+    /// );
+    /// ```
+    pub fn tiles_along_ray<'a>(
+        &'a self,
+        ray: &Ray2d,
+        max_distance: f32,
+    ) -> impl Iterator<Item = (V2i32, f32)> + 'a {
+        let mut traversal = GridTraversal2d::new(self, ray);
+        let mut started = false;
+        core::iter::from_fn(move || {
+            if started && !traversal.advance() {
+                return None;
+            }
+            started = true;
+            (traversal.distance <= max_distance).then_some((traversal.tile, traversal.distance))
+        })
+    }
+
+    /// First solid tile hit by `ray` up to `max_distance`.
+    ///
+    /// A ray starting in a solid tile hits it at distance `0.0` with a zero normal.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// # use lk_math::vector::V2i32;
+    /// let mut tilemap = Tilemap::default();
+    /// tilemap.set_tile(V2i32::from_xy(100, 3), 1);
+    /// let ray = Ray2d::new(Vec2::new(0.0, 3.0), Vec2::X);
+    /// let hit = tilemap.cast_ray_tile(&ray, 1000.0).unwrap();
+    /// assert_eq!(hit.tile, V2i32::from_xy(100, 3));
+    /// assert_eq!(hit.hit.distance, 99.5);
+    /// assert_eq!(hit.hit.normal, -Vec2::X);
+    /// ```
+    pub fn cast_ray_tile(&self, ray: &Ray2d, max_distance: f32) -> Option<TileRayHit2d> {
+        self.cast_ray_tile_from(ray, max_distance, false)
+    }
+
+    /// [Tilemap::cast_ray_tile], optionally passing through the shape the ray starts inside.
+    pub(super) fn cast_ray_tile_from(
+        &self,
+        ray: &Ray2d,
+        max_distance: f32,
+        ignore_inside: bool,
+    ) -> Option<TileRayHit2d> {
+        let (chunk_min, chunk_max) = self.chunk_bounds()?;
+        let mut traversal = GridTraversal2d::new(self, ray);
+        loop {
+            if traversal.distance > max_distance {
+                return None;
+            }
+            let chunk = self.tile_to_chunk_pos(&traversal.tile);
+            if self.get_chunk(&chunk).is_some() {
+                let hit = self
+                    .cast_ray_in_tile(&traversal, ray, max_distance)
+                    .filter(|hit| !ignore_inside || hit.normal != Vec2::ZERO);
+                if let Some(hit) = hit {
+                    return Some(TileRayHit2d {
+                        tile: traversal.tile,
+                        hit,
+                    });
+                }
+                if !traversal.advance() {
+                    return None;
+                }
+                continue;
+            }
+            // Past the allocated chunks and moving away from them, so there is nothing left to hit,
+            // even for an unbounded ray.
+            let leaving = |chunk: i32, min: i32, max: i32, step: i32| {
+                (chunk < min && step <= 0) || (chunk > max && step >= 0)
+            };
+            if leaving(chunk.x(), chunk_min.x(), chunk_max.x(), traversal.step.0)
+                || leaving(chunk.y(), chunk_min.y(), chunk_max.y(), traversal.step.1)
+            {
+                return None;
+            }
+            traversal.skip_chunk(self);
+        }
+    }
+
+    /// Hit of `ray` with the shape of the tile the traversal is in, see [TileShapeTable2d](super::TileShapeTable2d).
+    fn cast_ray_in_tile(
+        &self,
+        traversal: &GridTraversal2d,
+        ray: &Ray2d,
+        max_distance: f32,
+    ) -> Option<RayHit2d> {
+        if self.get_tile(traversal.tile) == 0 {
+            return None;
+        }
+        let (shape, center, properties) = self.tile_collider(traversal.tile)?;
+        if properties.sensor {
+            return None;
+        }
+        let hit = match (&properties.shape, properties.one_way) {
+            // The tile boundary crossed by the traversal is exact.
+            (TileShape2d::Full, false) => RayHit2d {
+                distance: traversal.distance,
+                point: ray.at(traversal.distance),
+                normal: traversal.normal,
+            },
+            _ => shape.cast_ray_t(&center, ray, max_distance)?,
+        };
+        // One-way tiles only block from above, starting inside one doesn't count.
+        if properties.one_way && hit.normal.y <= 0.0 {
+            return None;
+        }
+        Some(hit)
+    }
+
+    /// Whether the segment from `from` to `to` crosses no solid tile, tiles containing the ends
+    /// included.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// # use lk_math::vector::V2i32;
+    /// let mut tilemap = Tilemap::default();
+    /// tilemap.set_tile(V2i32::from_xy(2, 0), 1);
+    /// assert!(!tilemap.has_line_of_sight(Vec2::ZERO, Vec2::new(4.0, 0.0)));
+    /// assert!(tilemap.has_line_of_sight(Vec2::ZERO, Vec2::new(4.0, 2.0)));
+    /// ```
+    pub fn has_line_of_sight(&self, from: Vec2, to: Vec2) -> bool {
+        self.cast_ray_tile(&Ray2d::new(from, to - from), 1.0)
+            .is_none()
+    }
+
+    /// Smallest and largest position of allocated chunks, `None` if there are none.
+    fn chunk_bounds(&self) -> Option<(V2i32, V2i32)> {
+        self.chunks.keys().fold(None, |bounds, &chunk| {
+            let (min, max) = bounds.unwrap_or((chunk, chunk));
+            Some((
+                V2i32::from_xy(min.x().min(chunk.x()), min.y().min(chunk.y())),
+                V2i32::from_xy(max.x().max(chunk.x()), max.y().max(chunk.y())),
+            ))
+        })
+    }
+}
+
+impl RayCast2d for Tilemap {
+    fn cast_ray(&self, ray: &Ray2d, max_distance: f32) -> Option<RayHit2d> {
+        self.cast_ray_tile(ray, max_distance).map(|hit| hit.hit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::col2d::PointQuery2d;
+
+    fn v(x: i32, y: i32) -> V2i32 {
+        V2i32::from_xy(x, y)
+    }
+
+    #[test_log::test]
+    fn hits_entry_face() {
+        let mut tilemap = Tilemap::default();
+        tilemap.set_tile(v(3, 2), 1);
+
+        let ray = Ray2d::new(Vec2::new(3.2, -1.0), Vec2::Y);
+        let hit = tilemap.cast_ray_tile(&ray, 10.0).unwrap();
+        assert_eq!(hit.tile, v(3, 2));
+        assert_eq!(hit.hit.distance, 2.5);
+        assert_eq!(hit.hit.point, Vec2::new(3.2, 1.5));
+        assert_eq!(hit.hit.normal, -Vec2::Y);
+        assert_eq!(tilemap.cast_ray_tile(&ray, 2.0), None);
+
+        let ray = Ray2d::new(Vec2::new(6.0, 6.0), Vec2::new(-1.0, -1.5));
+        let hit = tilemap.cast_ray(&ray, 10.0).unwrap();
+        assert!(hit.normal == Vec2::X || hit.normal == Vec2::Y);
+        assert!(tilemap.contains_point(hit.point - 0.01 * hit.normal));
+
+        let ray = Ray2d::new(Vec2::new(3.0, 2.0), Vec2::X);
+        assert_eq!(tilemap.cast_ray(&ray, 10.0), Some(RayHit2d::inside(&ray)));
+    }
+
+    #[test_log::test]
+    fn skipping_chunks_matches_tiles_along_ray() {
+        let mut tilemap = Tilemap::default();
+        let solids = [
+            v(40, 13),
+            v(-20, 30),
+            v(-33, -17),
+            v(7, -45),
+            v(0, 1),
+            v(-1, -1),
+        ];
+        for &p in &solids {
+            tilemap.set_tile(p, 1);
+        }
+        for i in 0..64 {
+            let angle = core::f32::consts::TAU * i as f32 / 64.0 + 0.01;
+            let ray = Ray2d::new(Vec2::new(0.3, -0.2), Vec2::from_angle(angle));
+            let expected = tilemap
+                .tiles_along_ray(&ray, 80.0)
+                .find(|&(tile, _)| tilemap.get_tile(tile) != 0);
+            let hit = tilemap.cast_ray_tile(&ray, 80.0);
+            assert_eq!(
+                hit.map(|hit| hit.tile),
+                expected.map(|(tile, _)| tile),
+                "{i}"
+            );
+            if let (Some(hit), Some((_, distance))) = (hit, expected) {
+                assert!((hit.hit.distance - distance).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test_log::test]
+    fn unbounded_ray_terminates() {
+        let mut tilemap = Tilemap::default();
+        tilemap.set_tile(v(0, 0), 1);
+        let ray = Ray2d::new(Vec2::new(0.0, 5.0), Vec2::new(1.0, 0.1));
+        assert_eq!(tilemap.cast_ray_tile(&ray, f32::INFINITY), None);
+        assert_eq!(Tilemap::default().cast_ray_tile(&ray, f32::INFINITY), None);
+
+        let ray = Ray2d::new(Vec2::new(-300.0, 0.2), Vec2::X);
+        let hit = tilemap.cast_ray_tile(&ray, f32::INFINITY).unwrap();
+        assert_eq!(hit.tile, v(0, 0));
+    }
+
+    #[test_log::test]
+    fn line_of_sight() {
+        let mut tilemap = Tilemap::default();
+        for y in -2..=2 {
+            tilemap.set_tile(v(0, y), 1);
+        }
+        let (a, b) = (Vec2::new(-3.0, 0.0), Vec2::new(3.0, 0.0));
+        assert!(!tilemap.has_line_of_sight(a, b));
+        assert!(!tilemap.has_line_of_sight(b, a));
+        assert!(tilemap.has_line_of_sight(a, Vec2::new(3.0, 6.0)));
+        assert!(tilemap.has_line_of_sight(a, a));
+        assert!(!tilemap.has_line_of_sight(Vec2::ZERO, Vec2::ZERO));
+    }
+}
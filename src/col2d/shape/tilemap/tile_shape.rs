@@ -0,0 +1,515 @@
+use std::sync::OnceLock;
+
+use lk_math::vector::V2i32;
+use serde::{Deserialize, Serialize};
+
+use super::Tilemap;
+use crate::col2d::{signed_area, Box2d, Poly2d, Shape2d, Vec2};
+
+/// Side of a tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TileSide2d {
+    Bottom,
+    Top,
+    Left,
+    Right,
+}
+
+/// Collision geometry of a tile.
+///
+/// Shapes are given in tile units, the tile spans `[-0.5, 0.5]` along both axes and is scaled by
+/// [Tilemap::tile_size].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum TileShape2d {
+    /// Never collides.
+    #[default]
+    Empty,
+    /// The whole tile.
+    Full,
+    /// Half of the tile next to a side.
+    Half(TileSide2d),
+    /// Slope rising one tile over one tile towards `+x`, solid below.
+    ///
+    /// `flip_x` makes it rise towards `-x`, `flip_y` turns it into a ceiling solid above.
+    Slope45 { flip_x: bool, flip_y: bool },
+    /// Slope rising half a tile over one tile towards `+x`, solid below, often called a 22.5°
+    /// slope. A ramp is a tile with `upper == false` followed by one with `upper == true`.
+    ///
+    /// `flip_x` makes it rise towards `-x`, `flip_y` turns it into a ceiling solid above.
+    Slope22 {
+        upper: bool,
+        flip_x: bool,
+        flip_y: bool,
+    },
+    /// Custom convex polygon in tile units.
+    Poly(#[serde(with = "poly_points")] Poly2d),
+}
+
+/// [Poly2d] has no serde support of its own, custom tile shapes are stored as their points.
+mod poly_points {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::col2d::{Poly2d, Vec2};
+
+    pub fn serialize<S: Serializer>(poly: &Poly2d, serializer: S) -> Result<S::Ok, S::Error> {
+        poly.points.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Poly2d, D::Error> {
+        let points = Vec::<Vec2>::deserialize(deserializer)?;
+        Ok(Poly2d::new(&points))
+    }
+}
+
+impl TileShape2d {
+    /// Collider of a tile of size `tile_size` with its offset from the tile center.
+    pub fn collider(&self, tile_size: Vec2) -> Option<(Shape2d, Vec2)> {
+        let (halfsize, offset) = match *self {
+            TileShape2d::Empty => return None,
+            TileShape2d::Full => (Vec2::new(0.5, 0.5), Vec2::ZERO),
+            TileShape2d::Half(side) => match side {
+                TileSide2d::Bottom => (Vec2::new(0.5, 0.25), Vec2::new(0.0, -0.25)),
+                TileSide2d::Top => (Vec2::new(0.5, 0.25), Vec2::new(0.0, 0.25)),
+                TileSide2d::Left => (Vec2::new(0.25, 0.5), Vec2::new(-0.25, 0.0)),
+                TileSide2d::Right => (Vec2::new(0.25, 0.5), Vec2::new(0.25, 0.0)),
+            },
+            _ => {
+                let mut points: Vec<Vec2> =
+                    self.outline().into_iter().map(|p| p * tile_size).collect();
+                if signed_area(&points) < 0.0 {
+                    points.reverse();
+                }
+                // Slopes and flipped shapes may not contain the tile center, so the polygon is
+                // centered at its vertex average instead.
+                let center = points.iter().sum::<Vec2>() / points.len() as f32;
+                let points: Vec<Vec2> = points.into_iter().map(|p| p - center).collect();
+                return Some((Shape2d::Poly2d(Poly2d::new(&points)), center));
+            }
+        };
+        Some((
+            Shape2d::Box2d(Box2d::new(halfsize * tile_size)),
+            offset * tile_size,
+        ))
+    }
+
+    /// Vertices of a polygonal shape in tile units.
+    fn outline(&self) -> Vec<Vec2> {
+        let (points, flip_x, flip_y) = match *self {
+            TileShape2d::Slope45 { flip_x, flip_y } => (
+                vec![
+                    Vec2::new(-0.5, -0.5),
+                    Vec2::new(0.5, -0.5),
+                    Vec2::new(0.5, 0.5),
+                ],
+                flip_x,
+                flip_y,
+            ),
+            TileShape2d::Slope22 {
+                upper: false,
+                flip_x,
+                flip_y,
+            } => (
+                vec![
+                    Vec2::new(-0.5, -0.5),
+                    Vec2::new(0.5, -0.5),
+                    Vec2::new(0.5, 0.0),
+                ],
+                flip_x,
+                flip_y,
+            ),
+            TileShape2d::Slope22 {
+                upper: true,
+                flip_x,
+                flip_y,
+            } => (
+                vec![
+                    Vec2::new(-0.5, -0.5),
+                    Vec2::new(0.5, -0.5),
+                    Vec2::new(0.5, 0.5),
+                    Vec2::new(-0.5, 0.0),
+                ],
+                flip_x,
+                flip_y,
+            ),
+            TileShape2d::Poly(ref poly) => (poly.points.clone(), false, false),
+            TileShape2d::Empty => (Vec::new(), false, false),
+            TileShape2d::Full | TileShape2d::Half(_) => (
+                vec![
+                    Vec2::new(-0.5, -0.5),
+                    Vec2::new(0.5, -0.5),
+                    Vec2::new(0.5, 0.5),
+                    Vec2::new(-0.5, 0.5),
+                ],
+                false,
+                false,
+            ),
+        };
+        let scale = Vec2::new(
+            if flip_x { -1.0 } else { 1.0 },
+            if flip_y { -1.0 } else { 1.0 },
+        );
+        points.into_iter().map(|p| p * scale).collect()
+    }
+
+    /// The shape mirrored like a flipped tile in a map editor.
+    ///
+    /// The diagonal flip mirrors across the diagonal from the top-left to the bottom-right corner
+    /// and is applied first, then the horizontal and the vertical flip.
+    ///
+    /// # Example
+    /// ```
+    /// # use auburn::col2d::*;
+    /// let half = TileShape2d::Half(TileSide2d::Bottom);
+    /// assert!(matches!(
+    ///     half.flipped(false, true, false),
+    ///     TileShape2d::Half(TileSide2d::Top)
+    /// ));
+    /// assert!(matches!(
+    ///     half.flipped(false, false, true),
+    ///     TileShape2d::Half(TileSide2d::Right)
+    /// ));
+    /// ```
+    pub fn flipped(&self, flip_x: bool, flip_y: bool, flip_diagonal: bool) -> TileShape2d {
+        let flip = |p: Vec2| {
+            let p = if flip_diagonal {
+                Vec2::new(-p.y, -p.x)
+            } else {
+                p
+            };
+            Vec2::new(
+                if flip_x { -p.x } else { p.x },
+                if flip_y { -p.y } else { p.y },
+            )
+        };
+        match *self {
+            TileShape2d::Empty | TileShape2d::Full => self.clone(),
+            TileShape2d::Half(side) => {
+                let direction = match side {
+                    TileSide2d::Bottom => -Vec2::Y,
+                    TileSide2d::Top => Vec2::Y,
+                    TileSide2d::Left => -Vec2::X,
+                    TileSide2d::Right => Vec2::X,
+                };
+                let direction = flip(direction);
+                TileShape2d::Half(match (direction.x, direction.y) {
+                    (x, _) if x > 0.0 => TileSide2d::Right,
+                    (x, _) if x < 0.0 => TileSide2d::Left,
+                    (_, y) if y > 0.0 => TileSide2d::Top,
+                    _ => TileSide2d::Bottom,
+                })
+            }
+            _ => {
+                let points: Vec<Vec2> = self.outline().into_iter().map(flip).collect();
+                TileShape2d::Poly(Poly2d::new(&points))
+            }
+        }
+    }
+}
+
+/// Collision geometry and behaviour of a tile id.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TileProperties2d {
+    pub shape: TileShape2d,
+    /// Only blocks bodies falling onto it from above, e.g. jump-through platforms.
+    pub one_way: bool,
+    /// Never blocks, the shape is still reported by point queries, e.g. for ladders or water.
+    pub sensor: bool,
+}
+
+impl TileProperties2d {
+    pub fn new(shape: TileShape2d) -> Self {
+        Self {
+            shape,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_one_way(mut self, one_way: bool) -> Self {
+        self.one_way = one_way;
+        self
+    }
+
+    pub fn with_sensor(mut self, sensor: bool) -> Self {
+        self.sensor = sensor;
+        self
+    }
+}
+
+impl From<TileShape2d> for TileProperties2d {
+    fn from(shape: TileShape2d) -> Self {
+        Self::new(shape)
+    }
+}
+
+/// Maps tile ids to their [TileProperties2d].
+///
+/// Tile `0` is always empty, by default every other tile is [TileShape2d::Full]. Only the tiles
+/// that differ from the default are serialized.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// let shapes = TileShapeTable2d::default()
+///     .with(2, TileShape2d::Slope45 { flip_x: false, flip_y: false })
+///     .with(3, TileProperties2d::new(TileShape2d::Half(TileSide2d::Top)).with_one_way(true));
+/// assert!(shapes.get(3).one_way);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(
+    into = "Vec<(u8, TileProperties2d)>",
+    try_from = "Vec<(u8, TileProperties2d)>"
+)]
+pub struct TileShapeTable2d {
+    tiles: Vec<TileProperties2d>,
+}
+
+impl Default for TileShapeTable2d {
+    fn default() -> Self {
+        let mut tiles = vec![TileProperties2d::new(TileShape2d::Full); 256];
+        tiles[0] = TileProperties2d::default();
+        Self { tiles }
+    }
+}
+
+impl TileShapeTable2d {
+    pub fn get(&self, tile: u8) -> &TileProperties2d {
+        &self.tiles[tile as usize]
+    }
+
+    /// # Panics
+    /// If `tile` is `0`.
+    pub fn set(&mut self, tile: u8, properties: impl Into<TileProperties2d>) {
+        assert_ne!(tile, 0, "tile 0 is always empty");
+        self.tiles[tile as usize] = properties.into();
+    }
+
+    /// See [TileShapeTable2d::set].
+    pub fn with(mut self, tile: u8, properties: impl Into<TileProperties2d>) -> Self {
+        self.set(tile, properties);
+        self
+    }
+
+    fn is_default(properties: &TileProperties2d) -> bool {
+        matches!(properties.shape, TileShape2d::Full) && !properties.one_way && !properties.sensor
+    }
+}
+
+impl From<TileShapeTable2d> for Vec<(u8, TileProperties2d)> {
+    fn from(table: TileShapeTable2d) -> Self {
+        (0..=u8::MAX)
+            .zip(table.tiles)
+            .filter(|(tile, properties)| *tile != 0 && !TileShapeTable2d::is_default(properties))
+            .collect()
+    }
+}
+
+impl TryFrom<Vec<(u8, TileProperties2d)>> for TileShapeTable2d {
+    type Error = &'static str;
+
+    fn try_from(tiles: Vec<(u8, TileProperties2d)>) -> Result<Self, Self::Error> {
+        let mut table = Self::default();
+        for (tile, properties) in tiles {
+            if tile == 0 {
+                return Err("tile 0 is always empty");
+            }
+            table.set(tile, properties);
+        }
+        Ok(table)
+    }
+}
+
+impl Tilemap {
+    pub fn with_tile_shapes(mut self, tile_shapes: TileShapeTable2d) -> Self {
+        self.tile_shapes = tile_shapes;
+        self.tile_colliders = OnceLock::new();
+        self
+    }
+
+    pub fn tile_shapes(&self) -> &TileShapeTable2d {
+        &self.tile_shapes
+    }
+
+    pub fn tile_shapes_mut(&mut self) -> &mut TileShapeTable2d {
+        self.tile_colliders = OnceLock::new();
+        &mut self.tile_shapes
+    }
+
+    /// World-space collider of the tile at `pos` with its properties, `None` for empty tiles.
+    ///
+    /// Colliders are built once per tile id and shared by all tiles with that id.
+    pub fn tile_collider(&self, pos: V2i32) -> Option<(&Shape2d, Vec2, &TileProperties2d)> {
+        let tile = self.get_tile(pos);
+        let colliders = self.tile_colliders.get_or_init(|| {
+            (0..=u8::MAX)
+                .map(|tile| self.tile_shapes.get(tile).shape.collider(self.tile_size))
+                .collect()
+        });
+        let (shape, offset) = colliders[tile as usize].as_ref()?;
+        let properties = self.tile_shapes.get(tile);
+        Some((shape, self.tile_to_world_pos(&pos) + *offset, properties))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::col2d::{MassProperties2d, PointQuery2d};
+
+    fn slope() -> TileShape2d {
+        TileShape2d::Slope45 {
+            flip_x: false,
+            flip_y: false,
+        }
+    }
+
+    #[test_log::test]
+    fn colliders_fit_the_tile() {
+        let tile_size = Vec2::new(8.0, 4.0);
+        let shapes = [
+            (TileShape2d::Full, 32.0),
+            (TileShape2d::Half(TileSide2d::Left), 16.0),
+            (
+                TileShape2d::Slope45 {
+                    flip_x: true,
+                    flip_y: false,
+                },
+                16.0,
+            ),
+            (
+                TileShape2d::Slope22 {
+                    upper: false,
+                    flip_x: false,
+                    flip_y: true,
+                },
+                8.0,
+            ),
+            (
+                TileShape2d::Slope22 {
+                    upper: true,
+                    flip_x: true,
+                    flip_y: true,
+                },
+                24.0,
+            ),
+        ];
+        for (shape, area) in shapes {
+            let (collider, offset) = shape.collider(tile_size).unwrap();
+            let mass = collider.mass_properties(1.0).mass;
+            assert!((mass - area).abs() < 1e-4, "{shape:?} {mass}");
+            if let Shape2d::Poly2d(poly) = &collider {
+                assert!(poly.strict_check(), "{shape:?}");
+                for &p in &poly.points {
+                    let p = p + offset;
+                    assert!(p.x.abs() <= 4.0 + 1e-5 && p.y.abs() <= 2.0 + 1e-5);
+                }
+            }
+        }
+        assert!(TileShape2d::Empty.collider(tile_size).is_none());
+    }
+
+    #[test_log::test]
+    fn slope_orientation() {
+        let slope = |flip_x, flip_y| {
+            let (collider, offset) = TileShape2d::Slope45 { flip_x, flip_y }
+                .collider(Vec2::ONE)
+                .unwrap();
+            move |point: Vec2| collider.contains_point_t(&offset, point)
+        };
+        assert!(slope(false, false)(Vec2::new(0.4, -0.3)));
+        assert!(!slope(false, false)(Vec2::new(-0.4, 0.3)));
+        assert!(slope(true, false)(Vec2::new(-0.4, -0.3)));
+        assert!(slope(false, true)(Vec2::new(0.4, 0.3)));
+        assert!(slope(true, true)(Vec2::new(-0.4, 0.3)));
+        assert!(!slope(true, true)(Vec2::new(0.4, -0.3)));
+    }
+
+    #[test_log::test]
+    fn tile_shapes_survive_json() {
+        let shapes = TileShapeTable2d::default()
+            .with(2, slope())
+            .with(
+                3,
+                TileShape2d::Poly(Poly2d::new(&[
+                    Vec2::new(-0.5, -0.5),
+                    Vec2::new(0.5, -0.5),
+                    Vec2::new(0.0, 0.5),
+                ])),
+            )
+            .with(
+                4,
+                TileProperties2d::new(TileShape2d::Empty).with_sensor(true),
+            );
+        let mut tilemap = Tilemap::default().with_tile_shapes(shapes);
+        tilemap.set_tile(V2i32::from_xy(0, 0), 2);
+        tilemap.set_tile(V2i32::from_xy(1, 0), 3);
+
+        let json = serde_json::to_string(&tilemap).unwrap();
+        let loaded: Tilemap = serde_json::from_str(&json).unwrap();
+        assert!(loaded.tile_shapes().get(4).sensor);
+        for point in [
+            Vec2::new(0.3, -0.3),
+            Vec2::new(-0.3, 0.3),
+            Vec2::new(0.6, -0.4),
+            Vec2::new(1.0, 0.4),
+        ] {
+            assert_eq!(
+                loaded.contains_point(point),
+                tilemap.contains_point(point),
+                "{point}"
+            );
+        }
+
+        let default = serde_json::to_string(&TileShapeTable2d::default()).unwrap();
+        assert_eq!(default, "[]");
+        let zero = serde_json::from_str::<TileShapeTable2d>(r#"[[0, {"shape": "Full"}]]"#);
+        assert!(zero.is_err());
+    }
+
+    #[test_log::test]
+    fn cached_colliders_follow_changes() {
+        let mut tilemap = Tilemap::default();
+        tilemap.set_tile(V2i32::from_xy(0, 0), 2);
+        assert!(tilemap.contains_point(Vec2::new(-0.3, 0.3)));
+
+        tilemap.tile_shapes_mut().set(2, slope());
+        assert!(!tilemap.contains_point(Vec2::new(-0.3, 0.3)));
+        assert!(tilemap.contains_point(Vec2::new(0.3, -0.3)));
+
+        let tilemap = tilemap.with_tile_size(Vec2::splat(2.0));
+        assert!(tilemap.contains_point(Vec2::new(0.6, -0.6)));
+        assert!(!tilemap.contains_point(Vec2::new(-0.6, 0.6)));
+    }
+
+    #[test_log::test]
+    fn flipped_shapes() {
+        let contains = |shape: TileShape2d, point: Vec2| {
+            let (collider, offset) = shape.collider(Vec2::ONE).unwrap();
+            collider.contains_point_t(&offset, point)
+        };
+        let slope = TileShape2d::Slope45 {
+            flip_x: false,
+            flip_y: false,
+        };
+        // Flipping diagonally and horizontally rotates by 90° clockwise.
+        let rotated = slope.flipped(true, false, true);
+        assert!(contains(rotated.clone(), Vec2::new(-0.4, -0.3)));
+        assert!(!contains(rotated, Vec2::new(0.4, -0.3)));
+        let flipped = slope.flipped(true, true, false);
+        assert!(contains(flipped.clone(), Vec2::new(-0.4, 0.3)));
+        assert!(!contains(flipped, Vec2::new(0.4, -0.3)));
+
+        let ramp = TileShape2d::Slope22 {
+            upper: false,
+            flip_x: false,
+            flip_y: false,
+        };
+        let wall = ramp.flipped(false, false, true);
+        assert!(contains(wall.clone(), Vec2::new(0.3, -0.4)));
+        assert!(!contains(wall, Vec2::new(-0.3, -0.4)));
+
+        assert!(matches!(
+            TileShape2d::Half(TileSide2d::Left).flipped(true, false, true),
+            TileShape2d::Half(TileSide2d::Top)
+        ));
+    }
+}
@@ -65,7 +65,9 @@ mod tests {
             shape: &b,
             transform: &t2,
         };
-        let (collides, sdfv) = col1.sdfv(col2);
+        let result = col1.sdfv(col2);
+        let collides = result.collides().unwrap();
+        let sdfv = result.sdfv().unwrap();
         assert!(!collides);
         let distance = sdfv.length();
         trace!("sdfv length: {distance}");
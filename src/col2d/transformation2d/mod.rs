@@ -40,6 +40,35 @@ pub trait Transformation2d {
     fn scaling_factor(&self) -> f32;
 }
 
+/// Inverse of a borrowed transformation, for transformations that are not [Invertible].
+pub(crate) struct InverseTransform2d<'a, T: Transformation2d>(pub &'a T);
+
+impl<T: Transformation2d> Transformation2d for InverseTransform2d<'_, T> {
+    fn apply_origin(&self) -> Vec2 {
+        self.0.unapply(Vec2::ZERO)
+    }
+
+    fn apply(&self, point: Vec2) -> Vec2 {
+        self.0.unapply(point)
+    }
+
+    fn unapply(&self, point: Vec2) -> Vec2 {
+        self.0.apply(point)
+    }
+
+    fn apply_normal(&self, normal: Vec2) -> Vec2 {
+        self.0.unapply_normal(normal)
+    }
+
+    fn unapply_normal(&self, normal: Vec2) -> Vec2 {
+        self.0.apply_normal(normal)
+    }
+
+    fn scaling_factor(&self) -> f32 {
+        1.0 / self.0.scaling_factor()
+    }
+}
+
 impl Transformation2d for IdentityTransform {
     fn apply_origin(&self) -> Vec2 {
         Vec2::ZERO
@@ -0,0 +1,215 @@
+use super::*;
+
+/// Region lit by a light at `viewpoint` reaching up to `radius`, e.g. for lighting or fog of war.
+///
+/// Returns the boundary of the lit region counter-clockwise around the viewpoint. Rays are cast
+/// towards the silhouettes of the obstacles (found with extreme points) and towards their
+/// boundary points in `segments` directions, together with `segments` rays evenly spread around
+/// the circle. Corners of polygons are exact as long as `segments` directions hit each of them,
+/// curved boundaries are approximated. Obstacles containing the viewpoint are ignored.
+///
+/// Obstacles are culled by their bounding boxes, both against the lit circle and against each
+/// ray, so only nearby obstacles in the way of a ray are cast against.
+///
+/// # Example
+/// ```
+/// # use auburn::col2d::*;
+/// let wall = Box2d::with_halfdims(0.5, 2.0);
+/// let t = Vec2::new(3.0, 0.0);
+/// let lit = visibility_polygon(Vec2::ZERO, 10.0, [(&wall, &t)], 64);
+///
+/// assert!(polygon_contains_point(&lit, Vec2::new(2.0, 0.0)));
+/// assert!(!polygon_contains_point(&lit, Vec2::new(5.0, 0.0)));
+/// assert!(polygon_contains_point(&lit, Vec2::new(0.0, 5.0)));
+/// ```
+pub fn visibility_polygon<'a, S, T>(
+    viewpoint: Vec2,
+    radius: f32,
+    obstacles: impl IntoIterator<Item = (&'a S, &'a T)>,
+    segments: usize,
+) -> Vec<Vec2>
+where
+    S: RayCast2d + ExtremePointT2d<T> + 'a,
+    T: Transformation2d + 'a,
+{
+    let lit_bounds = Aabb2d::from_center_halfsize(viewpoint, Vec2::splat(radius));
+    let obstacles: Vec<(Collider2d<S, T>, Box2d, Vec2)> = obstacles
+        .into_iter()
+        .map(Collider2d::from)
+        .filter_map(|o| {
+            let aabb = o.shape.bounding_box_t(o.transform);
+            aabb.intersects(&lit_bounds)
+                .then(|| (o, Box2d::new(aabb.halfsize()), aabb.center()))
+        })
+        .collect();
+    let directions = circle_directions(segments);
+
+    let mut angles: Vec<f32> = directions.iter().map(|d| d.to_angle()).collect();
+    let angle_of = |p: Vec2| (p - viewpoint).to_angle();
+    for (obstacle, _, _) in &obstacles {
+        let mut points: Vec<Vec2> = directions
+            .iter()
+            .map(|&d| obstacle.extreme_point(d))
+            .collect();
+        points.dedup();
+        angles.extend(points.into_iter().map(angle_of));
+        // Rays just past the silhouette continue behind the obstacle.
+        for side in [1.0, -1.0] {
+            if let Some(tangent) = silhouette(obstacle, viewpoint, side) {
+                angles.extend(angles_around(angle_of(tangent)));
+            }
+        }
+    }
+
+    cast_rays(viewpoint, radius, angles, |ray, mut distance| {
+        for (o, aabb, center) in &obstacles {
+            if aabb.cast_ray_t(center, ray, distance).is_none() {
+                continue;
+            }
+            if let Some(hit) = o.shape.cast_ray_t(o.transform, ray, distance) {
+                if hit.normal != Vec2::ZERO {
+                    distance = hit.distance;
+                }
+            }
+        }
+        distance
+    })
+}
+
+const ANGLE_EPSILON: f32 = 1e-4;
+
+/// `segments` directions evenly spread around the circle, at least 3.
+pub(crate) fn circle_directions(segments: usize) -> Vec<Vec2> {
+    let segments = segments.max(3);
+    (0..segments)
+        .map(|i| Vec2::from_angle(core::f32::consts::TAU * i as f32 / segments as f32))
+        .collect()
+}
+
+/// `angle` with the angles just before and after it, for rays passing a corner on either side.
+pub(crate) fn angles_around(angle: f32) -> [f32; 3] {
+    [angle - ANGLE_EPSILON, angle, angle + ANGLE_EPSILON].map(wrap_angle)
+}
+
+/// Boundary of the lit region through rays from `viewpoint` at `angles`, `cast` shortens a ray
+/// of length `radius` to where it is blocked.
+pub(crate) fn cast_rays(
+    viewpoint: Vec2,
+    radius: f32,
+    mut angles: Vec<f32>,
+    cast: impl Fn(&Ray2d, f32) -> f32,
+) -> Vec<Vec2> {
+    angles.sort_by(f32::total_cmp);
+    angles.dedup_by(|a, b| *a - *b < 0.1 * ANGLE_EPSILON);
+    angles
+        .into_iter()
+        .map(|angle| {
+            let ray = Ray2d::new(viewpoint, Vec2::from_angle(angle));
+            ray.at(cast(&ray, radius))
+        })
+        .collect()
+}
+
+/// Wraps `angle` into `(-PI, PI]`, the range of [Vec2::to_angle].
+fn wrap_angle(angle: f32) -> f32 {
+    use core::f32::consts::{PI, TAU};
+    if angle > PI {
+        angle - TAU
+    } else if angle <= -PI {
+        angle + TAU
+    } else {
+        angle
+    }
+}
+
+/// Boundary point of `obstacle` where a line from `viewpoint` touches it, on the left for
+/// `side == 1.0` and on the right for `side == -1.0`.
+fn silhouette<S, T>(obstacle: &Collider2d<S, T>, viewpoint: Vec2, side: f32) -> Option<Vec2>
+where
+    S: ExtremePointT2d<T>,
+    T: Transformation2d,
+{
+    let towards = obstacle.transform.apply_origin() - viewpoint;
+    if towards == Vec2::ZERO {
+        return None;
+    }
+    let mut point = obstacle.extreme_point(side * towards.perp());
+    // Each step moves to the extreme point past the line through the current one, which only
+    // turns further to the side, polygons converge in a few steps.
+    for _ in 0..16 {
+        let normal = side * (point - viewpoint).perp();
+        if normal == Vec2::ZERO {
+            return None;
+        }
+        let next = obstacle.extreme_point(normal);
+        if (next - point).dot(normal) <= 1e-6 * normal.length_squared() {
+            return Some(point);
+        }
+        point = next;
+    }
+    Some(point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn empty() {
+        let lit = visibility_polygon::<Ball, Vec2>(Vec2::ONE, 2.0, [], 32);
+        assert_eq!(lit.len(), 32);
+        assert!(lit
+            .iter()
+            .all(|p| (p.distance(Vec2::ONE) - 2.0).abs() < 1e-5));
+    }
+
+    #[test_log::test]
+    fn box_corner_is_exact() {
+        let b = Box2d::with_halfdims(1.0, 1.0);
+        let t = Transform2d::from_translation(Vec2::new(4.0, 0.0))
+            .with_angle(core::f32::consts::FRAC_PI_4);
+        let lit = visibility_polygon(Vec2::ZERO, 10.0, [(&b, &t)], 16);
+
+        let corner = Vec2::new(4.0 - core::f32::consts::SQRT_2, 0.0);
+        assert!(lit.iter().any(|p| p.distance(corner) < 1e-4), "{lit:?}");
+        assert!(!polygon_contains_point(&lit, Vec2::new(8.0, 0.0)));
+        assert!(!polygon_contains_point(&lit, Vec2::new(8.0, 1.5)));
+        assert!(polygon_contains_point(&lit, Vec2::new(8.0, 4.0)));
+    }
+
+    #[test_log::test]
+    fn ball_shadow() {
+        let ball = Ball::with_radius(1.0);
+        let t = Vec2::new(0.0, 3.0);
+        let lit = visibility_polygon(Vec2::ZERO, 10.0, [(&ball, &t)], 64);
+
+        // The shadow widens behind the ball, its edges touch the ball at the tangent points.
+        let tangent = Vec2::new(8.0f32.sqrt() / 3.0, 8.0 / 3.0);
+        assert!(lit.iter().any(|p| p.distance(tangent) < 0.05), "{lit:?}");
+        assert!(!polygon_contains_point(&lit, Vec2::new(0.0, 9.0)));
+        assert!(!polygon_contains_point(&lit, Vec2::new(2.5, 8.0)));
+        assert!(polygon_contains_point(&lit, Vec2::new(3.5, 8.0)));
+    }
+
+    #[test_log::test]
+    fn far_obstacles_are_culled() {
+        let ball = Ball::with_radius(1.0);
+        let far: Vec<Vec2> = (0..100).map(|i| Vec2::new(20.0 + i as f32, 0.0)).collect();
+        let lit = visibility_polygon(Vec2::ZERO, 10.0, far.iter().map(|t| (&ball, t)), 32);
+        assert_eq!(
+            lit,
+            visibility_polygon::<Ball, Vec2>(Vec2::ZERO, 10.0, [], 32)
+        );
+    }
+
+    #[test_log::test]
+    fn inside_obstacle_is_ignored() {
+        let shapes = [
+            (Shape2d::ball(1.0), Vec2::ZERO),
+            (Shape2d::rect(0.5, 0.5), Vec2::new(-3.0, 0.0)),
+        ];
+        let lit = visibility_polygon(Vec2::ZERO, 5.0, shapes.iter().map(|(s, t)| (s, t)), 32);
+        assert!(polygon_contains_point(&lit, Vec2::new(4.0, 0.0)));
+        assert!(!polygon_contains_point(&lit, Vec2::new(-4.0, 0.0)));
+    }
+}
@@ -4,6 +4,7 @@
 //! * [Ball] - ball
 //! * [Box3d] - 3D box
 //! * [Cylinder3d] - 3D cylinder
+//! * [HalfSpace3d] - infinite ground plane
 //!
 //! # Collision and Resolution
 //! * [Collides3d::collides]
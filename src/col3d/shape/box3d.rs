@@ -50,8 +50,10 @@ impl ExtremePoint3d for Box3d {
     }
 }
 
+#[cfg(minkowski)]
 impl MinkowskiNegationIsIdentity for Box3d {}
 
+#[cfg(minkowski)]
 impl MinkowskiSum<Box3d> for Box3d {
     type Output = Self;
 
@@ -281,6 +283,8 @@ mod tests {
         assert_relative_eq!(b.sdf_rel(&Point, &n5), 0.1414213);
     }
 
+    // Box3d v Box3d goes through the Minkowski defaults.
+    #[cfg(minkowski)]
     #[test_log::test]
     fn box_v_box_no_collision() {
         let b = Box3d::with_halfdims(0.5, 0.5, 0.0);
@@ -289,6 +293,7 @@ mod tests {
         assert_eq!(b.penetrates_rel(&b, &delta), None);
     }
 
+    #[cfg(minkowski)]
     #[test_log::test]
     fn box_v_box_perfect_overlap() {
         let b = Box3d::with_halfdims(0.5, 0.5, 0.5);
@@ -298,6 +303,7 @@ mod tests {
         assert!(b.penetrates_rel(&b, &delta).is_some());
     }
 
+    #[cfg(minkowski)]
     #[test_log::test]
     fn box_v_box_collision() {
         let b = Box3d::with_halfdims(0.5, 0.5, 8.0);
@@ -50,6 +50,9 @@ impl MinkowskiSum<Cylinder3d> for Cylinder3d {
 
 impl MinkowskiNegationIsIdentity for Cylinder3d {}
 
+impl_half_space3d_v!(Cylinder3d);
+impl_v_half_space3d!(Cylinder3d);
+
 impl CollidesRel3d<Point> for Cylinder3d {
     fn collides_rel(&self, t: &Point, rel: &impl Transformation3d) -> bool {
         let o = rel.apply_origin();
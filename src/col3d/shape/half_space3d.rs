@@ -0,0 +1,204 @@
+use super::*;
+
+/// Infinite solid region `normal · p <= offset`, e.g. a ground plane.
+///
+/// See [crate::col2d::HalfSpace2d] for the 2D counterpart.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+pub struct HalfSpace3d {
+    /// Unit normal pointing out of the solid region.
+    pub normal: Vec3,
+    /// Signed distance of the boundary from the origin along [HalfSpace3d::normal].
+    pub offset: f32,
+}
+
+impl HalfSpace3d {
+    /// Half-space `normal · p <= offset`, `normal` does not need to be normalized.
+    ///
+    /// Panics if `normal` is zero.
+    pub fn new(normal: Vec3, offset: f32) -> Self {
+        let length = normal.length();
+        assert!(length > 0.0, "half-space normal must be non-zero");
+        Self {
+            normal: normal / length,
+            offset: offset / length,
+        }
+    }
+
+    /// Half-space whose boundary passes through `point`.
+    ///
+    /// Panics if `normal` is zero.
+    pub fn from_point_normal(point: Vec3, normal: Vec3) -> Self {
+        assert!(normal != Vec3::ZERO, "half-space normal must be non-zero");
+        let normal = normal.normalize();
+        Self {
+            normal,
+            offset: normal.dot(point),
+        }
+    }
+
+    /// Signed distance of `point` from the boundary, negative inside.
+    pub fn signed_distance(&self, point: Vec3) -> f32 {
+        self.normal.dot(point) - self.offset
+    }
+
+    /// The half-space transformed by `rel`, i.e. as seen from a shape `rel` is relative to.
+    pub(crate) fn seen_from(&self, rel: &impl Transformation3d) -> Self {
+        let origin = rel.apply_origin();
+        Self::from_point_normal(
+            rel.apply(self.offset * self.normal),
+            rel.apply(self.normal) - origin,
+        )
+    }
+
+    /// Signed distance of the deepest point of `b` transformed by `rel`.
+    pub(crate) fn sdf_extreme_point(
+        &self,
+        b: &impl ExtremePoint3d,
+        rel: &impl Transformation3d,
+    ) -> f32 {
+        // Transformation3d has no normal transform, the support direction in `b`'s space is the
+        // transposed linear part applied to the world direction.
+        let origin = rel.apply_origin();
+        let direction = -self.normal;
+        let local_direction = Vec3::new(
+            (rel.apply(Vec3::X) - origin).dot(direction),
+            (rel.apply(Vec3::Y) - origin).dot(direction),
+            (rel.apply(Vec3::Z) - origin).dot(direction),
+        );
+        self.signed_distance(rel.apply(b.extreme_point(local_direction)))
+    }
+}
+
+/// Implements queries between a half-space and each of `$b`, using the deepest point of `$b`.
+macro_rules! impl_half_space3d_v {
+    ($($b:ty),+) => {
+        $(
+            impl CollidesRel3d<$b> for HalfSpace3d {
+                fn collides_rel(&self, b: &$b, rel: &impl Transformation3d) -> bool {
+                    self.sdf_extreme_point(b, rel) < 0.0
+                }
+            }
+
+            impl PenetratesRel3d<$b> for HalfSpace3d {
+                fn penetrates_rel(&self, b: &$b, rel: &impl Transformation3d) -> Option<Vec3> {
+                    let sdf = self.sdf_extreme_point(b, rel);
+                    (sdf < 0.0).then(|| sdf * self.normal)
+                }
+            }
+
+            impl SdfRel3d<$b> for HalfSpace3d {
+                fn sdf_rel(&self, b: &$b, rel: &impl Transformation3d) -> f32 {
+                    self.sdf_extreme_point(b, rel)
+                }
+            }
+
+            impl SdfvRel3d<$b> for HalfSpace3d {
+                fn sdfv_rel(&self, b: &$b, rel: &impl Transformation3d) -> Vec3 {
+                    self.sdf_extreme_point(b, rel) * self.normal
+                }
+            }
+        )+
+    };
+}
+
+impl_half_space3d_v!(Point, Ball, Box3d);
+
+/// Implements queries between each of `$a` and a half-space by moving the half-space into the
+/// space of `$a`.
+macro_rules! impl_v_half_space3d {
+    ($($a:ty),+) => {
+        $(
+            impl CollidesRel3d<HalfSpace3d> for $a {
+                fn collides_rel(&self, b: &HalfSpace3d, rel: &impl Transformation3d) -> bool {
+                    b.seen_from(rel).sdf_extreme_point(self, &Vec3::ZERO) < 0.0
+                }
+            }
+
+            impl PenetratesRel3d<HalfSpace3d> for $a {
+                fn penetrates_rel(
+                    &self,
+                    b: &HalfSpace3d,
+                    rel: &impl Transformation3d,
+                ) -> Option<Vec3> {
+                    let b = b.seen_from(rel);
+                    let sdf = b.sdf_extreme_point(self, &Vec3::ZERO);
+                    (sdf < 0.0).then(|| -sdf * b.normal)
+                }
+            }
+
+            impl SdfRel3d<HalfSpace3d> for $a {
+                fn sdf_rel(&self, b: &HalfSpace3d, rel: &impl Transformation3d) -> f32 {
+                    b.seen_from(rel).sdf_extreme_point(self, &Vec3::ZERO)
+                }
+            }
+
+            impl SdfvRel3d<HalfSpace3d> for $a {
+                fn sdfv_rel(&self, b: &HalfSpace3d, rel: &impl Transformation3d) -> Vec3 {
+                    let b = b.seen_from(rel);
+                    -b.sdf_extreme_point(self, &Vec3::ZERO) * b.normal
+                }
+            }
+        )+
+    };
+}
+
+impl_v_half_space3d!(Point, Ball, Box3d);
+
+// Used by `Cylinder3d`, which is not part of the module tree yet.
+#[allow(unused_imports)]
+pub(crate) use {impl_half_space3d_v, impl_v_half_space3d};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_log::test]
+    fn ground_v_box() {
+        let ground = HalfSpace3d::from_point_normal(Vec3::ZERO, Vec3::Z);
+        let b = Box3d::with_halfdims(1.0, 1.0, 1.0);
+        assert_eq!(ground.sdf_rel(&b, &Vec3::new(5.0, -5.0, 3.0)), 2.0);
+        assert_eq!(
+            ground.penetrates_rel(&b, &Vec3::new(0.0, 0.0, 0.5)),
+            Some(Vec3::new(0.0, 0.0, -0.5))
+        );
+        assert!(!ground.collides_rel(&Point, &Vec3::new(0.0, 0.0, 0.1)));
+    }
+
+    #[test_log::test]
+    fn tilted_ground_v_ball() {
+        let ground = HalfSpace3d::new(Vec3::new(1.0, 0.0, 1.0), 0.0);
+        let ball = Ball::new(1.0);
+        let sdfv = ground.sdfv_rel(&ball, &Vec3::new(1.0, 0.0, 1.0));
+        let expected = (2f32.sqrt() - 1.0) * ground.normal;
+        assert!(sdfv.abs_diff_eq(expected, 1e-5));
+        assert!(ground.collides_rel(&ball, &Vec3::new(0.5, 0.0, 0.0)));
+    }
+
+    #[test_log::test]
+    fn reversed_pairs_agree() {
+        let ground = HalfSpace3d::new(Vec3::new(0.0, 1.0, 2.0), 1.0);
+        let b = Box3d::with_halfdims(1.0, 0.5, 0.25);
+        let ball = Ball::new(0.75);
+        for z in [-1.0, 0.5, 1.0, 3.0] {
+            let rel = Vec3::new(1.0, -2.0, z);
+            assert_eq!(
+                b.collides_rel(&ground, &-rel),
+                ground.collides_rel(&b, &rel)
+            );
+            assert!((b.sdf_rel(&ground, &-rel) - ground.sdf_rel(&b, &rel)).abs() < 1e-5);
+            assert!(b
+                .sdfv_rel(&ground, &-rel)
+                .abs_diff_eq(-ground.sdfv_rel(&b, &rel), 1e-5));
+            match (
+                ball.penetrates_rel(&ground, &-rel),
+                ground.penetrates_rel(&ball, &rel),
+            ) {
+                (Some(a), Some(b)) => assert!(a.abs_diff_eq(-b, 1e-5)),
+                (a, b) => assert_eq!(a, b),
+            }
+        }
+        assert!(Point.collides_rel(&ground, &Vec3::new(0.0, 0.0, 1.0)));
+        assert!(!Point.collides_rel(&ground, &Vec3::new(0.0, 0.0, -1.0)));
+    }
+}
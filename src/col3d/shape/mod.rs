@@ -2,6 +2,8 @@ use super::*;
 
 mod ball3d;
 mod box3d;
+mod half_space3d;
 mod point3d;
 
 pub use box3d::*;
+pub use half_space3d::*;
@@ -80,29 +80,49 @@ macro_rules! error {
 #[cfg(not(feature = "logging"))]
 #[macro_export]
 macro_rules! trace {
-    ($($arg:tt)*) => {};
+    ($($arg:tt)*) => {
+        if false {
+            let _ = format_args!($($arg)*);
+        }
+    };
 }
 
 #[cfg(not(feature = "logging"))]
 #[macro_export]
 macro_rules! debug {
-    ($($arg:tt)*) => {};
+    ($($arg:tt)*) => {
+        if false {
+            let _ = format_args!($($arg)*);
+        }
+    };
 }
 
 #[cfg(not(feature = "logging"))]
 #[macro_export]
 macro_rules! info {
-    ($($arg:tt)*) => {};
+    ($($arg:tt)*) => {
+        if false {
+            let _ = format_args!($($arg)*);
+        }
+    };
 }
 
 #[cfg(not(feature = "logging"))]
 #[macro_export]
 macro_rules! warn {
-    ($($arg:tt)*) => {};
+    ($($arg:tt)*) => {
+        if false {
+            let _ = format_args!($($arg)*);
+        }
+    };
 }
 
 #[cfg(not(feature = "logging"))]
 #[macro_export]
 macro_rules! error {
-    ($($arg:tt)*) => {};
+    ($($arg:tt)*) => {
+        if false {
+            let _ = format_args!($($arg)*);
+        }
+    };
 }
@@ -52,6 +52,7 @@ impl<E> Default for Ledger<E> {
 }
 
 pub struct CallbackPublisher<E> {
+    #[allow(clippy::type_complexity)]
     subscribers: Vec<Box<dyn Fn(&E) + Sync + Send>>,
 }
 
@@ -1,6 +1,6 @@
 use lk_math::vector::{V2i32, Vector};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Rect2i32 {
     pub min: V2i32,
     pub max: V2i32,
@@ -11,6 +11,18 @@ impl Rect2i32 {
         Self { min, max }
     }
 
+    /// Grows to include `p`.
+    pub fn cover(&mut self, p: V2i32) {
+        self.min = V2i32::from_xy(self.min.x().min(p.x()), self.min.y().min(p.y()));
+        self.max = V2i32::from_xy(self.max.x().max(p.x()), self.max.y().max(p.y()));
+    }
+
+    /// Whether `p` is inside, `min` and `max` included.
+    pub fn contains(&self, p: V2i32) -> bool {
+        (self.min.x()..=self.max.x()).contains(&p.x())
+            && (self.min.y()..=self.max.y()).contains(&p.y())
+    }
+
     pub fn pad(self, padding: V2i32) -> Self {
         Self {
             min: self.min - padding,
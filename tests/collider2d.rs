@@ -1,5 +1,5 @@
 use auburn::utils::approx::Approx;
-use auburn::{assert_approx_eq, col2d::*, debug, info};
+use auburn::{assert_approx_eq, col2d::*, info};
 
 #[test_log::test]
 fn collider2d_box() {
@@ -176,6 +176,7 @@ fn point_v_box_penetrates() {
 }
 
 #[test_log::test]
+#[allow(unused_variables)]
 fn point_v_box_sdf() {
     let b = Box2d::with_halfdims(2.0, 1.0);
     let y1 = Vec2::new(0.0, 0.0);
@@ -196,6 +197,12 @@ fn point_v_box_sdf() {
     assert_relative_eq!(b.sdf_rel(&Point, &n5), 0.1414213);
 }
 
+#[test_log::test]
+fn point_v_box_sdf_below_center() {
+    let b = Box2d::with_halfdims(2.0, 1.0);
+    assert_eq!(b.sdf_rel(&Point, &Vec2::new(0.0, -0.5)), -0.5);
+}
+
 #[test_log::test]
 fn box_v_box_no_collision() {
     let b = Box2d::with_halfdims(0.5, 0.5);
@@ -264,7 +271,9 @@ fn poly_from_box_v_poly_from_box_collision_random_case_1() {
         transform: &t2,
     };
     // let sdf = col1.sdf(col2);
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     // assert!(sdf > 0.0);
     // assert!(sdf < 0.1);
     assert!(!collides);
@@ -292,7 +301,9 @@ fn box_v_box_collision_random_case_1() {
         shape: &b,
         transform: &t2,
     };
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     assert!(!collides);
     let distance = sdfv.length();
     trace!("sdfv length: {distance}");
@@ -321,9 +332,12 @@ fn box_v_box_collision_random_case_2() {
         shape: &b,
         transform: &t2,
     };
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     assert!(collides);
     let distance = if collides { -1.0 } else { 1.0 } * sdfv.length();
+    trace!("signed distance: {distance}");
     box_box_transform2d_distance_bounds_check(col1, col2);
 }
 
@@ -348,7 +362,9 @@ fn box_v_box_collision_random_case_3() {
         shape: &b,
         transform: &t2,
     };
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     assert!(!collides);
     let distance = sdfv.length();
     trace!("sdfv length: {distance}");
@@ -376,7 +392,9 @@ fn box_v_box_collision_random_case_4() {
         shape: &b,
         transform: &t2,
     };
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     assert!(!collides);
     let distance = sdfv.length();
     trace!("sdfv length: {distance}");
@@ -406,7 +424,9 @@ fn box_v_box_collision_random_case_5() {
         shape: &b,
         transform: &t2,
     };
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     assert!(!collides);
     box_box_transform2d_distance_bounds_check(col1, col2);
     assert!(sdfv.length() > 1.0);
@@ -433,7 +453,9 @@ fn box_v_box_collision_random_case_6() {
         shape: &b,
         transform: &t2,
     };
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     assert!(!collides);
     box_box_transform2d_distance_bounds_check(col1, col2);
     assert!(sdfv.length() > 1.0);
@@ -460,7 +482,9 @@ fn box_v_box_collision_random_case_7() {
         shape: &b,
         transform: &t2,
     };
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     assert!(!collides);
     box_box_transform2d_distance_bounds_check(col1, col2);
     assert!(sdfv.length() > 1.0);
@@ -494,7 +518,9 @@ fn box_v_box_collision_diamond_with_offset() {
             transform: &t2,
         };
         // let sdf = col1.sdf(col2);
-        let (collides, sdfv) = col1.sdfv(col2);
+        let result = col1.sdfv(col2);
+        let collides = result.collides().unwrap();
+        let sdfv = result.sdfv().unwrap();
         assert!(!collides);
         if !sdfv.approx_eq_tolerance(&Vec2::new(padding, padding), 10e-6) {
             auburn::error!("expected offset: {padding:?}, got sdfv: {sdfv:?}");
@@ -533,7 +559,9 @@ fn box_v_box_collision_diamond_with_offset_mod1() {
             transform: &t2,
         };
         // let sdf = col1.sdf(col2);
-        let (collides, sdfv) = col1.sdfv(col2);
+        let result = col1.sdfv(col2);
+        let collides = result.collides().unwrap();
+        let sdfv = result.sdfv().unwrap();
         assert!(!collides);
         if !sdfv.approx_eq_tolerance(&Vec2::new(padding, padding), 10e-1) {
             auburn::error!(
@@ -603,7 +631,9 @@ fn box_v_point_case_1() {
         shape: &b,
         transform: &t2,
     };
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     assert!(!collides);
     trace!("sdfv: {sdfv}");
     assert_approx_eq!(sdfv, Vec2::new(0.5, 0.0));
@@ -626,7 +656,9 @@ fn box_v_point_case_2() {
         shape: &b,
         transform: &t2,
     };
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     assert!(!collides);
     trace!("sdfv: {sdfv}");
     assert_approx_eq!(sdfv, Vec2::new(1.0 - core::f32::consts::FRAC_1_SQRT_2, 0.0));
@@ -1,11 +1,8 @@
 mod collider2d;
 pub mod common;
 
-use approx::assert_relative_eq;
-use auburn::assert_approx_eq;
 use auburn::col2d::*;
 use auburn::trace;
-use auburn::utils::approx::Approx;
 use glam::Quat;
 
 #[test_log::test]
@@ -30,7 +27,9 @@ fn box_v_ball_case_1() {
         shape: &ball,
         transform: &t2,
     };
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     assert!(!collides);
     trace!("sdfv: {}", sdfv);
     assert!(sdfv.length() < 0.15);
@@ -58,7 +57,9 @@ fn ball_v_box_case_1() {
         shape: &boxx,
         transform: &t2,
     };
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     assert!(!collides);
     trace!("sdfv: {}", sdfv);
     assert!(sdfv.length() < 0.18);
@@ -78,7 +79,9 @@ fn ball_v_box_case_2() {
         shape: &boxx,
         transform: &t2,
     };
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     assert!(!collides);
     trace!("sdfv: {}", sdfv);
     assert!(sdfv.length() < 0.18);
@@ -183,7 +183,7 @@ fn sdfv_x() {
         let b_pos = Translate2d::from(offset);
         let a = (&a_shape, &a_pos);
         let b = (&b_shape, &b_pos);
-        assert_eq!(expect, a.sdfv(b));
+        assert_eq!(QueryResult2d::from_sdfv(expect.0, expect.1), a.sdfv(b));
     }
 }
 
@@ -304,7 +304,7 @@ fn sdfv_diag() {
         let b_pos = Translate2d::from(offset);
         let a = (&a_shape, &a_pos);
         let b = (&b_shape, &b_pos);
-        assert_eq!(expect, a.sdfv(b));
+        assert_eq!(QueryResult2d::from_sdfv(expect.0, expect.1), a.sdfv(b));
     }
 }
 
@@ -324,7 +324,9 @@ fn triangle_v_box_case_1() {
         shape: &Poly2d::regular_upright(3, 1.0),
         transform: &t2,
     };
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     assert!(collides);
     trace!("sdfv: {sdfv}");
     assert_approx_eq!(sdfv, Vec2::new(0.0, -0.4305557));
@@ -346,7 +348,8 @@ fn upright_triangle_v_box_descend() {
                 scale: Vec2::ONE,
             },
         };
-        let (collides, sdfv) = col1.sdfv(col2);
+        let result = col1.sdfv(col2);
+        let sdfv = result.sdfv().unwrap();
         // assert!(collides);
         trace!("sdfv: {sdfv}");
         let expected = Vec2::new(0.0, offset - 1.0);
@@ -375,7 +378,8 @@ fn upright_triangle_v_box_descend_slight_offset() {
                 scale: Vec2::ONE,
             },
         };
-        let (collides, sdfv) = col1.sdfv(col2);
+        let result = col1.sdfv(col2);
+        let sdfv = result.sdfv().unwrap();
         // assert!(collides);
         trace!("sdfv: {sdfv}");
         let expected = Vec2::new(0.0, offset - 1.0);
@@ -404,7 +408,9 @@ fn triangle_v_box_case_2() {
         shape: &Poly2d::regular_upright(3, 1.0),
         transform: &t2,
     };
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     assert!(collides);
     trace!("sdfv: {sdfv}");
     assert_approx_eq!(sdfv, Vec2::new(0.95396185, 0.55077016));
@@ -426,7 +432,9 @@ fn triangle_v_box_case_3() {
         shape: &Poly2d::regular_upright(3, 1.0),
         transform: &t2,
     };
-    let (collides, sdfv) = col1.sdfv(col2);
+    let result = col1.sdfv(col2);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     assert!(collides);
     trace!("sdfv: {sdfv}");
     assert_approx_eq!(sdfv, Vec2::new(0.0, -1.0972223));
@@ -1,18 +1,7 @@
-use std::sync::LazyLock;
+#![allow(dead_code)]
 
 use auburn::{col2d::*, trace};
 
-#[cfg(obsolete)]
-pub static LOGGER: LazyLock<()> = LazyLock::new(|| {
-    env_logger::builder()
-        .format_timestamp(None)
-        .format_module_path(false)
-        .format_target(false)
-        .is_test(true)
-        .try_init()
-        .unwrap();
-});
-
 pub struct F32Iterator {
     min: f32,
     max: f32,
@@ -53,11 +42,13 @@ pub fn box_box_transform2d_distance_bounds_check<'a>(
     a: Collider2d<'a, Box2d, Transform2d>,
     b: Collider2d<'a, Box2d, Transform2d>,
 )
-// fn box_box_necessary_distance<'a, T>(a: T, b: T, sdfv: (bool, Vec2))
+// fn box_box_necessary_distance<'a, T>(a: T, b: T, sdfv: QueryResult2d)
 // where
 //     T = Collider2d<'a, Box2d, Transform2d>,
 {
-    let (collides, sdfv) = a.sdfv(b);
+    let result = a.sdfv(b);
+    let collides = result.collides().unwrap();
+    let sdfv = result.sdfv().unwrap();
     let distance = if collides { -1.0 } else { 1.0 } * sdfv.length();
 
     trace!("sdfv length: {distance}");
@@ -85,3 +76,35 @@ pub fn box_box_transform2d_distance_bounds_check<'a>(
     // trace!("sdf: {sdf}");
     // assert_approx_eq!(distance, sdf);
 }
+
+/// Compiles only if every query is implemented for `A` v `B`.
+pub fn implements_all_queries<A, B, T>()
+where
+    A: CollidesRel2d<B, T>
+        + PenetratesRel2d<B, T>
+        + DistanceToRel2d<B, T>
+        + SdfvRel2d<B, T>
+        + SdfRel2d<B>,
+    T: Transformation2d,
+{
+}
+
+/// Checks every pair of `$shapes` against transformation `$t`.
+#[allow(unused_macros)]
+macro_rules! check_matrix {
+    ($t:ty; $shapes:tt) => {
+        $crate::common::check_matrix!(@rows $t; $shapes; $shapes);
+    };
+    (@rows $t:ty; [$($a:ty),+]; $shapes:tt) => {
+        $(
+            $crate::common::check_matrix!(@row $t; $a; $shapes);
+        )+
+    };
+    (@row $t:ty; $a:ty; [$($b:ty),+]) => {
+        $(
+            $crate::common::implements_all_queries::<$a, $b, $t>();
+        )+
+    };
+}
+#[allow(unused_imports)]
+pub(crate) use check_matrix;
@@ -0,0 +1,85 @@
+mod common;
+
+use auburn::col2d::*;
+use common::{check_matrix, implements_all_queries};
+
+/// Triangle with vertices on the unit circle, pointing towards `+x`.
+#[derive(Debug, Clone)]
+pub struct Triangle;
+
+impl Triangle {
+    fn points() -> [Vec2; 3] {
+        let angle = 2.0 * std::f32::consts::PI / 3.0;
+        [
+            Vec2::X,
+            Vec2::new(angle.cos(), angle.sin()),
+            Vec2::new(angle.cos(), -angle.sin()),
+        ]
+    }
+}
+
+impl ExtremePoint2d for Triangle {
+    fn extreme_point(&self, direction: Vec2) -> Vec2 {
+        Self::points()
+            .into_iter()
+            .max_by(|a, b| a.dot(direction).total_cmp(&b.dot(direction)))
+            .unwrap()
+    }
+}
+
+impl SymmetricBoundingBox2d for Triangle {
+    fn symmetric_bounding_box(&self) -> Box2d {
+        Box2d::with_halfdims(1.0, 1.0)
+    }
+}
+
+auburn::convex_shape!(Triangle);
+auburn::shape_union!(GameShape; Ball, Box2d; Triangle);
+
+#[test]
+fn custom_shapes_implement_every_query() {
+    check_matrix!(Transform2d; [Point, Ball, Box2d, Poly2d, RoundedBox2d, Triangle, GameShape]);
+    implements_all_queries::<GameShape, GameShape, Vec2>();
+}
+
+#[test_log::test]
+fn custom_shape_matches_equivalent_poly() {
+    let triangle = Triangle;
+    let poly = Poly2d::new(&Triangle::points());
+    let ball = Ball::with_radius(0.5);
+    let rel = Transform2d::from_translation(Vec2::new(1.2, 0.3)).with_angle(0.2);
+
+    let expected = poly.sdfv_rel(&ball, &rel);
+    assert_eq!(triangle.sdfv_rel(&ball, &rel), expected);
+    assert_eq!(
+        GameShape::Triangle(Triangle).sdfv_rel(&GameShape::Ball(ball), &rel),
+        expected
+    );
+    assert_eq!(ball.sdfv_rel(&triangle, &rel), ball.sdfv_rel(&poly, &rel));
+}
+
+#[test_log::test]
+fn union_bounding_box() {
+    assert_eq!(
+        GameShape::Triangle(Triangle).symmetric_bounding_box(),
+        Box2d::with_halfdims(1.0, 1.0)
+    );
+    assert_eq!(
+        GameShape::Ball(Ball::with_radius(2.0)).symmetric_bounding_box(),
+        Box2d::with_halfdims(2.0, 2.0)
+    );
+}
+
+#[test_log::test]
+fn union_colliders() {
+    let a = GameShape::Box2d(Box2d::with_halfdims(1.0, 1.0));
+    let b = GameShape::Triangle(Triangle);
+    let a_transform = Vec2::new(0.0, 0.0);
+    let b_transform = Vec2::new(1.5, 0.0);
+    assert!((&a, &a_transform).collides((&b, &b_transform)));
+    assert!(Collider2d::new(&a, &a_transform)
+        .penetrates(Collider2d::new(&b, &b_transform))
+        .is_some());
+    let b_transform = Vec2::new(2.5, 0.0);
+    assert!(!(&a, &a_transform).collides((&b, &b_transform)));
+}
@@ -0,0 +1,224 @@
+#![cfg(feature = "tilemap")]
+
+use std::collections::HashMap;
+
+use auburn::col2d::*;
+use lk_math::vector::V2i32;
+
+const TMX: &str = include_str!("data/sample.tmx");
+const TMJ: &str = include_str!("data/sample_infinite.tmj");
+const LDTK: &str = include_str!("data/sample.ldtk");
+
+fn object<'a>(objects: &'a [MapObject2d], name: &str) -> &'a MapObject2d {
+    objects.iter().find(|o| o.name == name).unwrap()
+}
+
+fn contains(object: &MapObject2d, point: Vec2) -> bool {
+    object
+        .shapes
+        .iter()
+        .any(|(shape, t)| shape.contains_point_t(t, point))
+}
+
+#[test_log::test]
+fn tiled_tile_layer_with_flipped_tiles() {
+    let map = TiledMap::from_tmx(TMX).unwrap();
+    assert_eq!(map.tile_size(), Vec2::splat(16.0));
+    assert!(map.tilemap("collision", |_| 1).is_none());
+
+    // Tile 2 is a slope, each orientation gets its own id in the shape table.
+    let slope = TileShape2d::Slope45 {
+        flip_x: false,
+        flip_y: false,
+    };
+    let mut ids = HashMap::new();
+    let mut shapes = TileShapeTable2d::default();
+    let tilemap = map
+        .tilemap("ground", |tile| match tile.id {
+            1 => 1,
+            _ => {
+                let next = ids.len() as u8 + 2;
+                *ids.entry(tile).or_insert_with(|| {
+                    shapes.set(next, tile.shape(&slope));
+                    next
+                })
+            }
+        })
+        .unwrap()
+        .with_tile_shapes(shapes);
+
+    assert_eq!(ids.len(), 2);
+    assert_eq!(tilemap.get_tile(V2i32::from_xy(0, 0)), 0);
+    assert_eq!(tilemap.get_tile(V2i32::from_xy(0, -2)), 1);
+    assert_eq!(tilemap.get_tile(V2i32::from_xy(3, -2)), 1);
+    assert_eq!(tilemap.get_tile(V2i32::from_xy(1, -1)), 2);
+    assert_eq!(tilemap.get_tile(V2i32::from_xy(3, -1)), 3);
+
+    // Pixel rows grow downwards, the bottom row spans y in [-48, -32].
+    assert!(tilemap.contains_point(Vec2::new(8.0, -40.0)));
+    assert!(!tilemap.contains_point(Vec2::new(8.0, -24.0)));
+    // The slope rises towards +x, the flipped one towards -x.
+    assert!(tilemap.contains_point(Vec2::new(30.0, -20.0)));
+    assert!(!tilemap.contains_point(Vec2::new(18.0, -20.0)));
+    assert!(tilemap.contains_point(Vec2::new(50.0, -20.0)));
+    assert!(!tilemap.contains_point(Vec2::new(62.0, -20.0)));
+}
+
+#[test_log::test]
+fn tiled_objects() {
+    let map = TiledMap::from_tmx(TMX).unwrap();
+    assert!(map.objects("ground").is_none());
+    let objects = map.objects("collision").unwrap();
+    assert_eq!(objects.len(), 8);
+
+    let floor = object(&objects, "floor");
+    assert_eq!(floor.class, "solid");
+    assert!(matches!(floor.shapes[..], [(Shape2d::Box2d(_), _)]));
+    assert_eq!(floor.shapes[0].1.pos, Vec2::new(32.0, -40.0));
+    assert!(contains(floor, Vec2::new(60.0, -46.0)));
+
+    // Rotated 90° clockwise around its top-left corner, it now hangs to the left.
+    let rotated = object(&objects, "rotated");
+    assert!((rotated.shapes[0].1.pos - Vec2::new(-5.0, -10.0)).length() < 1e-4);
+    assert!(contains(rotated, Vec2::new(-8.0, -18.0)));
+    assert!(!contains(rotated, Vec2::new(8.0, -4.0)));
+
+    let coin = object(&objects, "coin");
+    assert_eq!(coin.class, "pickup");
+    assert!(matches!(coin.shapes[..], [(Shape2d::Ball(_), _)]));
+    assert_eq!(coin.shapes[0].1.pos, Vec2::new(44.0, -4.0));
+
+    let oval = object(&objects, "oval");
+    assert!(matches!(oval.shapes[..], [(Shape2d::Poly2d(_), _)]));
+    assert!(contains(oval, Vec2::new(15.0, -4.0)));
+    assert!(!contains(oval, Vec2::new(15.5, -7.5)));
+
+    let ramp = object(&objects, "ramp");
+    assert!(contains(ramp, Vec2::new(44.0, -28.0)));
+    assert!(!contains(ramp, Vec2::new(20.0, -20.0)));
+
+    let ledge = object(&objects, "ledge");
+    assert!(ledge.shapes.len() >= 2);
+    assert!(contains(ledge, Vec2::new(90.0, -4.0)));
+    assert!(contains(ledge, Vec2::new(68.0, -28.0)));
+    assert!(!contains(ledge, Vec2::new(90.0, -28.0)));
+
+    let rope = object(&objects, "rope");
+    assert_eq!(rope.shapes.len(), 2);
+    assert!((rope.shapes[1].1.pos - Vec2::new(10.0, -5.0)).length() < 1e-4);
+
+    assert!(object(&objects, "spawn").shapes.is_empty());
+}
+
+#[test_log::test]
+fn tiled_json_matches_tmx_conventions() {
+    let map = TiledMap::from_json(TMJ).unwrap();
+    assert_eq!(
+        map.layer_names().collect::<Vec<_>>(),
+        vec!["ground", "triggers"]
+    );
+
+    let mut flipped = Vec::new();
+    let tilemap = map
+        .tilemap("ground", |tile| {
+            if tile.id == 3 {
+                flipped.push(tile);
+            }
+            tile.id as u8
+        })
+        .unwrap();
+    for x in -16..0 {
+        assert_eq!(tilemap.get_tile(V2i32::from_xy(x, -15)), 1);
+    }
+    assert_eq!(tilemap.get_tile(V2i32::from_xy(0, -15)), 0);
+    assert_eq!(tilemap.get_tile(V2i32::from_xy(-1, -14)), 3);
+    assert_eq!(
+        flipped,
+        vec![MapTile2d {
+            id: 3,
+            flip_x: false,
+            flip_y: true,
+            flip_diagonal: true,
+        }]
+    );
+
+    // Offsets of the group and the layer add up.
+    let triggers = map.objects("triggers").unwrap();
+    let door = object(&triggers, "door");
+    assert_eq!(door.class, "trigger");
+    assert_eq!(door.shapes[0].1.pos, Vec2::new(-14.0, -224.0));
+}
+
+#[test_log::test]
+fn ldtk_layers() {
+    let project = LdtkProject::from_json(LDTK).unwrap();
+    assert_eq!(project.level_names().collect::<Vec<_>>(), vec!["Level_0"]);
+    assert!(project.tilemap("Level_1", "Collisions", |_| 1).is_none());
+    assert!(project.tilemap("Level_0", "Entities", |_| 1).is_none());
+
+    let collisions = project
+        .tilemap("Level_0", "Collisions", |tile| tile.id as u8)
+        .unwrap();
+    assert_eq!(collisions.tile_size(), Vec2::splat(8.0));
+    assert_eq!(collisions.get_tile(V2i32::from_xy(0, -2)), 1);
+    assert_eq!(collisions.get_tile(V2i32::from_xy(3, -1)), 2);
+    assert_eq!(collisions.get_tile(V2i32::from_xy(0, -1)), 0);
+    assert!(collisions.contains_point(Vec2::new(1.0, -23.0)));
+
+    let mut tiles = Vec::new();
+    project
+        .tilemap("Level_0", "Decoration", |tile| {
+            tiles.push(tile);
+            1
+        })
+        .unwrap();
+    assert_eq!(tiles.len(), 3);
+    assert!(!tiles[0].flip_x && tiles[1].flip_x && !tiles[1].flip_y);
+    assert!(tiles[2].flip_x && tiles[2].flip_y && !tiles[2].flip_diagonal);
+
+    let entities = project.objects("Level_0", "Entities").unwrap();
+    let player = object(&entities, "Player");
+    assert_eq!(player.shapes[0].1.pos, Vec2::new(20.0, -16.0));
+    assert!(matches!(player.shapes[0].0, Shape2d::Box2d(b) if b.halfsize == Vec2::new(4.0, 8.0)));
+}
+
+#[test_log::test]
+fn invalid_maps() {
+    assert!(TiledMap::from_tmx("<tileset/>").is_err());
+    assert!(TiledMap::from_tmx("<map").is_err());
+    assert!(TiledMap::from_json("{}").is_err());
+    assert!(LdtkProject::from_json("[]").is_err());
+
+    let invalid = |result: std::io::Result<()>| {
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    };
+    let tmx = |tile_size: &str, width: &str| {
+        let layer =
+            format!(r#"<layer name="a" width="{width}"><data encoding="csv">1,1</data></layer>"#);
+        TiledMap::from_tmx(&format!("<map {tile_size}>{layer}</map>")).map(|_| ())
+    };
+    assert!(tmx(r#"tilewidth="16" tileheight="16""#, "2").is_ok());
+    invalid(tmx(r#"tilewidth="16" tileheight="16""#, "0"));
+    invalid(tmx(r#"tilewidth="0" tileheight="16""#, "2"));
+    invalid(tmx(r#"tilewidth="16" tileheight="-16""#, "2"));
+
+    let json = |tile_size: &str, chunk_width: u32| {
+        let chunk = format!(r#"{{"x": 0, "y": 0, "width": {chunk_width}, "data": [1, 1]}}"#);
+        let layer = format!(r#"{{"type": "tilelayer", "name": "a", "chunks": [{chunk}]}}"#);
+        TiledMap::from_json(&format!(r#"{{{tile_size}, "layers": [{layer}]}}"#)).map(|_| ())
+    };
+    assert!(json(r#""tilewidth": 16, "tileheight": 16"#, 2).is_ok());
+    invalid(json(r#""tilewidth": 16, "tileheight": 16"#, 0));
+    invalid(json(r#""tilewidth": 16, "tileheight": 0"#, 2));
+
+    let ldtk = |grid_size: i32| {
+        let layer = format!(
+            r#"{{"__identifier": "a", "__type": "IntGrid", "__cWid": 2, "__gridSize": {grid_size}, "intGridCsv": [1, 1]}}"#
+        );
+        let level = format!(r#"{{"identifier": "L", "layerInstances": [{layer}]}}"#);
+        LdtkProject::from_json(&format!(r#"{{"levels": [{level}]}}"#)).map(|_| ())
+    };
+    assert!(ldtk(8).is_ok());
+    invalid(ldtk(0));
+    invalid(ldtk(-8));
+}
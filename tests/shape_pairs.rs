@@ -0,0 +1,69 @@
+mod common;
+
+use auburn::col2d::*;
+use common::check_matrix;
+
+#[test]
+fn every_pair_implements_every_query() {
+    check_matrix!(Vec2; [Point, Ball, Box2d, Poly2d, RoundedBox2d, Shape2d]);
+    check_matrix!(Translate2d; [Point, Ball, Box2d, Poly2d, RoundedBox2d, Shape2d]);
+    check_matrix!(Transform2d; [Point, Ball, Box2d, Poly2d, RoundedBox2d, Shape2d]);
+    check_matrix!(AxisTransform2d; [Point, Ball, Box2d, Poly2d, RoundedBox2d, Shape2d]);
+}
+
+#[test_log::test]
+fn reversed_pairs_agree() {
+    let ball = Ball::with_radius(0.5);
+    let poly = Poly2d::regular(6, 1.0);
+    let rounded_box = RoundedBox2d::new(Vec2::new(1.0, 0.5), 0.25);
+    let rel = Transform2d::from_translation(Vec2::new(1.2, 0.3)).with_angle(0.4);
+    let inv_rel = rel.inverse();
+
+    let forward = ball.sdfv_rel(&poly, &rel);
+    let backward = poly.sdfv_rel(&ball, &inv_rel);
+    assert_eq!(forward.collides(), backward.collides());
+    assert!((forward.sdf().unwrap() - backward.sdf().unwrap()).abs() < 0.01);
+
+    let forward = Point.sdfv_rel(&poly, &rel);
+    let backward = poly.sdfv_rel(&Point, &inv_rel);
+    assert_eq!(forward.collides(), backward.collides());
+    assert!((forward.sdf().unwrap() - backward.sdf().unwrap()).abs() < 0.01);
+    assert!((Point.sdf_rel(&poly, &rel) - poly.sdf_rel(&Point, &inv_rel)).abs() < 0.01);
+
+    let forward = ball.sdfv_rel(&rounded_box, &rel);
+    let backward = rounded_box.sdfv_rel(&ball, &inv_rel);
+    assert_eq!(forward.collides(), backward.collides());
+    assert!((forward.sdf().unwrap() - backward.sdf().unwrap()).abs() < 0.01);
+}
+
+#[test_log::test]
+fn shape_union_matches_variants() {
+    let rel = Transform2d::from_translation(Vec2::new(1.4, -0.2)).with_angle(0.3);
+    let shapes = [
+        Shape2d::ball(0.5),
+        Shape2d::rect(1.0, 0.5),
+        Shape2d::Poly2d(Poly2d::regular(5, 1.0)),
+    ];
+
+    for a in &shapes {
+        for b in &shapes {
+            let expected = match (a, b) {
+                (Shape2d::Ball(a), Shape2d::Ball(b)) => a.sdfv_rel(b, &rel),
+                (Shape2d::Ball(a), Shape2d::Box2d(b)) => a.sdfv_rel(b, &rel),
+                (Shape2d::Ball(a), Shape2d::Poly2d(b)) => a.sdfv_rel(b, &rel),
+                (Shape2d::Box2d(a), Shape2d::Ball(b)) => a.sdfv_rel(b, &rel),
+                (Shape2d::Box2d(a), Shape2d::Box2d(b)) => a.sdfv_rel(b, &rel),
+                (Shape2d::Box2d(a), Shape2d::Poly2d(b)) => a.sdfv_rel(b, &rel),
+                (Shape2d::Poly2d(a), Shape2d::Ball(b)) => a.sdfv_rel(b, &rel),
+                (Shape2d::Poly2d(a), Shape2d::Box2d(b)) => a.sdfv_rel(b, &rel),
+                (Shape2d::Poly2d(a), Shape2d::Poly2d(b)) => a.sdfv_rel(b, &rel),
+            };
+            assert_eq!(a.sdfv_rel(b, &rel), expected);
+        }
+    }
+
+    assert_eq!(
+        Point.sdfv_rel(&shapes[1], &rel),
+        Point.sdfv_rel(&Box2d::with_halfdims(1.0, 0.5), &rel)
+    );
+}
@@ -122,6 +122,7 @@ fn delta_transform_1() {
     assert_eq!(c, b);
 }
 
+#[allow(dead_code)]
 const RIGHT_ANGLES: &[f32] = &[
     0.0,
     std::f32::consts::FRAC_PI_2,